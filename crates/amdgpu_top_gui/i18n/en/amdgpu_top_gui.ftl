@@ -54,6 +54,12 @@ ecc_memory = ECC Memory
 ecc_memory_error_count = ECC Memory Error Count
 corrected = Corrected
 uncorrected = UnCorrected
+deferred = Deferred
+ras = RAS
+ras_features = Features
+ras_eeprom_version = EEPROM Table Version
+ras_block = Block
+retired_pages = Retired Pages
 unknown = Unknown
 
 l1_cache_per_cu = L1 Cache (per CU)
@@ -80,6 +86,16 @@ power_cap_default = Power Cap. (Default)
 
 pcie_link_speed = PCIe Link Speed
 pci_power_state = PCI Power State
+runtime_pm = Runtime PM
+runtime_status = Runtime Status
+runtime_pm_control = Control
+autosuspend_delay = Autosuspend Delay
+runtime_active = Active
+runtime_suspended = Suspended
+since_boot = since boot
+gfxoff_residency = GFXOFF Residency
+gfxoff_entries = Entries
+session = Session
 power_profile = Power Profile
 # Dynamic Power Management (DPM)
 dpm = DPM
@@ -119,6 +135,9 @@ connector_info = Connector Info
 ## Graphics Register Bus Management (GRBM)
 grbm = GRBM
 grbm2 = GRBM2
+grbm_se = GRBM SE Breakdown
+power_state = Power State
+se_imbalance = Diff
 
 ## GRBM
 Graphics_Pipe = Graphics Pipe
@@ -176,8 +195,70 @@ name = Name
 pid = PID
 
 sensor = Sensors
+hwmon_channels = All Sensors (hwmon)
+channel = Channel
+value = Value
+limits = Limits
 
+fan = Fan
+fan_mode = Control Mode
+fan_target = Target
+fan_min = Min
+fan_zero_rpm = Zero RPM
+fan_zero_rpm_stop_temp = Zero RPM Stop Temp.
+fan_acoustic_limit = Acoustic Limit
+fan_acoustic_target = Acoustic Target
+fan_curve = Fan Curve
+dpm_residency = DPM Residency
+
+power_ctrl = Power Control
+perf_level = Performance Level
+power_profile_table = Power Profile Table
+require_root = root privileges are required to change the settings
+require_allow_power_ctrl = launch with `--allow-power-ctrl` to change the settings
+apply = Apply
+reset_to_default = Reset to default
+overdrive = Overdrive
+od_pending_changes = Pending changes (not committed yet):
+commit = Commit
+discard = Discard
+
+pcie = PCIe
 pcie_bw = PCIe Bandwidth
+pcie_total = Total
+link_health = Link Health
+topology = Topology
+total = Total
+compute_partition = Compute Partition
+memory_partition = Memory Partition
+xcc_busy = XCC Busy
+processes = Processes
+kfd_agents = ROCm Agents (KFD Topology)
+kfd_node = KFD Node
+device_type = Device Type
+simd_per_cu = SIMDs per CU
+wavefront_size = Wavefront Size
+max_waves_per_cu = Max Waves per CU
+max_clock = Max Clock
+lds = LDS
+memory_pool = Memory Pool
+capability = Capability
+kfd_vram = KFD VRAM
+cu_occupancy = CU Occupancy
+kfd_queues = Queues (CP/SDMA)
+kfd_sdma = SDMA
+evicted_time = Evicted Time
+display = Display
+connector = Connector
+current_mode = Current Mode
+vrr = VRR
+max_bpc = Max bpc
+colorspace = Colorspace
+hdr_metadata = HDR Metadata
+dpms = DPMS
+link_status = Link Status
+display_events = Display Events
+no_display = No display connected
 sent = Sent
 received = Received
 
@@ -200,5 +281,6 @@ avg_activity = Average Activity
 activity = Activity
 throttle_status = Throttle Status
 throttling_log = Throttling Log
+throttle_timeline = Throttle Timeline
 
 failed_to_set_up_gui = Failed to set up a graphics context.
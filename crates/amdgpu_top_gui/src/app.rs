@@ -1,18 +1,41 @@
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, mpsc};
 use eframe::wgpu::AdapterInfo;
 use crate::egui::{self, RichText};
-use crate::{BASE, MEDIUM, HISTORY_LENGTH};
+use crate::{BASE, MEDIUM, HISTORY_LENGTH, SPACE};
 use crate::{GuiAppData, GuiGpuMetrics, util::*, fl};
 use crate::gui_app_data::PlotHistory;
 use crate::tab_gui::{MainTab, InfoTab};
-use egui_plot::{Corner, Legend, Line, Plot, PlotPoint, PlotPoints};
+use egui_plot::{Corner, Legend, Line, Plot, PlotPoint, PlotPoints, Points};
 
 use libamdgpu_top::{
     AMDGPU::{GpuMetrics, MetricsInfo, RasErrorCount},
     DevicePath,
+    FanInfo,
+    GpuTopology,
     GuiMode,
+    KfdTopology,
+    OdClkVoltage,
+    OdEdit,
+    OdRange,
+    OdStaging,
+    OdUnit,
     PCI,
-    stat::{FdInfoSortType, PerfCounter, Sensors},
+    PerfLevel,
+    PowerCapChange,
+    PowerProfileTable,
+    RasInfo,
+    stat::{
+        DpmTable,
+        FdInfoSortType,
+        GrbmSe,
+        HwmonChannel,
+        PerfCounter,
+        PowerState,
+        PowerStateMachine,
+        Sensors,
+        ThrottleLog,
+        ThrottleReason,
+    },
 };
 
 const SPACING: [f32; 2] = [16.0; 2];
@@ -40,6 +63,15 @@ pub struct MyApp {
     pub gui_mode: GuiMode,
     pub main_tab: MainTab,
     pub info_tab: InfoTab,
+    pub allow_power_ctrl: bool,
+    /// notify the update thread to re-read perf_level and power_profile of the device
+    pub power_ctrl_tx: mpsc::Sender<PCI::BUS_INFO>,
+    pub power_ctrl_msg: Option<String>,
+    pub power_cap_input: Option<u32>, // W
+    pub od_staging: Option<(PCI::BUS_INFO, OdStaging)>,
+    pub od_msg: Option<String>,
+    pub topology: GpuTopology,
+    pub kfd: KfdTopology,
 }
 
 pub fn grid(ui: &mut egui::Ui, v: &[(&str, &str)]) {
@@ -68,6 +100,347 @@ impl GuiMemoryErrorCount for RasErrorCount {
     }
 }
 
+pub trait GuiRasInfo {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiRasInfo for RasInfo {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("RAS Info").show(ui, |ui| {
+            if let Some(features) = self.features {
+                ui.label(fl!("ras_features"));
+                ui.label(format!("{features:#x}"));
+                ui.end_row();
+            }
+
+            if let Some(ver) = self.eeprom.version {
+                ui.label(fl!("ras_eeprom_version"));
+                ui.label(format!("{ver:#x}"));
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(SPACE);
+
+        egui::Grid::new("RAS Error Count").show(ui, |ui| {
+            ui.label(fl!("ras_block")).highlight();
+            ui.label(fl!("uncorrected")).highlight();
+            ui.label(fl!("corrected")).highlight();
+            ui.label(fl!("deferred")).highlight();
+            ui.end_row();
+
+            for b in &self.blocks {
+                ui.label(b.block.to_uppercase());
+                ui.label(format!("{} (+{})", b.ue, b.ue_delta()));
+                ui.label(format!("{} (+{})", b.ce, b.ce_delta()));
+                ui.label(b.de.map_or("-".to_string(), |de| de.to_string()));
+                ui.end_row();
+            }
+        });
+
+        if self.bad_pages.is_empty() {
+            return;
+        }
+
+        ui.add_space(SPACE);
+        ui.label(format!("{}: {}", fl!("retired_pages"), self.bad_pages.len()));
+
+        egui::Grid::new("RAS Bad Pages").show(ui, |ui| {
+            for p in &self.bad_pages {
+                ui.label(format!("{:#014x}", p.address()));
+                ui.label(format!("{:#x}", p.size));
+                ui.label(p.status.to_string());
+                ui.end_row();
+            }
+        });
+    }
+}
+
+pub trait GuiHwmonChannels {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiHwmonChannels for [HwmonChannel] {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("hwmon channels").show(ui, |ui| {
+            ui.label(fl!("channel")).highlight();
+            ui.label(fl!("name")).highlight();
+            ui.label(fl!("value")).highlight();
+            ui.label(fl!("limits")).highlight();
+            ui.end_row();
+
+            for ch in self {
+                let Some(input) = ch.input else { continue };
+                let unit = ch.unit();
+                let limits: Vec<String> = ch.limits()
+                    .into_iter()
+                    .filter_map(|(name, val)| {
+                        val.map(|val| format!("{name}: {:.0} {unit}", ch.to_display_value(val)))
+                    })
+                    .collect();
+
+                ui.label(format!("{}{}", ch.type_.prefix(), ch.index));
+                ui.label(ch.name());
+                ui.label(format!("{:.3} {unit}", ch.to_display_value(input)));
+                ui.label(limits.join(", "));
+                ui.end_row();
+            }
+        });
+    }
+}
+
+pub trait GuiFanInfo {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiFanInfo for FanInfo {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("Fan Info").show(ui, |ui| {
+            if let Some(mode) = self.pwm_mode {
+                ui.label(fl!("fan_mode"));
+                ui.label(mode.to_string());
+                ui.end_row();
+            }
+
+            if let (Some(pwm), Some(per)) = (self.pwm, self.pwm_percent()) {
+                ui.label("PWM");
+                ui.label(format!("{per}% ({pwm}/255)"));
+                ui.end_row();
+            }
+
+            for (name, val) in [
+                (fl!("fan_target"), self.target_rpm),
+                (fl!("fan_min"), self.min_rpm),
+            ] {
+                let Some(val) = val else { continue };
+                ui.label(name);
+                ui.label(format!("{val} {}", fl!("rpm")));
+                ui.end_row();
+            }
+
+            for (name, val, unit) in [
+                (fl!("fan_zero_rpm"), self.zero_rpm_enable, ""),
+                (fl!("fan_zero_rpm_stop_temp"), self.zero_rpm_stop_temp, "C"),
+                (fl!("fan_acoustic_limit"), self.acoustic_limit_rpm, "RPM"),
+                (fl!("fan_acoustic_target"), self.acoustic_target_rpm, "RPM"),
+            ] {
+                let Some(val) = val else { continue };
+                let range = val.range
+                    .map(|[min, max]| format!(" ({min}-{max} {unit})"))
+                    .unwrap_or_default();
+
+                ui.label(name);
+                ui.label(format!("{} {unit}{range}", val.value));
+                ui.end_row();
+            }
+        });
+
+        let Some(curve) = &self.fan_curve else { return };
+        let points: Vec<[f64; 2]> = curve.points
+            .iter()
+            .map(|p| [p.temp as f64, p.speed as f64])
+            .collect();
+        let label_fmt = |_s: &str, val: &PlotPoint| {
+            format!("{:.0} C : {:.0}%", val.x, val.y)
+        };
+        let [t_min, t_max] = curve.temp_range.unwrap_or([0, 100]);
+
+        ui.label(fl!("fan_curve"));
+        Plot::new("Fan Curve Plot")
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .allow_drag(false)
+            .show_axes([true, true])
+            .include_x(t_min as f64)
+            .include_x(t_max as f64)
+            .include_y(0.0)
+            .include_y(100.0)
+            .label_formatter(label_fmt)
+            .height(PLOT_HEIGHT)
+            .width(PLOT_HEIGHT * 2.0)
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(fl!("fan_curve"), points.clone()));
+                plot_ui.points(Points::new(fl!("fan_curve"), points).radius(3.0));
+            });
+    }
+}
+
+pub trait GuiDpmTables {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiDpmTables for [DpmTable] {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("DPM Residency").show(ui, |ui| {
+            for table in self {
+                ui.label(RichText::new(table.domain.to_string()).font(MEDIUM));
+                ui.end_row();
+
+                for (i, level) in table.levels.iter().enumerate() {
+                    let per = table.residency_percent(i);
+                    let text = format!("{per:5.1}%");
+                    let bar = egui::ProgressBar::new(per as f32 / 100.0)
+                        .text(RichText::new(&text).font(BASE));
+                    let label = format!("{}: {}", level.label, level.desc);
+
+                    if level.is_active {
+                        ui.label(RichText::new(format!("{label} *")).strong());
+                    } else {
+                        ui.label(label);
+                    }
+
+                    ui.add_sized([240.0, 16.0], bar);
+                    ui.end_row();
+                }
+            }
+        });
+    }
+}
+
+pub trait GuiGrbmSe {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiGrbmSe for GrbmSe {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("GRBM SE Breakdown").show(ui, |ui| {
+            ui.label("");
+
+            for i in 0..self.se.len() {
+                ui.label(format!("SE{i}")).highlight();
+            }
+
+            ui.label(fl!("se_imbalance")).highlight();
+            ui.end_row();
+
+            for (i, name) in self.bit_names().iter().enumerate() {
+                ui.label(*name);
+
+                for usage in self.usage(i) {
+                    let bar = egui::ProgressBar::new(usage as f32 / 100.0)
+                        .text(RichText::new(format!("{usage:3}%")).font(BASE));
+                    ui.add_sized([80.0, 16.0], bar);
+                }
+
+                ui.label(format!("{:3}%", self.imbalance(i)));
+                ui.end_row();
+            }
+        });
+    }
+}
+
+pub trait GuiThrottleLog {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiThrottleLog for ThrottleLog {
+    fn ui(&self, ui: &mut egui::Ui) {
+        const MAX_LOG_LINES: usize = 20;
+        let now = self.elapsed().as_secs_f64();
+
+        egui::Grid::new("Throttle Residency").show(ui, |ui| {
+            for reason in ThrottleReason::ALL {
+                ui.label(reason.to_string());
+                ui.label(format!("{:5.1}%", self.residency_percent(reason)));
+                ui.end_row();
+            }
+
+            // APU: `throttle_residency_*` of gpu_metrics v3.0
+            for c in &self.apu_residency {
+                ui.label(c.label);
+                ui.label(format!("+{} ({})", c.delta, c.total_delta));
+                ui.end_row();
+            }
+        });
+
+        let color = |reason: ThrottleReason| match reason {
+            ThrottleReason::Power => egui::Color32::from_rgb(255, 200, 0),
+            ThrottleReason::Current => egui::Color32::from_rgb(0, 160, 255),
+            ThrottleReason::Thermal => egui::Color32::from_rgb(255, 60, 60),
+            ThrottleReason::Prochot => egui::Color32::from_rgb(200, 0, 200),
+            ThrottleReason::Other => egui::Color32::GRAY,
+        };
+        let label_fmt = |name: &str, val: &PlotPoint| {
+            format!("{:.1}s : {name}", val.x)
+        };
+
+        Plot::new("Throttle Timeline")
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .allow_drag(false)
+            .show_axes([true, false])
+            .include_x(0.0)
+            .include_x(now)
+            .include_y(-0.5)
+            .include_y(ThrottleReason::ALL.len() as f64 - 0.5)
+            .label_formatter(label_fmt)
+            .legend(Legend::default().position(Corner::LeftTop))
+            .height(PLOT_HEIGHT / 2.0)
+            .width(PLOT_WIDTH.min(ui.available_width()))
+            .show(ui, |plot_ui| {
+                for (y, reason) in ThrottleReason::ALL.into_iter().enumerate() {
+                    for e in self.events.iter().filter(|e| e.reasons().contains(&reason)) {
+                        let end = e.end.map_or(now, |end| end.as_secs_f64());
+                        let points = vec![[e.start.as_secs_f64(), y as f64], [end, y as f64]];
+
+                        plot_ui.line(
+                            Line::new(reason.to_string(), points)
+                                .color(color(reason))
+                                .width(8.0)
+                        );
+                    }
+                }
+            });
+
+        for e in self.events.iter().rev().take(MAX_LOG_LINES) {
+            let end = e.end.map_or("...".to_string(), |end| format!("{:.1}s", end.as_secs_f64()));
+
+            ui.label(format!(
+                "{:.1}s - {end} ({:.1}s): {}",
+                e.start.as_secs_f64(),
+                e.duration(self.elapsed()).as_secs_f64(),
+                e.throttlers.join(", "),
+            ));
+        }
+    }
+}
+
+pub trait GuiPowerState {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiPowerState for PowerStateMachine {
+    fn ui(&self, ui: &mut egui::Ui) {
+        const MAX_LOG_LINES: usize = 8;
+        let text = RichText::new(format!(
+            "{} ({}) for {:.1}s",
+            self.state,
+            self.state.description(),
+            self.duration().as_secs_f64(),
+        ));
+
+        // values of the paused sources are not updated
+        ui.label(match self.state {
+            PowerState::Active => text,
+            PowerState::NoPerfCounter => text.color(egui::Color32::from_rgb(0, 160, 255)),
+            PowerState::D3hot => text.color(egui::Color32::from_rgb(255, 160, 0)),
+        });
+
+        if self.transitions.is_empty() {
+            return;
+        }
+
+        egui::Grid::new("Power State Transitions").show(ui, |ui| {
+            for t in self.transitions.iter().rev().take(MAX_LOG_LINES) {
+                ui.label(format!("{:.1}s", t.time.as_secs_f64()));
+                ui.label(format!("{} -> {}", t.from, t.to));
+                ui.label(t.reason);
+                ui.end_row();
+            }
+        });
+    }
+}
+
 impl MyApp {
     pub fn egui_perf_counter(
         &self,
@@ -254,6 +627,20 @@ impl MyApp {
                 }
             }
 
+            let has_kfd_stat = self.buf_data.stat.fdinfo.has_kfd_stat();
+
+            if has_kfd_stat {
+                for (s, align) in [
+                    (fl!("kfd_vram"), 10),
+                    (fl!("cu_occupancy"), 12),
+                    (fl!("kfd_queues"), 15),
+                    (fl!("kfd_sdma"), 5),
+                ] {
+                    let s = format!("{s:^align$}");
+                    let _ = ui.button(rt_base(s));
+                }
+            }
+
             ui.end_row();
 
             let mut fdinfo = self.buf_data.stat.fdinfo.clone();
@@ -287,6 +674,14 @@ impl MyApp {
                     ui.label(format!("{:3} %", pu.usage.vpe));
                 }
 
+                if has_kfd_stat && let Some(kfd) = &pu.kfd {
+                    ui.label(format!("{:5} {mib}", kfd.vram_usage >> 10))
+                        .on_hover_text(format!("{}: {} ms", fl!("evicted_time"), kfd.evicted_ms));
+                    ui.label(format!("{:4}", kfd.cu_occupancy));
+                    ui.label(format!("{:3} / {:3}", kfd.compute_queues, kfd.sdma_queues));
+                    ui.label(format!("{:3} %", kfd.sdma));
+                }
+
                 ui.end_row();
             } // proc_usage
         });
@@ -346,6 +741,243 @@ impl MyApp {
         }
     }
 
+    pub fn egui_power_ctrl(&mut self, ui: &mut egui::Ui) {
+        let buf_data = self.buf_data.clone();
+        let Some(sensors) = buf_data.stat.sensors.as_ref() else { return };
+        let sysfs_path = &sensors.sysfs_path;
+        let access = buf_data.power_ctrl_access;
+        let allow = self.allow_power_ctrl;
+        let mut result = None;
+
+        egui::Grid::new("Power Control").show(ui, |ui| {
+            if let Some(cur) = sensors.perf_level {
+                ui.label(fl!("perf_level"));
+                ui.add_enabled_ui(allow && access.perf_level, |ui| {
+                    egui::ComboBox::from_id_salt("Perf Level")
+                        .selected_text(cur.as_str())
+                        .show_ui(ui, |ui| for level in PerfLevel::ALL {
+                            let button = egui::widgets::Button::selectable(level == cur, level.as_str());
+
+                            if ui.add(button).clicked() && level != cur {
+                                result = Some(level.set(sysfs_path));
+                            }
+                        });
+                });
+                ui.end_row();
+            }
+
+            if let Some(table) = &sensors.power_profile_table {
+                let cur = table.current();
+
+                ui.label(fl!("power_profile"));
+                ui.add_enabled_ui(allow && access.power_profile, |ui| {
+                    egui::ComboBox::from_id_salt("Power Profile")
+                        .selected_text(cur.map(|m| m.name.as_str()).unwrap_or_default())
+                        .show_ui(ui, |ui| for mode in &table.modes {
+                            let button = egui::widgets::Button::selectable(mode.is_active, &mode.name);
+
+                            if ui.add(button).clicked() && !mode.is_active {
+                                result = Some(PowerProfileTable::set(sysfs_path, mode.index));
+                            }
+                        });
+                });
+                ui.end_row();
+            }
+
+            if let Some(cap) = &sensors.power_cap {
+                let input = self.power_cap_input.get_or_insert(cap.current);
+
+                ui.label(fl!("power_cap"));
+                ui.add_enabled_ui(allow && access.power_cap, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Slider::new(input, cap.min..=cap.max).suffix(" W"));
+
+                        if ui.button(fl!("apply")).clicked() {
+                            let change = PowerCapChange::new(cap, *input);
+                            result = Some(change.apply(&sensors.hwmon_path));
+                        }
+
+                        if ui.button(fl!("reset_to_default")).clicked() {
+                            let change = PowerCapChange::reset_to_default(cap);
+                            *input = change.to;
+                            result = Some(change.apply(&sensors.hwmon_path));
+                        }
+                    });
+                });
+                ui.end_row();
+            }
+        });
+
+        if let Some(r) = result {
+            if r.is_ok() {
+                let _ = self.power_ctrl_tx.send(buf_data.pci_bus);
+            }
+
+            self.power_ctrl_msg = r.err().map(|e| e.to_string());
+        }
+
+        if let Some(msg) = &self.power_ctrl_msg {
+            ui.label(msg);
+        } else if !allow {
+            ui.label(fl!("require_allow_power_ctrl"));
+        } else if !access.perf_level {
+            ui.label(fl!("require_root"));
+        }
+
+        if let Some(table) = &sensors.power_profile_table {
+            collapsing(ui, &fl!("power_profile_table"), false, |ui| {
+                ui.label(RichText::new(&table.table).font(BASE));
+            });
+        }
+    }
+
+    pub fn egui_od(&mut self, ui: &mut egui::Ui) {
+        let buf_data = self.buf_data.clone();
+        let Some(od) = &buf_data.device_info.od_clk_voltage else { return };
+        let sysfs_path = &buf_data.device_info.sysfs_path;
+        let pci_bus = buf_data.pci_bus;
+
+        if self.od_staging.as_ref().is_none_or(|(bus, _)| *bus != pci_bus) {
+            self.od_staging = Some((pci_bus, OdStaging::new(od.clone())));
+            self.od_msg = None;
+        }
+
+        let Some((_, staging)) = &mut self.od_staging else { return };
+        let staged = staging.staged();
+        let mut edits = Vec::new();
+        let mut result = None;
+
+        let drag_value = |ui: &mut egui::Ui, mut val: i32, range: Option<&OdRange>, unit: OdUnit| {
+            let mut drag = egui::DragValue::new(&mut val).suffix(format!(" {unit}"));
+
+            if let Some(r) = range {
+                drag = drag.range(r.min..=r.max);
+            }
+
+            let changed = ui.add(drag).changed();
+
+            if let Some(r) = range {
+                ui.label(format!("({} - {} {unit})", r.min, r.max));
+            }
+
+            changed.then_some(val)
+        };
+
+        egui::Grid::new("Overdrive").show(ui, |ui| {
+            for (section, points) in [
+                ("OD_SCLK", &staged.sclk),
+                ("OD_MCLK", &staged.mclk),
+                ("OD_VDDC_CURVE", &staged.vddc_curve),
+            ] {
+                for p in points {
+                    let Some(mhz) = p.mhz else { continue };
+                    let (clk_range, volt_range) = match section {
+                        "OD_SCLK" => ("SCLK".to_string(), "VDDC".to_string()),
+                        "OD_MCLK" => ("MCLK".to_string(), "VDDC".to_string()),
+                        _ => (format!("VDDC_CURVE_SCLK[{}]", p.index), format!("VDDC_CURVE_VOLT[{}]", p.index)),
+                    };
+                    let mut new_mhz = None;
+                    let mut new_mv = None;
+
+                    ui.label(format!("{section}[{}]", p.index));
+                    ui.horizontal(|ui| {
+                        new_mhz = drag_value(ui, mhz, staged.range(&clk_range), OdUnit::MHz);
+
+                        if let Some(mv) = p.mv {
+                            new_mv = drag_value(ui, mv, staged.range(&volt_range), OdUnit::MV);
+                        }
+                    });
+                    ui.end_row();
+
+                    if new_mhz.is_none() && new_mv.is_none() {
+                        continue;
+                    }
+
+                    let (index, mhz, mv) = (p.index, new_mhz.unwrap_or(mhz), new_mv.or(p.mv));
+
+                    edits.push(match section {
+                        "OD_SCLK" => OdEdit::Sclk { index, mhz, mv },
+                        "OD_MCLK" => OdEdit::Mclk { index, mhz, mv },
+                        _ => OdEdit::VddcCurve { index, mhz, mv: mv.unwrap_or_default() },
+                    });
+                }
+            }
+
+            for (section, val, range, unit) in [
+                ("OD_SCLK_OFFSET", staged.sclk_offset, "SCLK_OFFSET", OdUnit::MHz),
+                ("OD_VDDGFX_OFFSET", staged.vddgfx_offset, "VDDGFX_OFFSET", OdUnit::MV),
+            ] {
+                let Some(val) = val else { continue };
+                let mut new_val = None;
+
+                ui.label(section);
+                ui.horizontal(|ui| new_val = drag_value(ui, val, staged.range(range), unit));
+                ui.end_row();
+
+                if let Some(val) = new_val {
+                    edits.push(match unit {
+                        OdUnit::MHz => OdEdit::SclkOffset(val),
+                        OdUnit::MV => OdEdit::VddgfxOffset(val),
+                    });
+                }
+            }
+        });
+
+        for edit in edits {
+            if let Err(e) = staging.stage(edit) {
+                self.od_msg = Some(e.to_string());
+            }
+        }
+
+        let diff = staging.diff();
+
+        if !diff.is_empty() {
+            ui.label(fl!("od_pending_changes"));
+
+            for d in &diff {
+                ui.label(RichText::new(format!("  {d}")).font(BASE));
+            }
+        }
+
+        let has_edits = !staging.edits.is_empty();
+        let access = buf_data.power_ctrl_access;
+
+        ui.add_enabled_ui(self.allow_power_ctrl && access.od, |ui| {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(has_edits, egui::Button::new(fl!("commit"))).clicked() {
+                    result = Some(staging.commit(sysfs_path));
+                }
+
+                if ui.add_enabled(has_edits, egui::Button::new(fl!("discard"))).clicked() {
+                    staging.discard();
+                }
+
+                if ui.button(fl!("reset_to_default")).clicked() {
+                    let r = OdClkVoltage::reset(sysfs_path);
+
+                    if r.is_ok() {
+                        *staging = OdStaging::from_sysfs(sysfs_path)
+                            .unwrap_or_else(|| OdStaging::new(od.clone()));
+                    }
+
+                    result = Some(r);
+                }
+            });
+        });
+
+        if let Some(r) = result {
+            self.od_msg = r.err().map(|e| e.to_string());
+        }
+
+        if let Some(msg) = &self.od_msg {
+            ui.label(msg);
+        } else if !self.allow_power_ctrl {
+            ui.label(fl!("require_allow_power_ctrl"));
+        } else if !access.od {
+            ui.label(fl!("require_root"));
+        }
+    }
+
     pub fn egui_sensors(&self, ui: &mut egui::Ui) {
         let Some(sensors) = self.buf_data.stat.sensors.as_ref() else { return };
         let mut n = 1;
@@ -387,22 +1019,6 @@ impl MyApp {
                     1500, // "1500 mV" is not an exact value
                     fl!("mv"),
                 ),
-                (
-                    &self.buf_data.history.sensors_history.average_power,
-                    sensors.average_power.as_ref().map(|power| power.value),
-                    "Average Power",
-                    0,
-                    sensors.power_cap.as_ref().map(|cap| cap.current).unwrap_or(350), // "350 W" is not an exact value
-                    fl!("w"),
-                ),
-                (
-                    &self.buf_data.history.sensors_history.input_power,
-                    sensors.input_power.as_ref().map(|power| power.value),
-                    "Input Power",
-                    0,
-                    sensors.power_cap.as_ref().map(|cap| cap.current).unwrap_or(350), // "350 W" is not an exact value
-                    fl!("w"),
-                ),
                 (
                     &self.buf_data.history.sensors_history.fan_rpm,
                     sensors.fan_rpm,
@@ -456,13 +1072,41 @@ impl MyApp {
             }
 
             if n % 2 == 0 { ui.end_row(); }
+            self.egui_power_plot(ui);
             self.egui_temp_plot(ui);
         });
 
         self.egui_core_freq_plot(ui, sensors);
 
-        if let Some(cur) = sensors.current_link {
-            let min_max = if let [Some(min), Some(max)] = [sensors.min_dpm_link, sensors.max_dpm_link] {
+        if let Some(power2) = &sensors.power2 {
+            ui.label(format!(
+                "{} => {:.2} {}",
+                power2.label.as_deref().unwrap_or("Power2"),
+                power2.value.as_watts_f64(),
+                fl!("w"),
+            ));
+        }
+
+        for v in [&sensors.voltage_in0, &sensors.voltage_in1].into_iter().flatten() {
+            ui.label(format!("{} => {} {}", v.name(), v.value.0, fl!("mv")));
+        }
+
+        for c in &sensors.currents {
+            ui.label(format!("{} => {:.3} A", c.name(), c.value.as_amperes_f64()));
+        }
+
+        if let Some(target_rpm) = sensors.fan.as_ref().and_then(|f| f.target_rpm) {
+            ui.label(format!("Fan Target => {target_rpm} {}", fl!("rpm")));
+        }
+
+        if let Some(pwm) = sensors.fan.as_ref().and_then(|f| f.pwm_percent()) {
+            ui.label(format!("Fan PWM => {pwm}%"));
+        }
+
+        let pcie = &self.buf_data.stat.pcie;
+
+        if let Some(cur) = pcie.current_link {
+            let min_max = if let [Some(min), Some(max)] = [pcie.min_link, pcie.max_link] {
                 format!(
                     " (Gen{}x{} - Gen{}x{})",
                     min.r#gen,
@@ -470,7 +1114,7 @@ impl MyApp {
                     max.r#gen,
                     max.width,
                 )
-            } else if let Some(max) = sensors.max_dpm_link {
+            } else if let Some(max) = pcie.max_link {
                 format!(" ({} Gen{}x{})", fl!("max"), max.r#gen, max.width)
             } else {
                 String::new()
@@ -492,13 +1136,235 @@ impl MyApp {
             ));
         }
 
-        if let Some(power_profile) = &sensors.power_profile {
+        if let Some(power_profile) = sensors.power_profile() {
             ui.label(format!(
                 "{}: {}",
                 fl!("power_profile"),
-                power_profile,
+                power_profile.name,
             ));
         }
+
+        self.egui_gfxoff_residency(ui);
+    }
+
+    pub fn egui_gfxoff_residency(&self, ui: &mut egui::Ui) {
+        let gfxoff = &self.buf_data.stat.gfxoff;
+
+        if let Some(note) = &gfxoff.note {
+            ui.label(format!("{} => N/A ({note})", fl!("gfxoff_residency")));
+            return;
+        }
+
+        let mut s = String::new();
+
+        if let Some(r) = gfxoff.residency {
+            s.push_str(&format!("{r:.1}%"));
+        }
+
+        if let Some(r) = gfxoff.session_residency {
+            s.push_str(&format!(" ({}: {r:.1}%)", fl!("session")));
+        }
+
+        if let [Some(count), Some(session_count)] = [gfxoff.count, gfxoff.session_count] {
+            s.push_str(&format!(
+                ", {}: {session_count} ({count} {})",
+                fl!("gfxoff_entries"),
+                fl!("since_boot"),
+            ));
+        }
+
+        ui.label(format!("{} => {s}", fl!("gfxoff_residency")));
+    }
+
+    pub fn egui_runtime_pm(&self, ui: &mut egui::Ui) {
+        let Some(runtime_pm) = &self.buf_data.stat.runtime_pm else { return };
+        let pci_power_state = self.buf_data.stat.sensors.as_ref().and_then(|s| s.pci_power_state.as_ref());
+
+        egui::Grid::new("Runtime PM").show(ui, |ui| {
+            ui.label(fl!("runtime_status"));
+            ui.label(runtime_pm.status.to_string());
+            ui.end_row();
+
+            if let Some(control) = runtime_pm.control {
+                ui.label(fl!("runtime_pm_control"));
+                ui.label(control.to_string());
+                ui.end_row();
+            }
+
+            if let Some(delay) = runtime_pm.autosuspend_delay_ms {
+                ui.label(fl!("autosuspend_delay"));
+                ui.label(format!("{delay} ms"));
+                ui.end_row();
+            }
+
+            if let Some(power_state) = pci_power_state {
+                ui.label(fl!("pci_power_state"));
+                ui.label(power_state);
+                ui.end_row();
+            }
+
+            for (label, time, percent, since_boot) in [
+                (fl!("runtime_active"), runtime_pm.session_active_time(), runtime_pm.active_percent(), runtime_pm.active_time),
+                (fl!("runtime_suspended"), runtime_pm.session_suspended_time(), runtime_pm.suspended_percent(), runtime_pm.suspended_time),
+            ] {
+                ui.label(label);
+                ui.label(format!("{:.1}s ({percent:5.1}%)", time.as_secs_f64()));
+                ui.label(format!("{:.1}s {}", since_boot.as_secs_f64(), fl!("since_boot")));
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn egui_display(&self, ui: &mut egui::Ui) {
+        let Some(display) = &self.buf_data.stat.display else { return };
+        let opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "_".to_string());
+
+        if display.connected().count() == 0 {
+            ui.label(fl!("no_display"));
+        } else {
+            egui::Grid::new("Display").show(ui, |ui| {
+                for label in [
+                    fl!("connector"),
+                    fl!("current_mode"),
+                    fl!("vrr"),
+                    fl!("max_bpc"),
+                    fl!("colorspace"),
+                    fl!("hdr_metadata"),
+                    fl!("dpms"),
+                    fl!("link_status"),
+                ] {
+                    ui.label(label).highlight();
+                }
+                ui.end_row();
+
+                for d in display.connected() {
+                    ui.label(&d.name);
+                    ui.label(d.mode.map_or("disabled".to_string(), |m| m.to_string()));
+                    ui.label(match (d.vrr_capable, d.vrr_enabled) {
+                        (Some(false), _) => "not capable",
+                        (_, Some(true)) => "on",
+                        (_, Some(false)) => "off",
+                        _ => "_",
+                    });
+                    ui.label(d.max_bpc.map_or("_".to_string(), |v| v.to_string()));
+                    ui.label(opt(&d.colorspace));
+                    ui.label(if d.hdr_output_metadata { "set" } else { "" });
+                    ui.label(opt(&d.dpms));
+                    ui.label(opt(&d.link_status));
+                    ui.end_row();
+                }
+            });
+        }
+
+        if !display.events.is_empty() {
+            ui.add_space(SPACE);
+            collapsing(ui, &fl!("display_events"), false, |ui| {
+                for e in display.events.iter().rev() {
+                    ui.label(format!("{:.1}s {}: {}", e.time.as_secs_f64(), e.connector, e.change));
+                }
+            });
+        }
+    }
+
+    pub fn egui_partition(&self, ui: &mut egui::Ui) {
+        let (Some(info), Some(stat)) = (
+            &self.buf_data.device_info.partition,
+            &self.buf_data.stat.partition,
+        ) else { return };
+
+        egui::Grid::new("Partition Mode").show(ui, |ui| {
+            ui.label(fl!("compute_partition"));
+            ui.label(info.compute_mode.to_string());
+            ui.end_row();
+
+            if let Some(memory_mode) = info.memory_mode {
+                ui.label(fl!("memory_partition"));
+                ui.label(memory_mode.to_string());
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(SPACE);
+
+        egui::Grid::new("Partitions").show(ui, |ui| {
+            ui.label("");
+            ui.label("XCC");
+            ui.label(fl!("xcc_busy"));
+            ui.label(fl!("gfx"));
+            ui.label(fl!("compute"));
+            ui.label(fl!("vram"));
+            ui.label(fl!("processes"));
+            ui.end_row();
+
+            for (p, usage) in info.partitions.iter().zip(stat.partitions.iter()) {
+                ui.label(format!("[{}] {}", p.index, p.render.display()))
+                    .on_hover_text(format!("KFD Node {}", p.kfd_node));
+                ui.label(format!("{:?}", p.xcc));
+                ui.label(usage.avg_xcc_busy().map_or("_".to_string(), |v| format!("{v}%")))
+                    .on_hover_text(format!("{:?}", usage.xcc_busy));
+                ui.label(format!("{}%", usage.gfx));
+                ui.label(format!("{}%", usage.compute));
+                ui.label(format!("{} {}", usage.vram_usage >> 10, fl!("mib")));
+
+                let procs: Vec<String> = usage.processes
+                    .iter()
+                    .map(|(pid, name)| format!("{name}({pid})"))
+                    .collect();
+                ui.label(procs.join(", "));
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn egui_power_plot(&self, ui: &mut egui::Ui) {
+        let Some(sensors) = self.buf_data.stat.sensors.as_ref() else { return };
+        let unit = fl!("w");
+        // "350 W" is not an exact value
+        let max = sensors.power_cap.as_ref().map(|cap| cap.current).unwrap_or(350);
+        let label_fmt = |_name: &str, val: &PlotPoint| {
+            format!("{:.1}s\n{:.2} {}", val.x, val.y, fl!("w"))
+        };
+        ui.style_mut().override_font_id = Some(MEDIUM);
+        let mut n = 1;
+
+        for (label, power, power_history) in [
+            ("Average Power", &sensors.average_power, &self.buf_data.history.sensors_history.average_power),
+            ("Input Power", &sensors.input_power, &self.buf_data.history.sensors_history.input_power),
+        ] {
+            let Some(power) = power else { continue };
+
+            egui::Grid::new(label).show(ui, |ui| {
+                let val = power.value.as_watts_f64();
+                let per = (power.value.0 * 100).checked_div(max as u64 * 1_000_000);
+
+                if let Some(per) = per {
+                    ui.label(format!("{label} ({val:6.2} {unit}) ({per:>3}%)"));
+                } else {
+                    ui.label(format!("{label} ({val:6.2} {unit})"));
+                }
+                ui.end_row();
+
+                let points = power_history.vec_plotpoint.as_slice();
+                let line = Line::new(label.to_string(), points).fill(0.0);
+
+                Plot::new(label)
+                    .allow_zoom(false)
+                    .allow_scroll(false)
+                    .include_y(0)
+                    .include_y(max)
+                    .show_axes(false)
+                    .label_formatter(label_fmt)
+                    .auto_bounds([true, false])
+                    .height(SENSORS_HEIGHT)
+                    .width(SENSORS_WIDTH)
+                    .show(ui, |plot_ui| plot_ui.line(line));
+            });
+
+            n += 1;
+            if n % 2 == 1 { ui.end_row(); }
+        }
+
+        if n % 2 == 0 { ui.end_row(); }
     }
 
     pub fn egui_temp_plot(&self, ui: &mut egui::Ui) {
@@ -517,10 +1383,10 @@ impl MyApp {
             let Some(temp) = temp else { continue };
 
             egui::Grid::new(label).show(ui, |ui| {
-                let val = temp.current;
-                let max = temp.critical.unwrap_or(105) as f64;
+                let val = temp.current.as_celsius_f64();
+                let max = temp.critical.map(|c| c.as_celsius_f64()).unwrap_or(105.0);
 
-                ui.label(format!("{label} Temp. ({val:4} C)"));
+                ui.label(format!("{label} Temp. ({val:5.1} C)"));
                 ui.end_row();
 
                 let points = temp_history.vec_plotpoint.as_slice();
@@ -542,7 +1408,7 @@ impl MyApp {
         if let Some(ref tctl) = sensors.tctl {
             let label = "CPU Tctl";
             egui::Grid::new(label).show(ui, |ui| {
-                ui.label(format!("CPU Tctl ({:3} C)", tctl / 1000));
+                ui.label(format!("CPU Tctl ({:5.1} C)", tctl.as_celsius_f64()));
                 ui.end_row();
 
                 let points = self.buf_data.history.sensors_history.tctl.vec_plotpoint.as_slice();
@@ -563,6 +1429,60 @@ impl MyApp {
         }
     }
 
+    pub fn egui_pcie(&self, ui: &mut egui::Ui) {
+        let pcie = &self.buf_data.stat.pcie;
+
+        if let Some(src) = pcie.bw_source {
+            ui.label(format!("{} ({src})", fl!("pcie_bw")));
+            self.egui_pcie_bw(ui);
+        }
+
+        if !pcie.counters.is_empty() {
+            egui::Grid::new("PCIe Counters").show(ui, |ui| {
+                for c in &pcie.counters {
+                    ui.label(c.label);
+                    ui.label(format!("+{} ({})", c.delta, c.total_delta));
+                    ui.end_row();
+                }
+            });
+        }
+
+        self.egui_link_health(ui);
+    }
+
+    /// with the warning badge of the link health
+    pub fn pcie_title(&self) -> String {
+        if self.buf_data.stat.link_health.as_ref().is_some_and(|l| l.has_warning()) {
+            format!("{} \u{26a0}", fl!("pcie"))
+        } else {
+            fl!("pcie")
+        }
+    }
+
+    pub fn egui_link_health(&self, ui: &mut egui::Ui) {
+        let Some(link_health) = &self.buf_data.stat.link_health else { return };
+
+        if link_health.has_warning() {
+            for issue in &link_health.issues {
+                ui.colored_label(egui::Color32::from_rgb(255, 160, 0), format!("\u{26a0} {issue}"));
+            }
+        } else {
+            ui.label(format!("{}: OK", fl!("link_health")));
+        }
+
+        egui::Grid::new("PCIe Topology").show(ui, |ui| {
+            for hop in &link_health.hops {
+                let [cur, max] = [hop.current_link, hop.max_link]
+                    .map(|link| link.map_or("N/A".to_string(), |l| format!("Gen{}x{}", l.r#gen, l.width)));
+
+                ui.label(&hop.bdf);
+                ui.label(format!("{cur} ({} {max})", fl!("max")));
+                ui.label(hop.aer.to_string());
+                ui.end_row();
+            }
+        });
+    }
+
     pub fn egui_pcie_bw(&self, ui: &mut egui::Ui) {
         let label_fmt = |name: &str, val: &PlotPoint| {
             format!("{:.1}s : {name} {:.0} {}", val.x, val.y, fl!("mib_s"))
@@ -570,14 +1490,17 @@ impl MyApp {
 
         let fl_sent = fl!("sent");
         let fl_rec = fl!("received");
+        let fl_total = fl!("pcie_total");
         let mib_s = fl!("mib_s");
 
         let sent_history = &self.buf_data.history.pcie_sent_bw_history;
         let rec_history = &self.buf_data.history.pcie_rec_bw_history;
+        let bw_history = &self.buf_data.history.pcie_bw_history;
 
-        let [sent, rec] = [
+        let [sent, rec, total] = [
             Line::new(fl_sent.clone(), sent_history.vec_plotpoint.as_slice()),
             Line::new(fl_rec.clone(), rec_history.vec_plotpoint.as_slice()),
+            Line::new(fl_total.clone(), bw_history.vec_plotpoint.as_slice()),
         ];
 
         default_plot("pcie_bw plot")
@@ -589,15 +1512,103 @@ impl MyApp {
             .show(ui, |plot_ui| {
                 plot_ui.line(sent);
                 plot_ui.line(rec);
+                plot_ui.line(total);
             });
 
         if let [Some(sent), Some(rec)] = [sent_history.value_history.latest(), rec_history.value_history.latest()] {
             ui.label(format!("{fl_sent}: {sent:5} {mib_s}, {fl_rec}: {rec:5} {mib_s}"));
+        } else if let Some(total) = bw_history.value_history.latest() {
+            ui.label(format!("{fl_total}: {total:5} {mib_s}"));
         } else {
             ui.label(format!("{fl_sent}: _ {mib_s}, {fl_rec}: _ {mib_s}"));
         }
     }
 
+    pub fn has_topology(&self) -> bool {
+        self.topology.has_multiple_gpus() || self.buf_data.device_info.xgmi.is_some()
+    }
+
+    pub fn egui_topology(&self, ui: &mut egui::Ui) {
+        let share_data = crate::SHARE_DATA.load();
+        let len = self.topology.gpus.len();
+
+        egui::Grid::new("Topology GPUs").show(ui, |ui| {
+            for (i, gpu) in self.topology.gpus.iter().enumerate() {
+                ui.label(format!("GPU{i}"));
+                ui.label(format!("{} ({})", gpu.name, gpu.pci));
+                ui.label(gpu.kfd_node.map_or(String::new(), |node| format!("KFD Node {node}")));
+                ui.label(
+                    gpu.xgmi
+                        .and_then(|x| x.hive_id)
+                        .map_or(String::new(), |id| format!("XGMI Hive {id:#x}"))
+                );
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(SPACE);
+
+        egui::Grid::new("Topology Matrix").show(ui, |ui| {
+            ui.label("");
+
+            for i in 0..len {
+                ui.label(format!("GPU{i}"));
+            }
+
+            ui.end_row();
+
+            for i in 0..len {
+                ui.label(format!("GPU{i}"));
+
+                for j in 0..len {
+                    if i == j {
+                        ui.label("-");
+                        continue;
+                    }
+
+                    match self.topology.link(i, j) {
+                        Some(link) => ui.label(link.short_label()).on_hover_text(format!(
+                            "{} - {} MB/s",
+                            link.min_bandwidth,
+                            link.max_bandwidth,
+                        )),
+                        None => ui.label("N/A"),
+                    };
+                }
+
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(SPACE);
+        ui.label(format!("XGMI ({})", fl!("mib_s")));
+
+        egui::Grid::new("XGMI Throughput").show(ui, |ui| {
+            for (i, gpu) in self.topology.gpus.iter().enumerate() {
+                let Some(data) = share_data.iter().find(|d| d.pci_bus == gpu.pci) else { continue };
+                let xgmi = &data.stat.xgmi;
+
+                if !xgmi.has_stat() { continue }
+
+                ui.label(format!("GPU{i}"));
+
+                if let [Some(width), Some(speed)] = [xgmi.link_width, xgmi.link_speed] {
+                    ui.label(format!("x{width} {speed}Gbps"));
+                } else {
+                    ui.label("");
+                }
+
+                ui.label(format!("{}: {:.1}", fl!("total"), xgmi.total()));
+
+                for (j, link) in xgmi.links.iter().enumerate() {
+                    ui.label(format!("Link{j}: {:.1}/{:.1}", link.read, link.write));
+                }
+
+                ui.end_row();
+            }
+        });
+    }
+
     pub fn egui_activity(&self, ui: &mut egui::Ui) {
         let label_fmt = |name: &str, val: &PlotPoint| {
             format!("{:.1}s : {name} {:.0}%", val.x, val.y)
@@ -2,7 +2,16 @@ use crate::egui::util::History;
 use egui_plot::PlotPoint;
 use crate::HISTORY_LENGTH;
 
-use libamdgpu_top::{AppDeviceInfo, ConnectorInfo, DevicePath, PCI};
+use libamdgpu_top::{
+    AppDeviceInfo,
+    ConnectorInfo,
+    DevicePath,
+    OdClkVoltage,
+    PCI,
+    PerfLevel,
+    PowerCapChange,
+    PowerProfileTable,
+};
 use libamdgpu_top::app::{
     AppAmdgpuTop,
     AppAmdgpuTopStat,
@@ -10,6 +19,8 @@ use libamdgpu_top::app::{
 use libamdgpu_top::AMDGPU::{MetricsInfo, ThrottleStatus};
 use libamdgpu_top::stat::{
     FdInfoUsage,
+    MicroWatt,
+    MilliCelsius,
     Sensors,
     gpu_metrics_util,
 };
@@ -51,6 +62,7 @@ impl<T: std::marker::Copy> PlotHistory<T> {
 pub struct HistoryData {
     pub grbm_history: Vec<PlotHistory<u8>>,
     pub grbm2_history: Vec<PlotHistory<u8>>,
+    pub extra_pc_history: Vec<Vec<PlotHistory<u8>>>,
     pub vram_history: PlotHistory<u64>,
     pub gtt_history: PlotHistory<u64>,
     pub fdinfo_history: History<FdInfoUsage>,
@@ -64,6 +76,7 @@ pub struct HistoryData {
     pub sensors_history: SensorsHistory,
     pub pcie_sent_bw_history: PlotHistory<u64>,
     pub pcie_rec_bw_history: PlotHistory<u64>,
+    pub pcie_bw_history: PlotHistory<u64>,
     pub throttling_history: History<ThrottleStatus>,
     pub gfx_activity: PlotHistory<u16>,
     pub umc_activity: PlotHistory<u16>,
@@ -87,19 +100,21 @@ pub struct SensorsHistory {
     pub fclk: PlotHistory<u32>,
     pub vddgfx: PlotHistory<u32>,
     pub vddnb: PlotHistory<u32>,
-    pub edge_temp: PlotHistory<i64>,
-    pub junction_temp: PlotHistory<i64>,
-    pub memory_temp: PlotHistory<i64>,
-    pub average_power: PlotHistory<u32>,
-    pub input_power: PlotHistory<u32>,
+    pub edge_temp: PlotHistory<MilliCelsius>,
+    pub junction_temp: PlotHistory<MilliCelsius>,
+    pub memory_temp: PlotHistory<MilliCelsius>,
+    pub average_power: PlotHistory<MicroWatt>,
+    pub input_power: PlotHistory<MicroWatt>,
     pub fan_rpm: PlotHistory<u32>,
-    pub tctl: PlotHistory<i64>,
+    pub tctl: PlotHistory<MilliCelsius>,
     pub core_freq: Vec<PlotHistory<u32>>,
 }
 
 impl SensorsHistory {
     pub fn new() -> Self {
-        let [sclk, mclk, fclk, vddgfx, vddnb, average_power, input_power, fan_rpm] = [0; 8]
+        let [sclk, mclk, fclk, vddgfx, vddnb, fan_rpm] = [0; 6]
+            .map(|_| PlotHistory::new());
+        let [average_power, input_power] = [0; 2]
             .map(|_| PlotHistory::new());
         let [edge_temp, junction_temp, memory_temp, tctl] = [0;4]
             .map(|_| PlotHistory::new());
@@ -115,25 +130,31 @@ impl SensorsHistory {
             (&mut self.fclk, sensors.fclk_dpm.as_ref().map(|f| f.current_mhz)),
             (&mut self.vddgfx, sensors.vddgfx),
             (&mut self.vddnb, sensors.vddnb),
-            (&mut self.average_power, sensors.average_power.as_ref().map(|power| power.value)),
-            (&mut self.input_power, sensors.input_power.as_ref().map(|power| power.value)),
             (&mut self.fan_rpm, sensors.fan_rpm),
         ] {
             let Some(val) = val else { continue };
             history.add_and_update(sec, val, |v| v as f64);
         }
 
+        for (history, power) in [
+            (&mut self.average_power, &sensors.average_power),
+            (&mut self.input_power, &sensors.input_power),
+        ] {
+            let Some(power) = power else { continue };
+            history.add_and_update(sec, power.value, |v| v.as_watts_f64());
+        }
+
         for (history, temp) in [
             (&mut self.edge_temp, &sensors.edge_temp),
             (&mut self.junction_temp, &sensors.junction_temp),
             (&mut self.memory_temp, &sensors.memory_temp),
         ] {
             let Some(temp) = temp else { continue };
-            history.add_and_update(sec, temp.current, |v| v as f64);
+            history.add_and_update(sec, temp.current, |v| v.as_celsius_f64());
         }
 
         if let Some(tctl_val) = sensors.tctl {
-            self.tctl.add_and_update(sec, tctl_val / 1000, |v| v as f64);
+            self.tctl.add_and_update(sec, tctl_val, |v| v.as_celsius_f64());
         }
 
         for (freq, freq_history) in sensors.all_cpu_core_freq_info.iter().zip(self.core_freq.iter_mut()) {
@@ -148,17 +169,41 @@ impl Default for SensorsHistory {
     }
 }
 
+/// Writable power control files, checked once at startup
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PowerCtrlAccess {
+    pub perf_level: bool,
+    pub power_profile: bool,
+    pub power_cap: bool,
+    pub od: bool,
+}
+
+impl PowerCtrlAccess {
+    pub fn new(app: &AppAmdgpuTop) -> Self {
+        let sysfs_path = &app.device_info.sysfs_path;
+
+        Self {
+            perf_level: PerfLevel::is_writable(sysfs_path),
+            power_profile: PowerProfileTable::is_writable(sysfs_path),
+            power_cap: app.stat.sensors
+                .as_ref()
+                .is_some_and(|sensors| PowerCapChange::is_writable(&sensors.hwmon_path)),
+            od: OdClkVoltage::is_writable(sysfs_path),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GuiAppData {
     pub stat: AppAmdgpuTopStat,
     pub device_info: AppDeviceInfo,
     pub pci_bus: PCI::BUS_INFO,
-    pub support_pcie_bw: bool,
     pub history: HistoryData,
     pub vec_connector_info: Vec<ConnectorInfo>,
     pub xdna_device_path: Option<DevicePath>,
     pub xdna_fw_version: Option<String>,
     pub xdna_resouce_info: Option<xdna::amdxdna_drm_get_resource_info>,
+    pub power_ctrl_access: PowerCtrlAccess,
 }
 
 impl GuiAppData {
@@ -169,10 +214,15 @@ impl GuiAppData {
         let sensors_history = SensorsHistory::default();
         let pcie_sent_bw_history = PlotHistory::new();
         let pcie_rec_bw_history = PlotHistory::new();
+        let pcie_bw_history = PlotHistory::new();
         let throttling_history = History::new(HISTORY_LENGTH, f32::INFINITY);
         let [grbm_history, grbm2_history] = [&app.stat.grbm, &app.stat.grbm2].map(|pc| {
             vec![PlotHistory::new(); pc.pc_index.len()]
         });
+        let extra_pc_history = app.stat.extra_pc
+            .iter()
+            .map(|pc| vec![PlotHistory::new(); pc.pc_index.len()])
+            .collect();
         let gfx_activity = PlotHistory::new();
         let umc_activity = PlotHistory::new();
         let media_activity = PlotHistory::new();
@@ -207,10 +257,10 @@ impl GuiAppData {
             stat: app.stat.clone(),
             device_info: app.device_info.clone(),
             pci_bus: app.device_info.pci_bus,
-            support_pcie_bw: app.stat.arc_pcie_bw.is_some(),
             history: HistoryData {
                 grbm_history,
                 grbm2_history,
+                extra_pc_history,
                 vram_history,
                 gtt_history,
                 fdinfo_history,
@@ -224,6 +274,7 @@ impl GuiAppData {
                 sensors_history,
                 pcie_sent_bw_history,
                 pcie_rec_bw_history,
+                pcie_bw_history,
                 throttling_history,
                 gfx_activity,
                 umc_activity,
@@ -243,32 +294,27 @@ impl GuiAppData {
             xdna_device_path,
             xdna_fw_version,
             xdna_resouce_info,
+            power_ctrl_access: PowerCtrlAccess::new(app),
         }
     }
 
     pub fn update_history(&mut self, secs: f64, no_pc: bool) {
         let metrics = self.stat.metrics.as_ref();
 
-        if let Some(arc_pcie_bw) = &self.stat.arc_pcie_bw {
-            let lock = arc_pcie_bw.try_lock();
-            if let Ok(pcie_bw) = lock
-                && let (Some(sent), Some(rec), Some(mps)) = (
-                    pcie_bw.sent,
-                    pcie_bw.received,
-                    pcie_bw.max_payload_size,
-                ) {
-                    let sent = (sent * mps as u64) >> 20;
-                    let rec = (rec * mps as u64) >> 20;
-                    self.history.pcie_sent_bw_history.add_and_update(secs, sent, |v| v as f64);
-                    self.history.pcie_rec_bw_history.add_and_update(secs, rec, |v| v as f64);
-                }
+        if let (Some(sent), Some(rec)) = (self.stat.pcie.sent, self.stat.pcie.received) {
+            self.history.pcie_sent_bw_history.add_and_update(secs, sent, |v| v as f64);
+            self.history.pcie_rec_bw_history.add_and_update(secs, rec, |v| v as f64);
+        }
+
+        if let Some(bw) = self.stat.pcie.bandwidth {
+            self.history.pcie_bw_history.add_and_update(secs, bw, |v| v as f64);
         }
 
         if !no_pc {
             for (pc, pc_history) in [
                 (&self.stat.grbm, &mut self.history.grbm_history),
                 (&self.stat.grbm2, &mut self.history.grbm2_history),
-            ] {
+            ].into_iter().chain(self.stat.extra_pc.iter().zip(self.history.extra_pc_history.iter_mut())) {
                 for (pc_index, h) in pc.pc_index.iter().zip(pc_history.iter_mut()) {
                     h.add_and_update(secs, pc_index.usage, |usage| usage as f64);
                 }
@@ -7,7 +7,7 @@ use crate::{
     fl,
 };
 
-use libamdgpu_top::{ConnectorInfo, ModeProp, drmModeModeInfo, drmModePropType};
+use libamdgpu_top::{ConnectorInfo, DevicePath, DisplayIdExtension, Edid, EdidTiming, KfdTopology, ModeProp, drmModeModeInfo, drmModePropType};
 use libamdgpu_top::AMDGPU::{
     GPU_INFO,
     HW_IP::HwIpInfo,
@@ -118,6 +118,97 @@ impl GuiIpDiscovery for Vec<IpDieEntry> {
     }
 }
 
+pub trait GuiKfdTopology {
+    fn ui(&self, ui: &mut egui::Ui, device_path_list: &[DevicePath]);
+}
+
+impl GuiKfdTopology for KfdTopology {
+    fn ui(&self, ui: &mut egui::Ui, device_path_list: &[DevicePath]) {
+        let kib = fl!("kib");
+        let mib = fl!("mib");
+        let mhz = fl!("mhz");
+
+        egui::Grid::new("kfd_agents").show(ui, |ui| {
+            for label in [
+                "".to_string(),
+                fl!("name"),
+                fl!("device_type"),
+                fl!("kfd_node"),
+                fl!("total_cu"),
+                fl!("simd_per_cu"),
+                fl!("wavefront_size"),
+                fl!("max_waves_per_cu"),
+                fl!("max_clock"),
+                fl!("lds"),
+                fl!("memory_pool"),
+                fl!("fw_version"),
+            ] {
+                ui.label(label).highlight();
+            }
+            ui.end_row();
+
+            for (i, node) in self.nodes.iter().enumerate() {
+                let render = node.render_path();
+                let device_path = render.as_ref().and_then(|render| device_path_list.iter().find(|d| {
+                    d.render == *render || d.partition_render.contains(render)
+                }));
+                let name = match (node.gfx_target(), device_path) {
+                    (Some(gfx), Some(d)) => format!("{gfx} ({})", d.device_name),
+                    (Some(gfx), None) => gfx.to_string(),
+                    (None, _) if !node.name.is_empty() => node.name.clone(),
+                    (None, _) => "CPU".to_string(),
+                };
+                let caches: Vec<String> = node.cache_size_per_level()
+                    .iter()
+                    .map(|(level, size)| format!("L{level}: {size} {kib}"))
+                    .collect();
+                let mem: Vec<String> = node.mem_banks
+                    .iter()
+                    .map(|bank| format!("{} {} {mib}", bank.heap_type_name(), bank.size_in_bytes >> 20))
+                    .collect();
+
+                ui.label(format!("Agent {}", i + 1));
+                ui.label(name).on_hover_text(format!(
+                    "{}\n{}: {:#x} {:?}",
+                    caches.join(", "),
+                    fl!("capability"),
+                    node.capability,
+                    node.capability_names(),
+                ));
+
+                if node.is_gpu() {
+                    ui.label("GPU");
+                    ui.label(match &render {
+                        Some(render) => format!("{} ({})", node.index, render.display()),
+                        None => node.index.to_string(),
+                    });
+                    ui.label(node.compute_units().to_string());
+                    ui.label(node.simd_per_cu.to_string());
+                    ui.label(node.wave_front_size.to_string());
+                    ui.label(node.max_waves_per_cu().to_string());
+                    ui.label(format!("{} {mhz}", node.max_engine_clk_fcompute));
+                    ui.label(format!("{} {kib}", node.lds_size_in_kb));
+                    ui.label(mem.join(", "));
+                    ui.label(format!("MEC {}, SDMA {}", node.fw_version, node.sdma_fw_version));
+                } else {
+                    ui.label("CPU");
+                    ui.label(node.index.to_string());
+                    ui.label(node.compute_units().to_string());
+                    ui.label("");
+                    ui.label("");
+                    ui.label("");
+                    ui.label(format!("{} {mhz}", node.max_engine_clk_ccompute));
+                    ui.label("");
+                    ui.label(mem.join(", "));
+                    ui.label("");
+                }
+
+                ui.end_row();
+            }
+        });
+    }
+}
+
 pub trait GuiVbiosInfo {
     fn ui(&self, ui: &mut egui::Ui);
 }
@@ -343,12 +434,12 @@ impl GuiInfo for AppDeviceInfo {
             let name = temp.type_.to_string();
             if let Some(crit) = temp.critical {
                 ui.label(format!("{name} Temp. (Critical)"));
-                ui.label(format!("{crit:4} C"));
+                ui.label(format!("{:4} C", crit.as_celsius()));
                 ui.end_row();
             }
             if let Some(e) = temp.emergency {
                 ui.label(format!("{name} Temp. (Emergency)"));
-                ui.label(format!("{e:4} C"));
+                ui.label(format!("{:4} C", e.as_celsius()));
                 ui.end_row();
             }
         }
@@ -417,6 +508,10 @@ impl GuiConnectorInfo for ConnectorInfo {
                 });
             }
 
+            if let Some(edid) = &self.edid {
+                edid.ui(&title, ui);
+            }
+
             for mode_prop in &self.mode_props {
                 mode_prop.ui(&title, ui);
             }
@@ -424,6 +519,94 @@ impl GuiConnectorInfo for ConnectorInfo {
     }
 }
 
+pub trait GuiEdid {
+    fn ui(&self, conn_name: &str, ui: &mut egui::Ui);
+}
+
+impl GuiEdid for Edid {
+    fn ui(&self, conn_name: &str, ui: &mut egui::Ui) {
+        let timings = |t: &[EdidTiming]| t.iter().map(|t| t.to_string()).collect::<Vec<_>>().join("\n");
+        let mut rows: Vec<(&str, String)> = vec![
+            ("Manufacturer", self.manufacturer.clone()),
+            ("Model", self.monitor_name.clone().unwrap_or_default()),
+            ("Product Code", format!("{:#06X}", self.product_code)),
+            ("Serial", self.serial_string.clone().unwrap_or_else(|| self.serial_number.to_string())),
+            (
+                if self.model_year { "Model Year" } else { "Manufacture Date" },
+                if let Some(week) = self.week { format!("{} week {week}", self.year) } else { self.year.to_string() },
+            ),
+            ("Version", format!("{}.{}", self.version.0, self.version.1)),
+        ];
+
+        if let Some(diagonal) = self.diagonal() {
+            rows.push(("Size", format!("{}x{} cm ({diagonal:.1}\")", self.size.0, self.size.1)));
+        }
+
+        if !self.established_timings.is_empty() {
+            rows.push(("Established Timings", self.established_timings.join("\n")));
+        }
+
+        if !self.standard_timings.is_empty() {
+            rows.push(("Standard Timings", timings(&self.standard_timings)));
+        }
+
+        if !self.detailed_timings.is_empty() {
+            rows.push(("Detailed Timings", timings(&self.detailed_timings)));
+        }
+
+        if let Some(r) = self.range_limits {
+            rows.push(("Range Limits", format!(
+                "{}-{} Hz, {}-{} kHz, {} MHz",
+                r.min_vrate,
+                r.max_vrate,
+                r.min_hrate,
+                r.max_hrate,
+                r.max_pixel_clock,
+            )));
+        }
+
+        if let Some((min, max)) = self.vrr_range() {
+            rows.push(("VRR Range", format!("{min}-{max} Hz")));
+        }
+
+        if let Some(cta) = &self.cta {
+            rows.push(("CTA-861", format!("rev. {}{}", cta.revision, if cta.hdmi_forum { ", HDMI Forum" } else { "" })));
+            rows.push(("VIC", format!("{:?}", cta.vics)));
+
+            if !cta.detailed_timings.is_empty() {
+                rows.push(("CTA-861 Detailed Timings", timings(&cta.detailed_timings)));
+            }
+        }
+
+        if let Some(hdr) = self.hdr_static_metadata() {
+            let lum = |v: Option<f32>| v.map(|v| format!("{v:.2} cd/m^2")).unwrap_or_else(|| "_".to_string());
+
+            rows.push(("HDR EOTF", hdr.eotf.join(", ")));
+            rows.push(("Max Luminance", lum(hdr.max_luminance)));
+            rows.push(("Max Frame-avg Luminance", lum(hdr.max_frame_avg_luminance)));
+            rows.push(("Min Luminance", lum(hdr.min_luminance)));
+        }
+
+        if let Some(displayid) = &self.displayid {
+            let blocks: Vec<&str> = displayid.data_block_tags
+                .iter()
+                .map(|tag| DisplayIdExtension::data_block_name(*tag))
+                .collect();
+
+            rows.push(("DisplayID", displayid.version_string()));
+            rows.push(("DisplayID Data Blocks", blocks.join("\n")));
+        }
+
+        collapsing_with_id(ui, "EDID", &format!("EDID {conn_name}"), false, |ui| {
+            egui::Grid::new(format!("EDID {conn_name}")).show(ui, |ui| {
+                let rows: Vec<(&str, &str)> = rows.iter().map(|(name, val)| (*name, val.as_str())).collect();
+
+                grid(ui, &rows);
+            });
+        });
+    }
+}
+
 pub trait GuiModeInfo {
     fn ui(&self, ui: &mut egui::Ui);
 }
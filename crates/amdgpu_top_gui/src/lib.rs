@@ -1,4 +1,4 @@
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, LazyLock, mpsc};
 use std::time::Duration;
 use std::ops::Range;
 use eframe::egui;
@@ -12,7 +12,7 @@ use libamdgpu_top::{
         GpuMetrics,
         MetricsInfo,
     },
-    app::AppAmdgpuTop,
+    app::{AppAmdgpuTop, AppOption},
     stat::{
         self,
         FdInfoSortType,
@@ -20,9 +20,11 @@ use libamdgpu_top::{
     },
     AppDeviceInfo,
     DevicePath,
+    GpuTopology,
     GuiMode,
     GuiWgpuBackend,
-    Sampling,
+    KfdTopology,
+    PowerSettingsBackup,
     UiArgs,
     PCI,
 };
@@ -31,13 +33,13 @@ mod gui_app_data;
 use gui_app_data::GuiAppData;
 
 mod app;
-use app::{GuiMemoryErrorCount, MyApp};
+use app::{GuiDpmTables, GuiFanInfo, GuiGrbmSe, GuiHwmonChannels, GuiMemoryErrorCount, GuiPowerState, GuiRasInfo, GuiThrottleLog, MyApp};
 
 mod gui_gpu_metrics;
 use gui_gpu_metrics::GuiGpuMetrics;
 
 mod gui_device_info;
-use gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiIpDiscovery, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
+use gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiKfdTopology, GuiIpDiscovery, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
 
 mod tab_gui;
 
@@ -91,9 +93,14 @@ pub fn run(
         device_path_list,
         update_process_index,
         no_pc,
+        pc_tables,
+        power_state_log,
         is_dark_mode,
         gui_wgpu_backend,
         gui_mode,
+        restore_power_settings,
+        allow_power_ctrl,
+        sampling,
         ..
     }: UiArgs,
 ) {
@@ -105,9 +112,17 @@ pub fn run(
         eprintln!("Error while loading languages for library_fluent {error}");
     }
 
+    let app_opt = AppOption {
+        sampling,
+        no_pc,
+        pc_tables,
+        power_state_log,
+        gfxoff_residency: true,
+        ..Default::default()
+    };
     let (mut vec_app, mut suspended_devices) = AppAmdgpuTop::create_app_and_suspended_list(
         &device_path_list,
-        &Default::default(),
+        &app_opt,
     );
 
     for app in vec_app.iter_mut() {
@@ -129,8 +144,16 @@ pub fn run(
     }
 
     let mut vec_data: Vec<_> = vec_app.iter().map(GuiAppData::new).collect();
+    let power_settings_backup: Vec<PowerSettingsBackup> = if restore_power_settings {
+        vec_app
+            .iter()
+            .map(|app| PowerSettingsBackup::from_sysfs(app.device_path.pci.get_sysfs_path()))
+            .collect()
+    } else {
+        Vec::new()
+    };
 
-    let sample = Sampling::low();
+    let sample = app_opt.sampling.pc_sampling();
 
     let selected_pci_bus = if !vec_data.iter().any(|d| selected_pci_bus == d.pci_bus) {
         vec_data.first().unwrap().pci_bus
@@ -154,6 +177,10 @@ pub fn run(
 
     SHARE_DATA.store(Arc::new(vec_arc_data.clone()));
 
+    let topology = GpuTopology::new(&device_path_list);
+    let kfd = KfdTopology::new();
+    let (power_ctrl_tx, power_ctrl_rx) = mpsc::channel::<PCI::BUS_INFO>();
+
     let mut gui_app = MyApp {
         fdinfo_sort: if arc_data.device_info.is_apu {
             FdInfoSortType::GTT
@@ -173,6 +200,14 @@ pub fn run(
         gui_mode,
         main_tab: Default::default(),
         info_tab: Default::default(),
+        allow_power_ctrl,
+        power_ctrl_tx,
+        power_ctrl_msg: None,
+        power_cap_input: None,
+        od_staging: None,
+        od_msg: None,
+        topology,
+        kfd,
     };
 
     unsafe {
@@ -206,14 +241,20 @@ pub fn run(
 
                     std::thread::sleep(sample.delay);
                 }
-
-                for app in vec_app.iter_mut() {
-                    app.update_pc_usage();
-                }
             } else {
                 std::thread::sleep(sample.to_duration());
             }
 
+            for pci_bus in power_ctrl_rx.try_iter() {
+                if let Some(sensors) = vec_app
+                    .iter_mut()
+                    .find(|app| app.device_info.pci_bus == pci_bus)
+                    .and_then(|app| app.stat.sensors.as_mut())
+                {
+                    sensors.update_power_ctrl();
+                }
+            }
+
             for app in vec_app.iter_mut() {
                 app.update(sample.to_duration());
             }
@@ -221,7 +262,6 @@ pub fn run(
             for (app, data) in vec_app.iter_mut().zip(vec_data.iter_mut()) {
                 data.stat = app.stat.clone();
                 data.update_history(now.elapsed().as_secs_f64(), no_pc);
-                if !no_pc { app.clear_pc(); }
             }
 
             {
@@ -241,7 +281,7 @@ pub fn run(
                     let Some(app) = AppAmdgpuTop::new(
                         amdgpu_dev,
                         dev.clone(),
-                        &Default::default(),
+                        &app_opt,
                     ) else { return true };
                     vec_data.push(GuiAppData::new(&app));
                     vec_app.push(app);
@@ -378,6 +418,12 @@ pub fn run(
         eprintln!("{err}");
         panic!();
     });
+
+    for backup in &power_settings_backup {
+        if let Err(e) = backup.restore() {
+            eprintln!("Failed to restore the power settings: {e}");
+        }
+    }
 }
 
 impl MyApp {
@@ -479,6 +525,11 @@ impl MyApp {
                 });
             }
 
+            if !self.kfd.is_empty() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("kfd_agents"), false, |ui| self.kfd.ui(ui, &self.device_path_list));
+            }
+
             ui.add_space(SPACE);
         });
     }
@@ -486,6 +537,9 @@ impl MyApp {
     fn egui_central_panel(&mut self, ui: &mut egui::Ui) {
         // ui.set_min_width(540.0);
         egui::ScrollArea::both().show(ui, |ui| {
+            collapsing(ui, &fl!("power_state"), true, |ui| self.buf_data.stat.power_state.ui(ui));
+            ui.add_space(SPACE);
+
             if !self.no_pc {
                 collapsing(ui, &fl!("grbm"), true, |ui| self.egui_perf_counter(
                     ui,
@@ -501,6 +555,16 @@ impl MyApp {
                     &self.buf_data.history.grbm2_history,
                 ));
                 ui.add_space(SPACE);
+
+                if let Some(grbm_se) = &self.buf_data.stat.grbm_se {
+                    collapsing(ui, &fl!("grbm_se"), false, |ui| grbm_se.ui(ui));
+                    ui.add_space(SPACE);
+                }
+
+                for (pc, history) in self.buf_data.stat.extra_pc.iter().zip(self.buf_data.history.extra_pc_history.iter()) {
+                    collapsing(ui, &pc.name, false, |ui| self.egui_perf_counter(ui, &pc.name, pc, history));
+                    ui.add_space(SPACE);
+                }
             }
 
             collapsing(ui, &fl!("vram"), true, |ui| self.egui_vram(ui));
@@ -514,14 +578,60 @@ impl MyApp {
                 collapsing(ui, &fl!("xdna_fdinfo"), true, |ui| self.egui_grid_xdna_fdinfo(ui));
             }
 
+            if self.buf_data.stat.partition.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("compute_partition"), true, |ui| self.egui_partition(ui));
+            }
+
             if self.buf_data.stat.sensors.is_some() {
                 ui.add_space(SPACE);
                 collapsing(ui, &fl!("sensor"), true, |ui| self.egui_sensors(ui));
             }
 
-            if self.buf_data.support_pcie_bw {
+            if let Some(sensors) = &self.buf_data.stat.sensors
+                && !sensors.hwmon_channels.is_empty()
+            {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("hwmon_channels"), false, |ui| sensors.hwmon_channels.ui(ui));
+            }
+
+            if let Some(fan) = self.buf_data.stat.sensors.as_ref().and_then(|s| s.fan.as_ref()) {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("fan"), false, |ui| fan.ui(ui));
+            }
+
+            if let Some(sensors) = &self.buf_data.stat.sensors
+                && !sensors.dpm_tables.is_empty()
+            {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("dpm_residency"), false, |ui| sensors.dpm_tables.ui(ui));
+            }
+
+            if self.buf_data.stat.sensors.as_ref().is_some_and(|s| {
+                s.perf_level.is_some() || s.power_profile_table.is_some() || s.power_cap.is_some()
+            }) {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("power_ctrl"), false, |ui| self.egui_power_ctrl(ui));
+            }
+
+            if self.buf_data.device_info.od_clk_voltage.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("overdrive"), false, |ui| self.egui_od(ui));
+            }
+
+            if self.buf_data.stat.pcie.has_stat() || self.buf_data.stat.link_health.is_some() {
                 ui.add_space(SPACE);
-                collapsing(ui, &fl!("pcie_bw"), true, |ui| self.egui_pcie_bw(ui));
+                collapsing_with_id(ui, &self.pcie_title(), "pcie", true, |ui| self.egui_pcie(ui));
+            }
+
+            if self.buf_data.stat.runtime_pm.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("runtime_pm"), false, |ui| self.egui_runtime_pm(ui));
+            }
+
+            if self.buf_data.stat.display.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("display"), false, |ui| self.egui_display(ui));
             }
 
             if let Some(ecc) = &self.buf_data.stat.memory_error_count {
@@ -529,17 +639,33 @@ impl MyApp {
                 collapsing(ui, &fl!("ecc_memory_error_count"), true, |ui| ecc.ui(ui));
             }
 
+            if let Some(ras) = &self.buf_data.stat.ras {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("ras"), false, |ui| ras.ui(ui));
+            }
+
             if self.buf_data.stat.metrics.is_some() {
                 ui.add_space(SPACE);
                 self.egui_gpu_metrics(ui);
             }
 
+            if self.has_topology() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("topology"), false, |ui| self.egui_topology(ui));
+            }
+
             collapsing(ui, &fl!("throttling_log"), false, |ui| {
                 for (time, value) in self.buf_data.history.throttling_history.iter() {
                     ui.label(format!("{time:.1}s: {:?}", value.get_all_throttler()));
                 }
             });
 
+            if self.buf_data.device_info.supports_gpu_metrics {
+                collapsing(ui, &fl!("throttle_timeline"), false, |ui| {
+                    self.buf_data.stat.throttle_log.ui(ui);
+                });
+            }
+
             ui.add_space(SPACE);
         });
     }
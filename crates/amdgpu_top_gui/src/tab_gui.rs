@@ -1,6 +1,7 @@
 use crate::egui;
-use crate::{collapsing, collapsing_plot, fl, label, MyApp, HEADING, BASE, SPACE, SPACE_3X};
-use crate::gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiIpDiscovery, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
+use crate::{collapsing, collapsing_plot, collapsing_with_id, fl, label, MyApp, HEADING, BASE, SPACE, SPACE_3X};
+use crate::gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiIpDiscovery, GuiKfdTopology, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
+use crate::app::{GuiDpmTables, GuiFanInfo, GuiGrbmSe, GuiHwmonChannels, GuiPowerState, GuiThrottleLog};
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
@@ -14,6 +15,7 @@ pub enum MainTab {
     Sensors,
     GpuMetrics,
     Xdna,
+    Topology,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -23,6 +25,7 @@ pub enum InfoTab {
     DeviceInfo,
     IpDiscoveryTable,
     ConnectorInfo,
+    KfdAgents,
 }
 
 impl MyApp {
@@ -56,6 +59,11 @@ impl MyApp {
                 ui.selectable_value(&mut self.main_tab, MainTab::Xdna, "XDNA");
                 ui.separator();
             }
+
+            if self.has_topology() {
+                ui.selectable_value(&mut self.main_tab, MainTab::Topology, fl!("topology"));
+                ui.separator();
+            }
         });
 
         ui.horizontal(|ui| {
@@ -86,6 +94,15 @@ impl MyApp {
                         );
                     }
 
+                    if !self.kfd.is_empty() {
+                        ui.separator();
+                        ui.selectable_value(
+                            &mut self.info_tab,
+                            InfoTab::KfdAgents,
+                            fl!("kfd_agents"),
+                        );
+                    }
+
                     ui.separator();
                 },
                 _ => {},
@@ -117,8 +134,21 @@ impl MyApp {
                         &self.buf_data.stat.grbm2,
                         &self.buf_data.history.grbm2_history,
                     );
+
+                    if let Some(grbm_se) = &self.buf_data.stat.grbm_se {
+                        ui.add(label(&fl!("grbm_se"), HEADING));
+                        grbm_se.ui(ui);
+                    }
+
+                    for (pc, history) in self.buf_data.stat.extra_pc.iter().zip(self.buf_data.history.extra_pc_history.iter()) {
+                        ui.add(label(&pc.name, HEADING));
+                        self.egui_perf_counter(ui, &pc.name, pc, history);
+                    }
                 },
                 MainTab::Activity => {
+                    self.buf_data.stat.power_state.ui(ui);
+                    ui.add_space(SPACE);
+
                     collapsing_plot(
                         ui,
                         &fl!("activity"),
@@ -133,10 +163,64 @@ impl MyApp {
                     ui.add_space(SPACE);
                     ui.add(label(&fl!("fdinfo"), HEADING));
                     self.egui_grid_fdinfo(ui);
+
+                    if self.buf_data.stat.partition.is_some() {
+                        ui.add_space(SPACE);
+                        ui.add(label(&fl!("compute_partition"), HEADING));
+                        self.egui_partition(ui);
+                    }
                 },
-                MainTab::Sensors => if self.buf_data.stat.sensors.is_some() {
+                MainTab::Sensors => if let Some(sensors) = &self.buf_data.stat.sensors {
                     ui.add(label(&fl!("sensor"), HEADING));
                     self.egui_sensors(ui);
+
+                    if !sensors.hwmon_channels.is_empty() {
+                        ui.add_space(SPACE_3X);
+                        collapsing(
+                            ui,
+                            &fl!("hwmon_channels"),
+                            false,
+                            |ui| sensors.hwmon_channels.ui(ui),
+                        );
+                    }
+
+                    if let Some(fan) = &sensors.fan {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("fan"), false, |ui| fan.ui(ui));
+                    }
+
+                    if !sensors.dpm_tables.is_empty() {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("dpm_residency"), false, |ui| sensors.dpm_tables.ui(ui));
+                    }
+
+                    if sensors.perf_level.is_some()
+                        || sensors.power_profile_table.is_some()
+                        || sensors.power_cap.is_some()
+                    {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("power_ctrl"), false, |ui| self.egui_power_ctrl(ui));
+                    }
+
+                    if self.buf_data.device_info.od_clk_voltage.is_some() {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("overdrive"), false, |ui| self.egui_od(ui));
+                    }
+
+                    if self.buf_data.stat.pcie.has_stat() || self.buf_data.stat.link_health.is_some() {
+                        ui.add_space(SPACE_3X);
+                        collapsing_with_id(ui, &self.pcie_title(), "pcie", false, |ui| self.egui_pcie(ui));
+                    }
+
+                    if self.buf_data.stat.runtime_pm.is_some() {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("runtime_pm"), false, |ui| self.egui_runtime_pm(ui));
+                    }
+
+                    if self.buf_data.stat.display.is_some() {
+                        ui.add_space(SPACE_3X);
+                        collapsing(ui, &fl!("display"), false, |ui| self.egui_display(ui));
+                    }
                 },
                 MainTab::GpuMetrics => {
                     self.egui_gpu_metrics(ui);
@@ -148,6 +232,12 @@ impl MyApp {
                             ui.label(format!("{time:.1}s: {:?}", value.get_all_throttler()));
                         }
                     );
+                    collapsing(
+                        ui,
+                        &fl!("throttle_timeline"),
+                        false,
+                        |ui| self.buf_data.stat.throttle_log.ui(ui),
+                    );
                 },
                 MainTab::Xdna => if self.buf_data.xdna_device_path.is_some() {
                     ui.add(label(&fl!("xdna_info"), HEADING));
@@ -161,6 +251,10 @@ impl MyApp {
                         |ui| self.egui_grid_xdna_fdinfo(ui),
                     );
                 },
+                MainTab::Topology => if self.has_topology() {
+                    ui.add(label(&fl!("topology"), HEADING));
+                    self.egui_topology(ui);
+                },
             }
         });
     }
@@ -208,6 +302,11 @@ impl MyApp {
                     conn.ui(ui);
                 }
             },
+            InfoTab::KfdAgents => if !self.kfd.is_empty() {
+                ui.add_space(SPACE);
+                ui.add(label(&fl!("kfd_agents"), HEADING));
+                self.kfd.ui(ui, &self.device_path_list);
+            },
         }
     }
 }
@@ -6,7 +6,10 @@ use libamdgpu_top::{
     },
     app::AppAmdgpuTop,
     DevicePath,
+    DrmObjectsInfo,
     GetNpuMetrics,
+    PpTableComparison,
+    PpTableSources,
 };
 use serde_json::{json, Map, Value};
 use crate::{amdgpu_top_version, OutputJson};
@@ -15,17 +18,81 @@ pub fn drm_info_json(device_path_list: &[DevicePath]) {
     let vec_drm_info_json: Vec<Value> = device_path_list.iter().map(|device_path| {
         let vec_conn_info = libamdgpu_top::connector_info(device_path);
         let vec_conn_info = vec_conn_info.iter().map(|conn| conn.json()).collect();
+        let objects = DrmObjectsInfo::new(device_path);
         let node = device_path.card.display();
 
         json!({
             "Node": node.to_string(),
             "Connectors": Value::Array(vec_conn_info),
+            "CRTCs": objects.crtcs.iter().map(|crtc| crtc.json()).collect::<Vec<Value>>(),
+            "Encoders": objects.encoders.iter().map(|encoder| encoder.json()).collect::<Vec<Value>>(),
+            "Planes": objects.planes.iter().map(|plane| plane.json()).collect::<Vec<Value>>(),
         })
     }).collect();
 
     println!("{}", Value::Array(vec_drm_info_json));
 }
 
+fn pp_tables_json(cmp: &PpTableComparison) -> Value {
+    let mut m = Map::new();
+
+    for (src, info) in &cmp.tables {
+        m.insert(src.to_string(), info.as_ref().map_or(Value::Null, |info| info.json()));
+    }
+
+    // the second table is compared with the first table
+    let diff = if let Some((fields, ranges)) = &cmp.diff {
+        let fields: Vec<Value> = fields.iter().map(|d| json!({
+            "name": d.name,
+            "unit": d.unit,
+            "from": d.a,
+            "to": d.b,
+        })).collect();
+        let ranges: Vec<Value> = ranges.iter().map(|r| json!({
+            "start": r.start,
+            "end": r.end,
+        })).collect();
+
+        json!({
+            "fields": fields,
+            "byte_ranges": ranges,
+        })
+    } else {
+        Value::Null
+    };
+
+    m.insert("diff".to_string(), diff);
+
+    m.into()
+}
+
+pub fn pp_table_json(device_path_list: &[DevicePath]) {
+    let vec_pp_table_json: Vec<Value> = device_path_list.iter().map(|device_path| {
+        let sources = PpTableSources::read(device_path);
+        let mut v = pp_tables_json(&sources.compare());
+
+        v["PCI"] = device_path.pci.to_string().into();
+        v["SMU version"] = sources.smu_version
+            .map(|(major, minor, rev)| format!("{major}.{minor}.{rev}"))
+            .into();
+
+        v
+    }).collect();
+
+    println!("{}", Value::Array(vec_pp_table_json));
+}
+
+pub fn pp_table_files_json(paths: &[String]) {
+    let files: Vec<Option<Vec<u8>>> = paths.iter().map(|path| std::fs::read(path).ok()).collect();
+    let tables: Vec<(&str, Option<&[u8]>)> = paths
+        .iter()
+        .zip(&files)
+        .map(|(path, bytes)| (path.as_str(), bytes.as_deref()))
+        .collect();
+
+    println!("{}", pp_tables_json(&PpTableComparison::new(&tables, None)));
+}
+
 pub fn gpu_metrics_json(_title: &str, device_path_list: &[DevicePath]) {
     let vec_metrics_json: Vec<Value> = device_path_list.iter().filter_map(|device_path| {
         let metrics = GpuMetrics::get_from_sysfs_path(&device_path.sysfs_path).ok()?.json();
@@ -233,6 +300,9 @@ impl JsonInfo for AppAmdgpuTop {
             "Power Profiles": power_profiles,
             "pp_feature_mask": pp_feature_mask,
             "NPU": self.xdna_device_path.as_ref().map(|x| x.device_name.clone()),
+            "XGMI": self.device_info.xgmi.map(|x| x.json()),
+            "Partition": self.device_info.partition.as_ref().map(|p| p.json()),
+            "KFD Node": self.device_info.kfd_node.as_ref().map(|n| n.json()),
             "Peak FP32": peak_fp32,
             "Peak Pixel Fill-Rate": peak_pixel,
             "Peak Memory Bandwidth": peak_mbw,
@@ -252,6 +322,7 @@ impl JsonInfo for AppAmdgpuTop {
             "gpu_metrics": self.stat.metrics.as_ref().map(|m| m.json()),
             "gpu_activity": self.stat.activity.json(),
             "npu_metrics": self.stat.metrics.as_ref().and_then(|m| m.get_npu_metrics()).map(|nm| nm.json()),
+            "RAS": self.stat.ras.as_ref().map(|r| r.json()),
         })
     }
 }
@@ -1,7 +1,7 @@
 #![recursion_limit = "256"]
 
 use chrono::{DateTime, Utc};
-use libamdgpu_top::{DevicePath, GetNpuMetrics, stat};
+use libamdgpu_top::{DevicePath, GetNpuMetrics, GpuTopology, KfdTopology, Sampling, stat};
 use libamdgpu_top::app::*;
 use serde_json::{json, Value};
 use std::time::{Duration, Instant};
@@ -11,7 +11,7 @@ use std::io::Write;
 mod output_json;
 use crate::output_json::FdInfoJson;
 mod dump;
-pub use dump::{dump_json, drm_info_json, gpu_metrics_json, JsonInfo};
+pub use dump::{dump_json, drm_info_json, gpu_metrics_json, pp_table_json, pp_table_files_json, JsonInfo};
 
 pub fn version_json(title: &str) {
     let version = json!({
@@ -37,13 +37,15 @@ pub struct JsonApp {
     pub vec_device_info: Vec<JsonDeviceInfo>,
     pub sus_app_list: Vec<DevicePath>,
     pub base_time: Instant,
-    pub interval: Duration,
     pub duration_time: Duration,
-    pub delay: Duration,
+    pub sample: Sampling,
+    pub app_opt: AppOption,
     pub iterations: u32,
     pub no_pc: bool,
     pub amdgpu_top_version: Value,
     pub rocm_version: Value,
+    pub topology: Value,
+    pub kfd_agents: Value,
     pub title: String,
     pub timestamp: DateTime<Utc>,
 }
@@ -52,15 +54,16 @@ impl JsonApp {
     pub fn new(
         title: &str,
         device_path_list: &[DevicePath],
-        refresh_period: u64,
+        app_opt: AppOption,
         update_process_index_interval: u64,
         iterations: u32,
-        no_pc: bool,
     ) -> Self {
-        let interval = Duration::from_millis(refresh_period);
-        let delay = interval / 100;
+        // The output period is the shortest interval of the sampling config.
+        let sample = app_opt.sampling.pc_sampling();
+        let interval = sample.to_duration();
+        let no_pc = app_opt.no_pc;
         let (mut vec_device_info, sus_app_list) =
-            JsonDeviceInfo::from_device_path_list(device_path_list);
+            JsonDeviceInfo::from_device_path_list(device_path_list, &app_opt);
 
         for device in vec_device_info.iter_mut() {
             device.app.stat.fdinfo.interval = interval;
@@ -88,12 +91,14 @@ impl JsonApp {
             sus_app_list,
             base_time,
             duration_time,
-            interval,
-            delay,
+            sample,
+            app_opt,
             iterations,
             no_pc,
             amdgpu_top_version: amdgpu_top_version(),
             rocm_version: libamdgpu_top::get_rocm_version().map_or(Value::Null, Value::String),
+            topology: GpuTopology::new(device_path_list).json(),
+            kfd_agents: KfdTopology::new().json(),
             title: title.to_string(),
             timestamp: Utc::now(),
         }
@@ -105,28 +110,18 @@ impl JsonApp {
 
     pub fn update(&mut self) {
         if !self.no_pc {
-            for device in self.vec_device_info.iter_mut() {
-                device.app.clear_pc();
-            }
-        }
-
-        if !self.no_pc {
-            for _ in 0..100 {
+            for _ in 0..self.sample.count {
                 for device in self.vec_device_info.iter_mut() {
                     device.app.update_pc();
                 }
-                std::thread::sleep(self.delay);
-            }
-
-            for device in self.vec_device_info.iter_mut() {
-                device.app.update_pc_usage();
+                std::thread::sleep(self.sample.delay);
             }
         } else {
-            std::thread::sleep(self.delay * 100);
+            std::thread::sleep(self.sample.to_duration());
         }
 
         for device in self.vec_device_info.iter_mut() {
-            device.app.update(self.interval);
+            device.app.update(self.sample.to_duration());
         }
 
         self.sus_app_list.retain(|sus_device| {
@@ -137,7 +132,7 @@ impl JsonApp {
                 let Some(mut app) = AppAmdgpuTop::new(
                     amdgpu_dev,
                     sus_device.clone(),
-                    &Default::default(),
+                    &self.app_opt,
                 ) else { return true };
                 let info = app.json_info();
                 self.vec_device_info.push(JsonDeviceInfo { app, info });
@@ -173,6 +168,8 @@ impl JsonApp {
             "suspended_devices_len": sus_devices.len(),
             "amdgpu_top_version": self.amdgpu_top_version,
             "ROCm version": self.rocm_version,
+            "topology": self.topology,
+            "kfd_agents": self.kfd_agents,
             "title": self.title,
             "timestamp": self.timestamp.to_rfc3339(),
         })
@@ -221,13 +218,13 @@ pub struct JsonDeviceInfo {
 }
 
 impl JsonDeviceInfo {
-    pub fn from_device_path_list(device_path_list: &[DevicePath]) -> (
+    pub fn from_device_path_list(device_path_list: &[DevicePath], opt: &AppOption) -> (
         Vec<Self>,
         Vec<DevicePath>,
     ) {
         let (vec_app, sus_app_list) = AppAmdgpuTop::create_app_and_suspended_list(
             device_path_list,
-            &Default::default(),
+            opt,
         );
         let vec_json_device = vec_app
             .into_iter()
@@ -249,6 +246,12 @@ impl JsonDeviceInfo {
             "Info": self.info,
             "GRBM": if !no_pc { self.app.stat.grbm.json() } else { Value::Null },
             "GRBM2": if !no_pc { self.app.stat.grbm2.json() } else { Value::Null },
+            "GRBM_SE": if !no_pc { self.app.stat.grbm_se.as_ref().map(|se| se.json()) } else { None },
+            "extra_pc": if !no_pc {
+                Value::Object(self.app.stat.extra_pc.iter().map(|pc| (pc.name.clone(), pc.json())).collect())
+            } else {
+                Value::Null
+            },
             "VRAM": self.app.stat.vram_usage.json(),
             "Sensors": self.app.stat.sensors.as_ref().map(|s| s.json()),
             "fdinfo": self.app.stat.fdinfo.json(),
@@ -257,6 +260,16 @@ impl JsonDeviceInfo {
             "gpu_metrics": self.app.stat.metrics.as_ref().map(|m| m.json()),
             "gpu_activity": self.app.stat.activity.json(),
             "npu_metrics": self.app.stat.metrics.as_ref().and_then(|m| m.get_npu_metrics()).map(|nm| nm.json()),
+            "throttle_log": self.app.stat.throttle_log.json(),
+            "RAS": self.app.stat.ras.as_ref().map(|r| r.json()),
+            "power_state": self.app.stat.power_state.json(),
+            "runtime_pm": self.app.stat.runtime_pm.as_ref().map(|r| r.json()),
+            "gfxoff": self.app.stat.gfxoff.json(),
+            "PCIe": self.app.stat.pcie.json(),
+            "link_health": self.app.stat.link_health.as_ref().map(|l| l.json()),
+            "XGMI": self.app.stat.xgmi.has_stat().then(|| self.app.stat.xgmi.json()),
+            "partitions": self.app.stat.partition.as_ref().map(|p| p.json()),
+            "display": self.app.stat.display.as_ref().map(|d| d.json()),
         })
     }
 }
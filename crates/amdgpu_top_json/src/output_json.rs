@@ -6,11 +6,30 @@ use libamdgpu_top::{
     VramUsage,
     PCI,
     ConnectorInfo,
+    DisplayStat,
+    Edid,
+    EdidTiming,
+    CrtcInfo,
+    EncoderInfo,
+    PlaneInfo,
+    DrmFormatModifier,
+    fourcc_name,
+    PpTableInfo,
     NpuMetrics,
     drmModePropType,
     drmModeModeInfo,
+    FanInfo,
+    FanOdValue,
+    RasInfo,
+    AerCounts,
+    LinkHealth,
+    GpuTopology,
+    KfdNode,
+    KfdTopology,
+    PartitionInfo,
+    XgmiInfo,
 };
-use stat::{FdInfoStat, FdInfoUsage, GpuActivity, Sensors, PerfCounter, ProcUsage};
+use stat::{DpmTable, FdInfoStat, GfxoffResidency, GrbmSe, FdInfoUsage, GpuActivity, HwmonChannel, KfdProcUsage, Sensors, PartitionStat, PcieStat, PerfCounter, PowerStateMachine, ProcUsage, RuntimePm, ThrottleLog, ThrottleReason, XgmiStat};
 use xdna::{XdnaFdInfoUsage, XdnaFdInfoStat};
 use serde_json::{json, Map, Value};
 use crate::OutputJson;
@@ -68,6 +87,8 @@ impl OutputJson for Sensors {
             ("VDDGFX", self.vddgfx, "mV"),
             ("Fan", self.fan_rpm, "RPM"),
             ("Fan Max", self.fan_max_rpm, "RPM"),
+            ("Fan Target", self.fan.as_ref().and_then(|f| f.target_rpm), "RPM"),
+            ("Fan PWM", self.fan.as_ref().and_then(|f| f.pwm.map(|pwm| pwm as u32)), "PWM"),
         ] {
             m.insert(
                 label.to_string(),
@@ -82,16 +103,45 @@ impl OutputJson for Sensors {
             ("GFX Power", &self.any_hwmon_power()),
             ("Average Power", &self.average_power),
             ("Input Power", &self.input_power),
+            ("Power2", &self.power2),
         ] {
             m.insert(
                 label.to_string(),
                 val.as_ref().map_or(Value::Null, |power| json!({
-                    "value": power.value,
+                    "value": power.value.as_watts_f64(),
                     "unit": "W",
+                    "label": power.label,
                 })),
             );
         }
 
+        for (label, val) in [
+            ("in0", &self.voltage_in0),
+            ("in1", &self.voltage_in1),
+        ] {
+            m.insert(
+                format!("Voltage {label}"),
+                val.as_ref().map_or(Value::Null, |v| json!({
+                    "value": v.value.0,
+                    "unit": "mV",
+                    "label": v.label,
+                })),
+            );
+        }
+
+        let currents: Vec<Value> = self.currents
+            .iter()
+            .map(|c| json!({
+                "value": c.value.as_amperes_f64(),
+                "unit": "A",
+                "label": c.name(),
+            }))
+            .collect();
+        m.insert(
+            "Currents".to_string(),
+            Value::Array(currents),
+        );
+
         for (label, temp, unit) in [
             ("Edge", &self.edge_temp, "C"),
             ("Junction", &self.junction_temp, "C"),
@@ -100,7 +150,7 @@ impl OutputJson for Sensors {
             m.insert(
                 format!("{label} Temperature"),
                 temp.as_ref().map_or(Value::Null, |temp| json!({
-                    "value": temp.current,
+                    "value": temp.current.as_celsius_f64(),
                     "unit": unit,
                 })),
             );
@@ -108,7 +158,7 @@ impl OutputJson for Sensors {
             m.insert(
                 format!("{label} Critical Temperature"),
                 temp.as_ref().map_or(Value::Null, |temp| json!({
-                    "value": temp.critical,
+                    "value": temp.critical.map(|c| c.as_celsius_f64()),
                     "unit": unit,
                 })),
             );
@@ -116,7 +166,7 @@ impl OutputJson for Sensors {
             m.insert(
                 format!("{label} Emergency Temperature"),
                 temp.as_ref().map_or(Value::Null, |temp| json!({
-                    "value": temp.emergency,
+                    "value": temp.emergency.map(|e| e.as_celsius_f64()),
                     "unit": unit,
                 })),
             );
@@ -125,7 +175,7 @@ impl OutputJson for Sensors {
         m.insert(
             "CPU Tctl".to_string(),
             self.tctl.as_ref().map_or(Value::Null, |tctl| json!({
-                "value": tctl / 1000,
+                "value": tctl.as_celsius_f64(),
                 "unit": "C",
             })),
         );
@@ -145,6 +195,21 @@ impl OutputJson for Sensors {
             Value::Array(all_cpu_core_freq),
         );
 
+        m.insert(
+            "hwmon".to_string(),
+            Value::Array(self.hwmon_channels.iter().map(|ch| ch.json()).collect()),
+        );
+
+        m.insert(
+            "Fan".to_string(),
+            self.fan.as_ref().map_or(Value::Null, |fan| fan.json()),
+        );
+
+        m.insert(
+            "DPM".to_string(),
+            Value::Array(self.dpm_tables.iter().map(|t| t.json()).collect()),
+        );
+
         m.insert(
             "PCIe Link Speed".to_string(),
             self.current_link.map_or(Value::Null, |link| link.json()),
@@ -157,13 +222,439 @@ impl OutputJson for Sensors {
 
         m.insert(
             "Power Profile".to_string(),
-            self.power_profile.map_or(Value::Null, |pp| Value::String(pp.to_string())),
+            self.power_profile().map_or(Value::Null, |mode| Value::String(mode.name.clone())),
         );
 
         m.into()
     }
 }
 
+impl OutputJson for HwmonChannel {
+    fn json(&self) -> Value {
+        let mut limits = Map::new();
+
+        for (name, val) in self.limits() {
+            limits.insert(
+                name.to_string(),
+                val.map_or(Value::Null, |val| Value::from(self.to_display_value(val))),
+            );
+        }
+
+        json!({
+            "type": self.type_.to_string(),
+            "channel": format!("{}{}", self.type_.prefix(), self.index),
+            "label": self.label,
+            "value": self.input.map(|val| self.to_display_value(val)),
+            "unit": self.unit(),
+            "limits": Value::Object(limits),
+        })
+    }
+}
+
+impl OutputJson for FanOdValue {
+    fn json(&self) -> Value {
+        json!({
+            "value": self.value,
+            "range": self.range,
+        })
+    }
+}
+
+impl OutputJson for FanInfo {
+    fn json(&self) -> Value {
+        let fan_curve = self.fan_curve.as_ref().map_or(Value::Null, |curve| json!({
+            "points": curve.points.iter().map(|p| json!({
+                "temp": p.temp,
+                "speed": p.speed,
+            })).collect::<Vec<Value>>(),
+            "temp_range": curve.temp_range,
+            "speed_range": curve.speed_range,
+        }));
+
+        json!({
+            "PWM Mode": self.pwm_mode.map(|mode| mode.to_string()),
+            "PWM": self.pwm,
+            "Target RPM": self.target_rpm,
+            "Min RPM": self.min_rpm,
+            "Zero RPM": self.zero_rpm_enable.map(|v| v.json()),
+            "Zero RPM Stop Temperature": self.zero_rpm_stop_temp.map(|v| v.json()),
+            "Acoustic Limit RPM": self.acoustic_limit_rpm.map(|v| v.json()),
+            "Acoustic Target RPM": self.acoustic_target_rpm.map(|v| v.json()),
+            "Fan Curve": fan_curve,
+        })
+    }
+}
+
+impl OutputJson for DpmTable {
+    fn json(&self) -> Value {
+        let levels: Vec<Value> = self.levels.iter().enumerate().map(|(i, level)| json!({
+            "label": level.label,
+            "desc": level.desc,
+            "mhz": level.mhz,
+            "active": level.is_active,
+            "residency": self.residency_percent(i),
+        })).collect();
+
+        json!({
+            "domain": self.domain.to_string(),
+            "levels": levels,
+            "samples": self.samples,
+            "duration": self.total.as_secs_f64(),
+        })
+    }
+}
+
+impl OutputJson for ThrottleLog {
+    fn json(&self) -> Value {
+        let now = self.elapsed();
+        let events: Vec<Value> = self.events.iter().map(|e| json!({
+            "throttlers": e.throttlers,
+            "reasons": e.reasons().iter().map(|r| r.to_string()).collect::<Vec<String>>(),
+            "start": e.start.as_secs_f64(),
+            "end": e.end.map(|end| end.as_secs_f64()),
+            "duration": e.duration(now).as_secs_f64(),
+        })).collect();
+        let mut residency = Map::new();
+
+        for reason in ThrottleReason::ALL {
+            residency.insert(reason.to_string(), Value::from(self.residency_percent(reason)));
+        }
+
+        let apu_residency: Vec<Value> = self.apu_residency.iter().map(|c| json!({
+            "label": c.label,
+            "value": c.value,
+            "delta": c.delta,
+            "total_delta": c.total_delta,
+        })).collect();
+
+        json!({
+            "elapsed": now.as_secs_f64(),
+            "events": events,
+            "residency": Value::Object(residency),
+            "throttle_residency": apu_residency,
+        })
+    }
+}
+
+impl OutputJson for PowerStateMachine {
+    fn json(&self) -> Value {
+        let transitions: Vec<Value> = self.transitions.iter().map(|t| json!({
+            "from": t.from.to_string(),
+            "to": t.to.to_string(),
+            "time": t.time.as_secs_f64(),
+            "reason": t.reason,
+        })).collect();
+
+        json!({
+            "state": self.state.to_string(),
+            "description": self.state.description(),
+            "duration": self.duration().as_secs_f64(),
+            "transitions": transitions,
+        })
+    }
+}
+
+impl OutputJson for RuntimePm {
+    fn json(&self) -> Value {
+        json!({
+            "runtime_status": self.status.to_string(),
+            "control": self.control.map(|c| c.to_string()),
+            "autosuspend_delay_ms": self.autosuspend_delay_ms,
+            "runtime_active_time": self.active_time.as_secs_f64(),
+            "runtime_suspended_time": self.suspended_time.as_secs_f64(),
+            "session_active_time": self.session_active_time().as_secs_f64(),
+            "session_suspended_time": self.session_suspended_time().as_secs_f64(),
+            "active_percent": self.active_percent(),
+            "suspended_percent": self.suspended_percent(),
+        })
+    }
+}
+
+impl OutputJson for GfxoffResidency {
+    fn json(&self) -> Value {
+        json!({
+            "residency": self.residency,
+            "session_residency": self.session_residency,
+            "count": self.count,
+            "session_count": self.session_count,
+            "note": self.note,
+        })
+    }
+}
+
+impl OutputJson for PcieStat {
+    fn json(&self) -> Value {
+        let counters: Map<String, Value> = self.counters.iter().map(|c| (
+            c.label.to_string(),
+            json!({
+                "value": c.value,
+                "delta": c.delta,
+                "total_delta": c.total_delta,
+            }),
+        )).collect();
+
+        json!({
+            "bandwidth_source": self.bw_source.map(|s| s.to_string()),
+            "sent": self.sent,
+            "received": self.received,
+            "bandwidth": self.bandwidth,
+            "unit": "MiB/s",
+            "counters": Value::Object(counters),
+            "current_link": self.current_link.map(|link| link.json()),
+            "min_link": self.min_link.map(|link| link.json()),
+            "max_link": self.max_link.map(|link| link.json()),
+        })
+    }
+}
+
+impl OutputJson for AerCounts {
+    fn json(&self) -> Value {
+        json!({
+            "correctable": self.correctable,
+            "nonfatal": self.nonfatal,
+            "fatal": self.fatal,
+        })
+    }
+}
+
+impl OutputJson for LinkHealth {
+    fn json(&self) -> Value {
+        let hops: Vec<Value> = self.hops.iter().map(|hop| json!({
+            "bdf": hop.bdf,
+            "current_link": hop.current_link.map(|link| link.json()),
+            "max_link": hop.max_link.map(|link| link.json()),
+            "AER": hop.aer.json(),
+            "correctable_delta": hop.correctable_delta(),
+        })).collect();
+        let issues: Vec<String> = self.issues.iter().map(|issue| issue.to_string()).collect();
+
+        json!({
+            "hops": hops,
+            "issues": issues,
+            "checked_under_load": self.checked_under_load,
+        })
+    }
+}
+
+impl OutputJson for XgmiInfo {
+    fn json(&self) -> Value {
+        json!({
+            "hive_id": self.hive_id,
+            "device_id": self.device_id,
+            "physical_id": self.physical_id,
+        })
+    }
+}
+
+impl OutputJson for XgmiStat {
+    fn json(&self) -> Value {
+        let links: Vec<Value> = self.links.iter().map(|l| json!({
+            "read_data_acc": l.read_acc,
+            "write_data_acc": l.write_acc,
+            "read": l.read,
+            "write": l.write,
+        })).collect();
+
+        json!({
+            "link_width": self.link_width,
+            "link_speed": self.link_speed,
+            "links": links,
+            "unit": "MiB/s",
+        })
+    }
+}
+
+impl OutputJson for PartitionInfo {
+    fn json(&self) -> Value {
+        let partitions: Vec<Value> = self.partitions.iter().map(|p| json!({
+            "index": p.index,
+            "render": p.render,
+            "kfd_node": p.kfd_node,
+            "XCC": p.xcc,
+        })).collect();
+
+        json!({
+            "compute_partition": self.compute_mode.to_string(),
+            "available_compute_partition": self.available_compute_modes.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+            "memory_partition": self.memory_mode.map(|m| m.to_string()),
+            "available_memory_partition": self.available_memory_modes.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+            "partitions": partitions,
+        })
+    }
+}
+
+impl OutputJson for PartitionStat {
+    fn json(&self) -> Value {
+        self.partitions.iter().map(|p| json!({
+            "index": p.index,
+            "render": p.render,
+            "XCC busy": p.xcc_busy,
+            "GFX": p.gfx,
+            "Compute": p.compute,
+            "VRAM": p.vram_usage,
+            "pids": p.processes.iter().map(|(pid, _)| *pid).collect::<Vec<_>>(),
+        })).collect()
+    }
+}
+
+impl OutputJson for KfdNode {
+    fn json(&self) -> Value {
+        let mem_banks: Vec<Value> = self.mem_banks.iter().map(|bank| json!({
+            "heap_type": bank.heap_type_name(),
+            "size_in_bytes": bank.size_in_bytes,
+            "width": bank.width,
+            "mem_clk_max": bank.mem_clk_max,
+        })).collect();
+        let caches: Vec<Value> = self.caches.iter().map(|cache| json!({
+            "processor_id_low": cache.processor_id_low,
+            "level": cache.level,
+            "size": cache.size,
+            "cache_line_size": cache.cache_line_size,
+            "type": cache.type_names(),
+        })).collect();
+        let io_links: Vec<Value> = self.io_links.iter().map(|link| json!({
+            "type": link.link_type.to_string(),
+            "node_from": link.node_from,
+            "node_to": link.node_to,
+            "weight": link.weight,
+            "min_bandwidth": link.min_bandwidth,
+            "max_bandwidth": link.max_bandwidth,
+        })).collect();
+
+        json!({
+            "node": self.index,
+            "name": self.name,
+            "gpu_id": self.gpu_id,
+            "device_type": if self.is_gpu() { "GPU" } else { "CPU" },
+            "gfx_target": self.gfx_target().map(|v| v.to_string()),
+            "render": self.render_path(),
+            "pci": self.is_gpu().then(|| self.pci_bus()),
+            "cpu_cores_count": self.cpu_cores_count,
+            "simd_count": self.simd_count,
+            "compute_units": self.compute_units(),
+            "simd_per_cu": self.simd_per_cu,
+            "wave_front_size": self.wave_front_size,
+            "max_waves_per_simd": self.max_waves_per_simd,
+            "max_waves_per_cu": self.max_waves_per_cu(),
+            "lds_size_in_kb": self.lds_size_in_kb,
+            "gds_size_in_kb": self.gds_size_in_kb,
+            "num_xcc": self.num_xcc,
+            "num_sdma_engines": self.num_sdma_engines,
+            "num_sdma_xgmi_engines": self.num_sdma_xgmi_engines,
+            "num_cp_queues": self.num_cp_queues,
+            "max_engine_clk_fcompute": self.max_engine_clk_fcompute,
+            "max_engine_clk_ccompute": self.max_engine_clk_ccompute,
+            "fw_version": self.fw_version,
+            "sdma_fw_version": self.sdma_fw_version,
+            "hive_id": self.hive_id,
+            "unique_id": self.unique_id,
+            "capability": self.capability,
+            "capability_flags": self.capability_names(),
+            "mem_banks": mem_banks,
+            "caches": caches,
+            "io_links": io_links,
+        })
+    }
+}
+
+impl OutputJson for KfdTopology {
+    fn json(&self) -> Value {
+        self.nodes.iter().map(|node| node.json()).collect()
+    }
+}
+
+impl OutputJson for GpuTopology {
+    fn json(&self) -> Value {
+        let gpus: Vec<Value> = self.gpus.iter().map(|gpu| json!({
+            "pci": gpu.pci.to_string(),
+            "DeviceName": gpu.name,
+            "kfd_node": gpu.kfd_node,
+            "XGMI": gpu.xgmi.map(|x| x.json()),
+        })).collect();
+        let mut links = Vec::new();
+
+        for from in 0..self.gpus.len() {
+            for to in (0..self.gpus.len()).filter(|to| *to != from) {
+                let Some(link) = self.link(from, to) else { continue };
+
+                links.push(json!({
+                    "from": from,
+                    "to": to,
+                    "type": link.link_type.to_string(),
+                    "hops": link.hops(),
+                    "weight": link.weight,
+                    "min_bandwidth": link.min_bandwidth,
+                    "max_bandwidth": link.max_bandwidth,
+                    "unit": "MB/s",
+                }));
+            }
+        }
+
+        let hives: Vec<Value> = self.hives().iter().map(|(id, gpus)| json!({
+            "hive_id": id,
+            "gpus": gpus,
+        })).collect();
+
+        json!({
+            "gpus": gpus,
+            "links": links,
+            "hives": hives,
+        })
+    }
+}
+
+impl OutputJson for GrbmSe {
+    fn json(&self) -> Value {
+        let se: Vec<Value> = self.se.iter().map(|pc| pc.json()).collect();
+        let mut imbalance = Map::new();
+
+        for (i, name) in self.bit_names().iter().enumerate() {
+            imbalance.insert(
+                name.to_string(),
+                json!({
+                    "value": self.imbalance(i),
+                    "unit": "%",
+                }),
+            );
+        }
+
+        json!({
+            "SE": se,
+            "imbalance": Value::Object(imbalance),
+        })
+    }
+}
+
+impl OutputJson for RasInfo {
+    fn json(&self) -> Value {
+        let blocks: Vec<Value> = self.blocks.iter().map(|b| json!({
+            "block": b.block,
+            "ue": b.ue,
+            "ce": b.ce,
+            "de": b.de,
+            "ue_delta": b.ue_delta(),
+            "ce_delta": b.ce_delta(),
+        })).collect();
+        let bad_pages: Vec<Value> = self.bad_pages.iter().map(|p| json!({
+            "address": p.address(),
+            "size": p.size,
+            "status": p.status.to_string(),
+        })).collect();
+
+        json!({
+            "features": self.features,
+            "blocks": blocks,
+            "total_ue": self.total_ue(),
+            "total_ce": self.total_ce(),
+            "bad_pages": bad_pages,
+            "eeprom": {
+                "version": self.eeprom.version,
+                "schema": self.eeprom.schema,
+            },
+        })
+    }
+}
+
 pub trait FdInfoJson {
     fn usage_json(&self, has_vcn: bool, has_vcn_unified: bool, has_vpe: bool) -> Value;
 }
@@ -237,6 +728,7 @@ impl OutputJson for FdInfoStat {
                 json!({
                     "name": pu.name,
                     "usage": pu.usage_json(has_vcn, has_vcn_unified, has_vpe),
+                    "kfd": pu.kfd.map(|kfd| kfd.json()),
                 }),
             );
         }
@@ -245,6 +737,28 @@ impl OutputJson for FdInfoStat {
     }
 }
 
+impl OutputJson for KfdProcUsage {
+    fn json(&self) -> Value {
+        json!({
+            "VRAM": {
+                "value": self.vram_usage >> 10,
+                "unit": "MiB",
+            },
+            "SDMA": {
+                "value": self.sdma,
+                "unit": "%",
+            },
+            "CU Occupancy": self.cu_occupancy,
+            "Evicted Time": {
+                "value": self.evicted_ms,
+                "unit": "ms",
+            },
+            "Compute Queues": self.compute_queues,
+            "SDMA Queues": self.sdma_queues,
+        })
+    }
+}
+
 impl OutputJson for XdnaFdInfoUsage {
     fn json(&self) -> Value {
         let mut sub = Map::new();
@@ -465,6 +979,7 @@ impl OutputJson for DevicePath {
         json!({
             "render": self.render,
             "card": self.card,
+            "partition_render": self.partition_render,
             "pci": self.pci.to_string(),
             "DeviceID": self.device_id,
             "RevisionID": self.revision_id,
@@ -513,6 +1028,192 @@ impl OutputJson for ConnectorInfo {
             "type_id": self.connector_type_id,
             "connection": self.connection.to_string(),
             "Properties": Value::Object(props),
+            "EDID": self.edid.as_ref().map(|edid| edid.json()),
+        })
+    }
+}
+
+impl OutputJson for EdidTiming {
+    fn json(&self) -> Value {
+        json!({
+            "hactive": self.hactive,
+            "vactive": self.vactive,
+            "refresh_rate": self.refresh_rate,
+        })
+    }
+}
+
+impl OutputJson for Edid {
+    fn json(&self) -> Value {
+        let timings = |t: &[EdidTiming]| t.iter().map(|t| t.json()).collect::<Vec<Value>>();
+
+        json!({
+            "manufacturer": self.manufacturer,
+            "product_code": self.product_code,
+            "serial_number": self.serial_number,
+            "serial_string": self.serial_string,
+            "monitor_name": self.monitor_name,
+            "week": self.week,
+            "year": self.year,
+            "model_year": self.model_year,
+            "version": format!("{}.{}", self.version.0, self.version.1),
+            "size": {
+                "width": self.size.0,
+                "height": self.size.1,
+                "unit": "cm",
+            },
+            "established_timings": self.established_timings,
+            "standard_timings": timings(&self.standard_timings),
+            "detailed_timings": timings(&self.detailed_timings),
+            "range_limits": self.range_limits.map(|r| json!({
+                "min_vrate": r.min_vrate,
+                "max_vrate": r.max_vrate,
+                "min_hrate": r.min_hrate,
+                "max_hrate": r.max_hrate,
+                "max_pixel_clock": r.max_pixel_clock,
+            })),
+            "vrr_range": self.vrr_range().map(|(min, max)| json!({
+                "min": min,
+                "max": max,
+                "unit": "Hz",
+            })),
+            "CTA-861": self.cta.as_ref().map(|cta| json!({
+                "revision": cta.revision,
+                "vics": cta.vics,
+                "detailed_timings": timings(&cta.detailed_timings),
+                "hdmi_forum": cta.hdmi_forum,
+                "hdr_static_metadata": cta.hdr_static_metadata.as_ref().map(|hdr| json!({
+                    "eotf": hdr.eotf,
+                    "max_luminance": hdr.max_luminance,
+                    "max_frame_avg_luminance": hdr.max_frame_avg_luminance,
+                    "min_luminance": hdr.min_luminance,
+                })),
+            })),
+            "DisplayID": self.displayid.as_ref().map(|displayid| json!({
+                "version": displayid.version_string(),
+                "data_block_tags": displayid.data_block_tags,
+                "adaptive_sync_range": displayid.adaptive_sync_range,
+            })),
+        })
+    }
+}
+
+fn drm_props_json(props: &[(String, u64)]) -> Value {
+    let m: Map<String, Value> = props.iter().map(|(name, value)| (name.clone(), Value::from(*value))).collect();
+
+    m.into()
+}
+
+impl OutputJson for CrtcInfo {
+    fn json(&self) -> Value {
+        json!({
+            "id": self.crtc.crtc_id,
+            "fb_id": self.crtc.buffer_id,
+            "mode": (self.crtc.mode_valid != 0).then(|| self.crtc.mode.json()),
+            "Properties": drm_props_json(&self.props),
+        })
+    }
+}
+
+impl OutputJson for EncoderInfo {
+    fn json(&self) -> Value {
+        json!({
+            "id": self.encoder_id,
+            "type": self.encoder_type_name(),
+            "crtc_id": self.crtc_id,
+            "possible_crtcs": self.possible_crtcs,
+            "possible_clones": self.possible_clones,
+        })
+    }
+}
+
+impl OutputJson for PlaneInfo {
+    fn json(&self) -> Value {
+        let formats = |f: &[u32]| f.iter().map(|f| fourcc_name(*f)).collect::<Vec<String>>();
+        let in_formats: Vec<Value> = self.in_formats.iter().map(|(modifier, f)| json!({
+            "modifier": modifier.0,
+            "modifier_name": modifier.name(),
+            "formats": formats(f),
+        })).collect();
+
+        json!({
+            "id": self.plane_id,
+            "type": self.plane_type_name(),
+            "crtc_id": self.crtc_id,
+            "possible_crtcs": self.possible_crtcs,
+            "formats": formats(&self.formats),
+            "IN_FORMATS": in_formats,
+            "fb": self.fb.map(|fb| json!({
+                "id": fb.fb_id,
+                "width": fb.width,
+                "height": fb.height,
+                "format": fourcc_name(fb.pixel_format),
+                "pitch": fb.pitch,
+                "modifier": fb.modifier,
+                "modifier_name": fb.modifier.map(|m| DrmFormatModifier(m).name()),
+            })),
+            "zpos": self.zpos,
+            "rotation": self.rotation_string(),
+            "alpha": self.alpha,
+            "Properties": drm_props_json(&self.props),
+        })
+    }
+}
+
+impl OutputJson for PpTableInfo {
+    fn json(&self) -> Value {
+        let mut fields = Map::new();
+
+        for f in &self.fields {
+            fields.insert(
+                f.name.to_string(),
+                json!({
+                    "value": f.value,
+                    "unit": f.unit,
+                }),
+            );
+        }
+
+        json!({
+            "generation": self.generation.to_string(),
+            "size": self.size,
+            "format_revision": self.format_revision,
+            "content_revision": self.content_revision,
+            "table_revision": self.table_revision,
+            "fields": fields,
+        })
+    }
+}
+
+impl OutputJson for DisplayStat {
+    fn json(&self) -> Value {
+        let displays: Vec<Value> = self.displays.iter().map(|d| json!({
+            "connector_id": d.connector_id,
+            "name": d.name,
+            "connected": d.connected,
+            "crtc_id": d.crtc_id,
+            "mode": d.mode.map(|m| json!({
+                "hdisplay": m.hdisplay,
+                "vdisplay": m.vdisplay,
+                "refresh_rate": m.refresh_rate,
+            })),
+            "vrr_capable": d.vrr_capable,
+            "vrr_enabled": d.vrr_enabled,
+            "max_bpc": d.max_bpc,
+            "colorspace": d.colorspace,
+            "hdr_output_metadata": d.hdr_output_metadata,
+            "dpms": d.dpms,
+            "link_status": d.link_status,
+        })).collect();
+        let events: Vec<Value> = self.events.iter().map(|e| json!({
+            "time": e.time.as_secs_f64(),
+            "connector": e.connector,
+            "change": e.change,
+        })).collect();
+
+        json!({
+            "displays": displays,
+            "events": events,
         })
     }
 }
@@ -13,10 +13,11 @@ use libamdgpu_top::app::{AppAmdgpuTop, AppAmdgpuTopStat, AppOption};
 const WIDE_TERM_COLS: u16 = 150;
 
 pub const TOGGLE_HELP: &str = concat!(
-    " (g)rbm g(r)bm2 (v)ram_usage (a)ctivity (f)dinfo se(n)sor (m)etrics \n",
+    " (g)rbm g(r)bm2 (v)ram_usage (a)ctivity (f)dinfo se(n)sor h(w)mon (F)an (D)PM (m)etrics \n",
     " (P): sort_by_pid (V): sort_by_vram (G): sort_by_gfx (M): sort_by_media \n",
-    " (R): reverse sort (T): switch theme (light/dark) \n",
-    " (h)igh_freq (q)uit \n",
+    " (R): reverse sort (T): switch theme (light/dark) (S): power_state r(u)ntime_pm (X): partition \n",
+    " (p): next power_profile (L): next perf_level (+/-/=): power_cap p(o)wer_control \n",
+    " e(x)tra_pc (s)e_breakdown (t)hrottle_log (E): RAS (d)isplay (h)igh_freq (q)uit \n",
 );
 
 #[derive(Clone)]
@@ -25,13 +26,26 @@ pub(crate) struct AppLayout {
     pub index: usize,
     pub grbm_view: PerfCounterView,
     pub grbm2_view: PerfCounterView,
+    pub extra_pc_views: Vec<PerfCounterView>,
+    pub grbm_se_view: AppTextView,
     pub vram_usage_view: VramUsageView,
     pub activity_view: ActivityView,
     pub fdinfo_view: AppTextView,
     pub xdna_fdinfo_view: AppTextView,
     pub sensors_view: AppTextView,
+    pub hwmon_view: AppTextView,
+    pub fan_view: AppTextView,
+    pub dpm_view: AppTextView,
+    pub power_ctrl_view: AppTextView,
+    pub power_ctrl_msg: String,
     pub gpu_metrics_view: AppTextView,
+    pub throttle_log_view: AppTextView,
     pub ecc_view: AppTextView,
+    pub ras_view: AppTextView,
+    pub power_state_view: AppTextView,
+    pub runtime_pm_view: AppTextView,
+    pub partition_view: AppTextView,
+    pub display_view: AppTextView,
 }
 
 impl AppLayout {
@@ -41,13 +55,26 @@ impl AppLayout {
             index,
             grbm_view: PerfCounterView::reserve(index),
             grbm2_view: PerfCounterView::reserve(index),
+            extra_pc_views: Vec::new(),
+            grbm_se_view: Default::default(),
             vram_usage_view: VramUsageView::new(index),
             activity_view: ActivityView::new(index),
             fdinfo_view: Default::default(),
             xdna_fdinfo_view: Default::default(),
             sensors_view: Default::default(),
+            hwmon_view: Default::default(),
+            fan_view: Default::default(),
+            dpm_view: Default::default(),
+            power_ctrl_view: Default::default(),
+            power_ctrl_msg: String::new(),
             gpu_metrics_view: Default::default(),
+            throttle_log_view: Default::default(),
             ecc_view: Default::default(),
+            ras_view: Default::default(),
+            power_state_view: Default::default(),
+            runtime_pm_view: Default::default(),
+            partition_view: Default::default(),
+            display_view: Default::default(),
         }
     }
 
@@ -58,19 +85,36 @@ impl AppLayout {
     ) -> Self {
         let grbm_view = PerfCounterView::new(&app_amdgpu_top.stat.grbm, index);
         let grbm2_view = PerfCounterView::new(&app_amdgpu_top.stat.grbm2, index);
+        let extra_pc_views = app_amdgpu_top.stat.extra_pc
+            .iter()
+            .map(|pc| PerfCounterView::new(pc, index))
+            .collect();
 
         Self {
             no_pc,
             index,
             grbm_view,
             grbm2_view,
+            extra_pc_views,
+            grbm_se_view: Default::default(),
             vram_usage_view: VramUsageView::new(index),
             activity_view: ActivityView::new(index),
             fdinfo_view: Default::default(),
             xdna_fdinfo_view: Default::default(),
             sensors_view: Default::default(),
+            hwmon_view: Default::default(),
+            fan_view: Default::default(),
+            dpm_view: Default::default(),
+            power_ctrl_view: Default::default(),
+            power_ctrl_msg: String::new(),
             gpu_metrics_view: Default::default(),
+            throttle_log_view: Default::default(),
             ecc_view: Default::default(),
+            ras_view: Default::default(),
+            power_state_view: Default::default(),
+            runtime_pm_view: Default::default(),
+            partition_view: Default::default(),
+            display_view: Default::default(),
         }
     }
 
@@ -92,6 +136,8 @@ impl AppLayout {
                 .title_position(HAlign::Center)
             );
 
+        layout.add_child(self.power_state_view.text.resized_panel(AppTextView::POWER_STATE_TITLE, self.index));
+
         if !self.no_pc {
             let grbm_view = self.grbm_view.resized_panel(&stat.grbm);
             let grbm2_view = self.grbm2_view.resized_panel(&stat.grbm2);
@@ -106,6 +152,34 @@ impl AppLayout {
                 layout.add_child(grbm_view);
                 layout.add_child(grbm2_view);
             }
+
+            let extra_pc_views: Vec<_> = self.extra_pc_views
+                .iter()
+                .zip(stat.extra_pc.iter())
+                .map(|(view, pc)| view.resized_panel(pc))
+                .collect();
+
+            if is_wide_term {
+                let mut views = extra_pc_views.into_iter();
+
+                while let Some(v) = views.next() {
+                    let mut row = LinearLayout::horizontal().child(v);
+
+                    if let Some(v2) = views.next() {
+                        row.add_child(v2);
+                    }
+
+                    layout.add_child(row);
+                }
+            } else {
+                for v in extra_pc_views {
+                    layout.add_child(v);
+                }
+            }
+
+            if stat.grbm_se.is_some() {
+                layout.add_child(self.grbm_se_view.text.resized_panel(AppTextView::GRBM_SE_TITLE, self.index));
+            }
         }
 
         {
@@ -126,6 +200,10 @@ impl AppLayout {
 
         layout.add_child(self.fdinfo_view.text.resized_panel(AppTextView::FDINFO_TITLE, self.index));
 
+        if stat.partition.is_some() {
+            layout.add_child(self.partition_view.text.resized_panel(AppTextView::PARTITION_TITLE, self.index));
+        }
+
         {
             let sensors_view = stat.sensors.as_ref().map(|_| self.sensors_view.text.resized_panel("Sensors", self.index));
             let metrics_view = stat.metrics.as_ref().map(|m| {
@@ -163,10 +241,44 @@ impl AppLayout {
             }
         }
 
+        if stat.sensors.as_ref().is_some_and(|s| !s.hwmon_channels.is_empty()) {
+            layout.add_child(self.hwmon_view.text.resized_panel(AppTextView::HWMON_TITLE, self.index));
+        }
+
+        if stat.sensors.as_ref().is_some_and(|s| s.fan.is_some()) {
+            layout.add_child(self.fan_view.text.resized_panel(AppTextView::FAN_TITLE, self.index));
+        }
+
+        if stat.sensors.as_ref().is_some_and(|s| !s.dpm_tables.is_empty()) {
+            layout.add_child(self.dpm_view.text.resized_panel(AppTextView::DPM_TITLE, self.index));
+        }
+
+        if stat.sensors.as_ref().is_some_and(|s| {
+            s.perf_level.is_some() || s.power_profile_table.is_some() || s.power_cap.is_some()
+        }) {
+            layout.add_child(self.power_ctrl_view.text.resized_panel(AppTextView::POWER_CTRL_TITLE, self.index));
+        }
+
+        if stat.runtime_pm.is_some() {
+            layout.add_child(self.runtime_pm_view.text.resized_panel(AppTextView::RUNTIME_PM_TITLE, self.index));
+        }
+
+        if stat.display.is_some() {
+            layout.add_child(self.display_view.text.resized_panel(AppTextView::DISPLAY_TITLE, self.index));
+        }
+
+        if stat.metrics.is_some() {
+            layout.add_child(self.throttle_log_view.text.resized_panel(AppTextView::THROTTLE_LOG_TITLE, self.index));
+        }
+
         if stat.memory_error_count.is_some() {
             layout.add_child(self.ecc_view.text.resized_panel("ECC Error Count", self.index));
         }
 
+        if stat.ras.is_some() {
+            layout.add_child(self.ras_view.text.resized_panel(AppTextView::RAS_TITLE, self.index));
+        }
+
         if let Some(xdna_device_path) = xdna_device_path {
             let title = if let Some(res_info) = xdna_res_info {
                 format!(
@@ -205,12 +317,12 @@ impl SuspendedTuiApp {
         }
     }
 
-    pub fn to_tui_app(&self) -> Option<TuiApp> {
+    pub fn to_tui_app(&self, opt: &AppOption) -> Option<TuiApp> {
         let amdgpu_dev = self.device_path.init().ok()?;
         let app_amdgpu_top = AppAmdgpuTop::new(
             amdgpu_dev,
             self.device_path.clone(),
-            &AppOption { pcie_bw: true },
+            &AppOption { pcie_bw: true, ..opt.clone() },
         )?;
 
         Some(TuiApp {
@@ -260,6 +372,13 @@ impl TuiApp {
     }
 
     pub fn update(&mut self, flags: &ToggleOptions, sample: &Sampling) {
+        if let Some(req) = flags.power_req
+            && let Some(sensors) = &mut self.app_amdgpu_top.stat.sensors
+        {
+            self.layout.power_ctrl_msg = req.apply(sensors);
+            sensors.update_power_ctrl();
+        }
+
         self.app_amdgpu_top.update(sample.to_duration());
 
         let fdinfo_sort = if flags.fdinfo_sort == FdInfoSortType::VRAM && self.app_amdgpu_top.device_info.is_apu {
@@ -283,37 +402,91 @@ impl TuiApp {
 
         if let Some(sensors) = &self.app_amdgpu_top.stat.sensors {
             let _ = self.layout.sensors_view.print_sensors(sensors);
+            let _ = self.layout.hwmon_view.print_hwmon_channels(&sensors.hwmon_channels);
+
+            if let Some(fan) = &sensors.fan {
+                let _ = self.layout.fan_view.print_fan(fan, sensors.fan_rpm);
+            }
+
+            let _ = self.layout.dpm_view.print_dpm_tables(&sensors.dpm_tables);
+
+            let _ = self.layout.power_ctrl_view.print_power_ctrl(sensors, flags.allow_power_ctrl, &self.layout.power_ctrl_msg);
         }
 
-        {
-            if let Some(arc_pcie_bw) = &self.app_amdgpu_top.stat.arc_pcie_bw {
-                let lock = arc_pcie_bw.try_lock();
-                if let Ok(pcie_bw) = &lock {
-                    let _ = self.layout.sensors_view.print_pcie_bw(pcie_bw);
-                }
+        if self.app_amdgpu_top.stat.sensors.is_some() {
+            let _ = self.layout.sensors_view.print_pcie(&self.app_amdgpu_top.stat.pcie);
+
+            if let Some(link_health) = &self.app_amdgpu_top.stat.link_health {
+                let _ = self.layout.sensors_view.print_link_health(link_health);
             }
+
+            let _ = self.layout.sensors_view.print_gfxoff_residency(&self.app_amdgpu_top.stat.gfxoff);
+        }
+
+        if let Some(runtime_pm) = &self.app_amdgpu_top.stat.runtime_pm {
+            let pci_power_state = self.app_amdgpu_top.stat.sensors.as_ref()
+                .and_then(|s| s.pci_power_state.as_deref());
+            let _ = self.layout.runtime_pm_view.print_runtime_pm(runtime_pm, pci_power_state);
+        }
+
+        if let (Some(info), Some(partition)) = (
+            &self.app_amdgpu_top.device_info.partition,
+            &self.app_amdgpu_top.stat.partition,
+        ) {
+            let _ = self.layout.partition_view.print_partition(info, partition);
+        }
+
+        if let Some(display) = &self.app_amdgpu_top.stat.display {
+            let _ = self.layout.display_view.print_display(display);
         }
 
         if let Some(ecc) = &self.app_amdgpu_top.stat.memory_error_count {
             let _ = self.layout.ecc_view.print_memory_error_count(ecc);
         }
 
+        if let Some(ras) = &self.app_amdgpu_top.stat.ras {
+            let _ = self.layout.ras_view.print_ras_info(ras);
+        }
+
         if let Some(metrics) = &self.app_amdgpu_top.stat.metrics {
             let _ = self.layout.gpu_metrics_view.print_gpu_metrics(metrics);
+            let _ = self.layout.gpu_metrics_view.print_xgmi(&self.app_amdgpu_top.stat.xgmi);
         } else {
             self.layout.gpu_metrics_view.text.clear();
         }
 
+        let _ = self.layout.throttle_log_view.print_throttle_log(&self.app_amdgpu_top.stat.throttle_log);
+        let _ = self.layout.power_state_view.print_power_state(&self.app_amdgpu_top.stat.power_state);
+
         if !self.no_pc {
             self.layout.grbm_view.set_value(&self.app_amdgpu_top.stat.grbm);
             self.layout.grbm2_view.set_value(&self.app_amdgpu_top.stat.grbm2);
+
+            for (view, pc) in self.layout.extra_pc_views.iter().zip(self.app_amdgpu_top.stat.extra_pc.iter()) {
+                view.set_value(pc);
+            }
+
+            if let Some(grbm_se) = &self.app_amdgpu_top.stat.grbm_se {
+                let _ = self.layout.grbm_se_view.print_grbm_se(grbm_se);
+            }
         }
 
         self.layout.sensors_view.text.set();
+        self.layout.hwmon_view.text.set();
+        self.layout.fan_view.text.set();
+        self.layout.dpm_view.text.set();
+        self.layout.power_ctrl_view.text.set();
         self.layout.fdinfo_view.text.set();
         self.layout.xdna_fdinfo_view.text.set();
         self.layout.ecc_view.text.set();
+        self.layout.ras_view.text.set();
+        self.layout.grbm_se_view.text.set();
         self.layout.gpu_metrics_view.text.set();
+        self.layout.throttle_log_view.text.set();
+        self.layout.power_state_view.text.set();
+        self.layout.runtime_pm_view.text.set();
+        self.layout.partition_view.text.set();
+        self.layout.display_view.text.set();
     }
 
     pub fn label(&self) -> String {
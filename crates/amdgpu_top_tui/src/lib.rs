@@ -3,7 +3,7 @@ use cursive::view::{Nameable, Scrollable};
 use cursive::{event::Key, menu, traits::With};
 use cursive::theme::{BorderStyle, Theme, Palette};
 
-use libamdgpu_top::{app::AppAmdgpuTop, DevicePath, Sampling, UiArgs};
+use libamdgpu_top::{app::{AppAmdgpuTop, AppOption}, DevicePath, PowerSettingsBackup, SamplingConfig, UiArgs};
 use libamdgpu_top::stat::{self, FdInfoSortType, PCType};
 
 mod view;
@@ -22,11 +22,26 @@ struct ToggleOptions {
     vram: bool,
     activity: bool,
     sensor: bool,
+    hwmon: bool,
+    fan: bool,
+    dpm: bool,
+    power_ctrl: bool,
+    power_req: Option<PowerCtrlRequest>,
+    allow_power_ctrl: bool,
     high_freq: bool,
     fdinfo: bool,
     fdinfo_sort: FdInfoSortType,
     reverse_sort: bool,
     gpu_metrics: bool,
+    throttle_log: bool,
+    ras: bool,
+    extra_pc: bool,
+    grbm_se: bool,
+    power_state: bool,
+    runtime_pm: bool,
+    partition: bool,
+    display: bool,
+    extra_pc_names: Vec<String>,
     select_index: usize,
     indexes: Vec<usize>,
     is_dark_mode: bool,
@@ -40,11 +55,26 @@ impl Default for ToggleOptions {
             vram: true,
             activity: true,
             sensor: true,
+            hwmon: true,
+            fan: true,
+            dpm: true,
+            power_ctrl: true,
+            power_req: None,
+            allow_power_ctrl: false,
             high_freq: false,
             fdinfo: true,
             fdinfo_sort: Default::default(),
             reverse_sort: false,
             gpu_metrics: true,
+            throttle_log: true,
+            ras: true,
+            extra_pc: true,
+            grbm_se: true,
+            power_state: true,
+            runtime_pm: true,
+            partition: true,
+            display: true,
+            extra_pc_names: Vec::new(),
             select_index: 0,
             indexes: Vec::new(),
             is_dark_mode: false,
@@ -61,18 +91,37 @@ pub fn run(
         device_path_list,
         update_process_index,
         no_pc,
+        pc_tables,
+        power_state_log,
         is_dark_mode,
         hide_fdinfo,
+        restore_power_settings,
+        allow_power_ctrl,
+        sampling,
         ..
     }: UiArgs,
 ) {
     let is_dark_mode = is_dark_mode == Some(true); // The default theme for TUI is light.
     let title = title.to_string();
-    let mut toggle_opt = ToggleOptions { is_dark_mode, fdinfo: !hide_fdinfo, ..Default::default() };
+    let mut toggle_opt = ToggleOptions { is_dark_mode, fdinfo: !hide_fdinfo, allow_power_ctrl, ..Default::default() };
 
+    // keep the idle policies (`--pc-while-idle`, `--no-d3hot`) in high frequency mode
+    let high_sampling = {
+        let mut high = SamplingConfig::high();
+        high.policies = sampling.policies.clone();
+        high
+    };
+    let app_opt = AppOption {
+        sampling,
+        no_pc,
+        pc_tables,
+        power_state_log,
+        gfxoff_residency: true,
+        ..Default::default()
+    };
     let (vec_app, suspended_devices) = AppAmdgpuTop::create_app_and_suspended_list(
         &device_path_list,
-        &Default::default(),
+        &app_opt,
     );
     let mut vec_app: Vec<_> = vec_app
         .into_iter()
@@ -87,6 +136,10 @@ pub fn run(
         .collect();
 
     toggle_opt.indexes = vec_app.iter().map(|app| app.index).collect();
+    toggle_opt.extra_pc_names = vec_app
+        .iter()
+        .flat_map(|app| app.app_amdgpu_top.stat.extra_pc.iter().map(|pc| pc_view_name(&pc.name, app.index)))
+        .collect();
     let selected_device_path = {
         let first_app_device_path = vec_app.first().unwrap().app_amdgpu_top.device_path.clone();
 
@@ -102,7 +155,8 @@ pub fn run(
             toggle_opt.select_index = app.index;
         }
 
-        app.update(&toggle_opt, &Sampling::low());
+        let sample = app.app_amdgpu_top.pc_sampling();
+        app.update(&toggle_opt, &sample);
     }
 
     {
@@ -172,6 +226,7 @@ pub fn run(
     }
 
     let mut flags = toggle_opt.clone();
+    let mut high_freq = flags.high_freq;
     let toggle_opt = Arc::new(Mutex::new(toggle_opt));
 
     siv.set_autohide_menu(false);
@@ -182,6 +237,8 @@ pub fn run(
         if !no_pc {
             siv.add_global_callback('g', pc_type_cb(PCType::GRBM));
             siv.add_global_callback('r', pc_type_cb(PCType::GRBM2));
+            siv.add_global_callback('x', extra_pc_cb);
+            siv.add_global_callback('s', AppTextView::cb_grbm_se);
         }
         siv.add_global_callback('v', VramUsageView::cb);
         siv.add_global_callback('a', ActivityView::cb);
@@ -193,7 +250,25 @@ pub fn run(
         siv.add_global_callback('G', AppTextView::cb_sort_by_gfx);
         siv.add_global_callback('M', AppTextView::cb_sort_by_media);
         siv.add_global_callback('n', AppTextView::cb_sensors);
+        siv.add_global_callback('w', AppTextView::cb_hwmon);
+        siv.add_global_callback('F', AppTextView::cb_fan);
+        siv.add_global_callback('D', AppTextView::cb_dpm);
+        siv.add_global_callback('o', AppTextView::cb_power_ctrl);
+        // writing to sysfs requires an explicit opt-in, `--allow-power-ctrl`
+        if allow_power_ctrl {
+            siv.add_global_callback('p', power_req_cb(PowerCtrlRequest::NextPowerProfile));
+            siv.add_global_callback('L', power_req_cb(PowerCtrlRequest::NextPerfLevel));
+            siv.add_global_callback('+', power_req_cb(PowerCtrlRequest::IncreasePowerCap));
+            siv.add_global_callback('-', power_req_cb(PowerCtrlRequest::DecreasePowerCap));
+            siv.add_global_callback('=', power_req_cb(PowerCtrlRequest::ResetPowerCap));
+        }
         siv.add_global_callback('m', AppTextView::cb_gpu_metrics);
+        siv.add_global_callback('t', AppTextView::cb_throttle_log);
+        siv.add_global_callback('E', AppTextView::cb_ras);
+        siv.add_global_callback('S', AppTextView::cb_power_state);
+        siv.add_global_callback('u', AppTextView::cb_runtime_pm);
+        siv.add_global_callback('X', AppTextView::cb_partition);
+        siv.add_global_callback('d', AppTextView::cb_display);
         siv.add_global_callback('q', cursive::Cursive::quit);
         siv.add_global_callback('h', |siv| {
             let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
@@ -217,22 +292,39 @@ pub fn run(
     }
 
     let cb_sink = siv.cb_sink().clone();
+    let power_settings_backup: Vec<PowerSettingsBackup> = if restore_power_settings {
+        vec_app
+            .iter()
+            .map(|app| PowerSettingsBackup::from_sysfs(app.app_amdgpu_top.device_path.pci.get_sysfs_path()))
+            .collect()
+    } else {
+        Vec::new()
+    };
 
     std::thread::spawn(move || loop {
         {
             let lock = toggle_opt.try_lock();
-            if let Ok(opt) = lock {
+            if let Ok(mut opt) = lock {
                 flags = opt.clone();
+                opt.power_req = None;
             }
         }
 
-        let sample = if flags.high_freq { Sampling::high() } else { Sampling::low() };
+        if flags.high_freq != high_freq {
+            high_freq = flags.high_freq;
+            let sampling = if high_freq { &high_sampling } else { &app_opt.sampling };
+
+            for app in vec_app.iter_mut() {
+                app.app_amdgpu_top.set_sampling_config(sampling.clone());
+            }
+        }
 
         {
             let selected_app = vec_app
                 .iter_mut()
                 .find(|app| flags.select_index == app.index)
                 .unwrap();
+            let sample = selected_app.app_amdgpu_top.pc_sampling();
 
             if !no_pc {
                 for _ in 0..sample.count {
@@ -240,14 +332,12 @@ pub fn run(
 
                     std::thread::sleep(sample.delay);
                 }
-
-                selected_app.app_amdgpu_top.update_pc_usage();
             } else {
                 std::thread::sleep(sample.to_duration());
             }
 
             selected_app.update(&flags, &sample);
-            if !no_pc { selected_app.app_amdgpu_top.clear_pc(); }
+            flags.power_req = None;
         }
 
         vec_sus_app.retain(|sus_app| {
@@ -255,7 +345,8 @@ pub fn run(
 
             if is_active {
                 let title = title.clone();
-                let Some(tui_app) = sus_app.to_tui_app() else { return true };
+                let Some(mut tui_app) = sus_app.to_tui_app(&app_opt) else { return true };
+                if high_freq { tui_app.app_amdgpu_top.set_sampling_config(high_sampling.clone()); }
                 let index = tui_app.index;
                 let label = tui_app.label();
                 let info_bar = tui_app.app_amdgpu_top.device_info.info_bar();
@@ -310,6 +401,12 @@ pub fn run(
     });
 
     siv.run();
+
+    for backup in &power_settings_backup {
+        if let Err(e) = backup.restore() {
+            eprintln!("Failed to restore the power settings: {e}");
+        }
+    }
 }
 
 fn light_mode() -> Theme {
@@ -14,7 +14,7 @@ const LINE_LEN: usize = 150;
 const THR_LEN: usize = 57;
 const PROC_TITLE: &str = "Processes";
 
-use libamdgpu_top::app::AppAmdgpuTop;
+use libamdgpu_top::app::{AppAmdgpuTop, AppOption};
 
 struct SmiApp {
     app_amdgpu_top: AppAmdgpuTop,
@@ -51,7 +51,7 @@ impl SmiApp {
 
         writeln!(
             self.info_text.buf,
-            "#{i:<2} [{name:GPU_NAME_LEN$}]({gfx_ver:>7})| {pci}   |{vu:6}/{vt:6} MiB |",
+            "#{i:<2} [{name:GPU_NAME_LEN$}]({gfx_ver:>7})| {pci}{link:>3}|{vu:6}/{vt:6} MiB |",
             i = self.index,
             name = self.app_amdgpu_top.device_info.marketing_name
                 .get(..GPU_NAME_LEN)
@@ -61,6 +61,12 @@ impl SmiApp {
                 None => "",
             },
             pci = self.app_amdgpu_top.device_info.pci_bus,
+            // PCIe link health warning
+            link = if self.app_amdgpu_top.stat.link_health.as_ref().is_some_and(|l| l.has_warning()) {
+                "(!)"
+            } else {
+                ""
+            },
             vu = self.app_amdgpu_top.stat.vram_usage.0.vram.heap_usage >> 20,
             vt = self.app_amdgpu_top.stat.vram_usage.0.vram.total_heap_size >> 20,
         )?;
@@ -87,8 +93,8 @@ impl SmiApp {
             sensors.and_then(|s| s.power_cap.as_ref()),
         ) {
             (Some(power), Some(cap)) =>
-                write!(self.info_text.buf, " {:>3}/{:>3}W ", power.value, cap.current)?,
-            (Some(power), None) => write!(self.info_text.buf, " {:>3}/___W ", power.value)?,
+                write!(self.info_text.buf, " {:>3}/{:>3}W ", power.value.as_watts(), cap.current)?,
+            (Some(power), None) => write!(self.info_text.buf, " {:>3}/___W ", power.value.as_watts())?,
             _ => write!(self.info_text.buf, " ___/___W ")?,
         }
 
@@ -124,9 +130,14 @@ impl SmiApp {
 
         if let Some(temp) = sensors.and_then(|s| s.junction_temp.as_ref().or(s.edge_temp.as_ref())) {
             if let Some(mem_temp) = sensors.and_then(|s| s.memory_temp.as_ref()) {
-                write!(self.info_text.buf, "{:>3}C/{:>3}C", temp.current, mem_temp.current)?;
+                write!(
+                    self.info_text.buf,
+                    "{:>3}C/{:>3}C",
+                    temp.current.as_celsius(),
+                    mem_temp.current.as_celsius(),
+                )?;
             } else {
-                write!(self.info_text.buf, "{:>3}C/___C", temp.current)?;
+                write!(self.info_text.buf, "{:>3}C/___C", temp.current.as_celsius())?;
             }
         } else {
             write!(self.info_text.buf, "___C/___C")?;
@@ -173,7 +184,7 @@ impl SmiApp {
             }
 
             if let Some(tctl) = sensors.tctl {
-                write!(self.info_text.buf, " Tctl:{:>3}C", tctl / 1000)?;
+                write!(self.info_text.buf, " Tctl:{:>3}C", tctl.as_celsius())?;
             } else {
                 write!(self.info_text.buf, " Tctl:___C")?;
             }
@@ -286,9 +297,9 @@ impl SuspendedSmiApp {
             .title_position(HAlign::Left)
     }
 
-    fn to_smi_app(&self) -> Option<SmiApp> {
+    fn to_smi_app(&self, opt: &AppOption) -> Option<SmiApp> {
         let amdgpu_dev = self.device_path.init().ok()?;
-        let app_amdgpu_top = AppAmdgpuTop::new(amdgpu_dev, self.device_path.clone(), &Default::default())?;
+        let app_amdgpu_top = AppAmdgpuTop::new(amdgpu_dev, self.device_path.clone(), opt)?;
         let gfxoff_monitor = GfxoffMonitor::new(self.device_path.pci).ok();
 
         Some(SmiApp {
@@ -306,10 +317,15 @@ pub fn run_smi(
     UiArgs {
         device_path_list,
         update_process_index,
+        no_pc,
+        pc_tables,
+        power_state_log,
+        sampling,
         ..
     }: UiArgs,
 ) {
-    let sample = Sampling::low();
+    let sample = sampling.pc_sampling();
+    let app_opt = AppOption { sampling, no_pc, pc_tables, power_state_log, ..Default::default() };
     let mut vec_app: Vec<SmiApp> = Vec::with_capacity(device_path_list.len());
     let mut sus_app_devices: Vec<_> = device_path_list
         .iter()
@@ -365,7 +381,7 @@ pub fn run_smi(
     let cb_sink = siv.cb_sink().clone();
 
     {
-        extract_active_devices(&mut vec_app, &mut sus_app_devices);
+        extract_active_devices(&mut vec_app, &mut sus_app_devices, &app_opt);
 
         for app in vec_app.iter_mut() {
             app.update(&sample);
@@ -373,13 +389,13 @@ pub fn run_smi(
     }
 
     std::thread::spawn(move || loop {
-        std::thread::sleep(sample.to_duration()); // 1s
+        std::thread::sleep(sample.to_duration());
 
         for app in vec_app.iter_mut() {
             app.update(&sample);
         }
 
-        extract_active_devices(&mut vec_app, &mut sus_app_devices);
+        extract_active_devices(&mut vec_app, &mut sus_app_devices, &app_opt);
 
         cb_sink.send(Box::new(cursive::Cursive::noop)).unwrap();
     });
@@ -387,12 +403,16 @@ pub fn run_smi(
     siv.run();
 }
 
-fn extract_active_devices(vec_app: &mut Vec<SmiApp>, sus_app_devices: &mut Vec<SuspendedSmiApp>) {
+fn extract_active_devices(
+    vec_app: &mut Vec<SmiApp>,
+    sus_app_devices: &mut Vec<SuspendedSmiApp>,
+    opt: &AppOption,
+) {
     sus_app_devices.retain(|sus_app| {
         let is_active = sus_app.device_path.check_if_device_is_active();
 
         if is_active {
-            let Some(smi_app) = sus_app.to_smi_app() else { return true };
+            let Some(smi_app) = sus_app.to_smi_app(opt) else { return true };
             vec_app.push(smi_app);
         }
 
@@ -0,0 +1,91 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::DisplayStat;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const DISPLAY_TITLE: &str = "Display";
+
+    pub fn print_display(&mut self, stat: &DisplayStat) -> Result<(), fmt::Error> {
+        const MAX_LOG_LINES: usize = 4;
+        self.text.clear();
+
+        for d in stat.connected() {
+            let mode = d.mode.map_or("disabled".to_string(), |m| m.to_string());
+            let vrr = match (d.vrr_capable, d.vrr_enabled) {
+                (Some(false), _) => "_",
+                (_, Some(true)) => "on",
+                (_, Some(false)) => "off",
+                _ => "_",
+            };
+
+            write!(self.text.buf, " {:<16} {mode:<18} VRR: {vrr:<3}", d.name)?;
+
+            if let Some(bpc) = d.max_bpc {
+                write!(self.text.buf, ", max bpc: {bpc:>2}")?;
+            }
+
+            if let Some(colorspace) = &d.colorspace {
+                write!(self.text.buf, ", {colorspace}")?;
+            }
+
+            if d.hdr_output_metadata {
+                write!(self.text.buf, ", HDR")?;
+            }
+
+            if let Some(dpms) = &d.dpms {
+                write!(self.text.buf, ", DPMS: {dpms}")?;
+            }
+
+            if let Some(link_status) = &d.link_status {
+                write!(self.text.buf, ", link: {link_status}")?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        if stat.connected().count() == 0 {
+            writeln!(self.text.buf, " No display connected")?;
+        }
+
+        for e in stat.events.iter().rev().take(MAX_LOG_LINES) {
+            writeln!(
+                self.text.buf,
+                " {:>7.1}s {}: {}",
+                e.time.as_secs_f64(),
+                e.connector,
+                e.change,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn display_name(index: usize) -> String {
+        format!("{} {index}", Self::DISPLAY_TITLE)
+    }
+
+    pub fn cb_display(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.display ^= true;
+
+            visible = opt.display;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::display_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::DpmTable;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const DPM_TITLE: &str = "DPM Residency";
+
+    pub fn print_dpm_tables(&mut self, tables: &[DpmTable]) -> Result<(), fmt::Error> {
+        const BAR_WIDTH: usize = 20;
+        self.text.clear();
+
+        for table in tables {
+            writeln!(self.text.buf, " {}:", table.domain)?;
+
+            for (i, level) in table.levels.iter().enumerate() {
+                let mark = if level.is_active { "*" } else { " " };
+                let per = table.residency_percent(i);
+                let fill = (per as usize * BAR_WIDTH) / 100;
+
+                writeln!(
+                    self.text.buf,
+                    "  {mark}{:>2}: {:<22} [{:<BAR_WIDTH$}] {per:5.1}%",
+                    level.label,
+                    level.desc,
+                    "|".repeat(fill),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn dpm_name(index: usize) -> String {
+        format!("{} {index}", Self::DPM_TITLE)
+    }
+
+    pub fn cb_dpm(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.dpm ^= true;
+
+            visible = opt.dpm;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::dpm_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
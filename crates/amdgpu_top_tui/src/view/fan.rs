@@ -0,0 +1,104 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::{FanInfo, FanOdValue};
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const FAN_TITLE: &str = "Fan";
+
+    pub fn print_fan(&mut self, fan: &FanInfo, fan_rpm: Option<u32>) -> Result<(), fmt::Error> {
+        const CHART_WIDTH: usize = 40;
+        self.text.clear();
+
+        if let Some(mode) = fan.pwm_mode {
+            write!(self.text.buf, " Mode => {mode},")?;
+        }
+
+        if let (Some(pwm), Some(per)) = (fan.pwm, fan.pwm_percent()) {
+            write!(self.text.buf, " PWM => {per:>3}% ({pwm:>3}/255)")?;
+        }
+
+        writeln!(self.text.buf)?;
+
+        if let Some(target_rpm) = fan.target_rpm {
+            write!(self.text.buf, " Target => {target_rpm:4} RPM")?;
+        }
+
+        if let Some(min_rpm) = fan.min_rpm {
+            write!(self.text.buf, " Min => {min_rpm:4} RPM")?;
+        }
+
+        if fan.is_zero_rpm(fan_rpm) {
+            write!(self.text.buf, " (Zero RPM)")?;
+        }
+
+        writeln!(self.text.buf)?;
+
+        for (name, val, unit) in [
+            ("Zero RPM", fan.zero_rpm_enable, ""),
+            ("Zero RPM Stop Temp.", fan.zero_rpm_stop_temp, "C"),
+            ("Acoustic Limit", fan.acoustic_limit_rpm, "RPM"),
+            ("Acoustic Target", fan.acoustic_target_rpm, "RPM"),
+        ] {
+            let Some(FanOdValue { value, range }) = val else { continue };
+
+            write!(self.text.buf, " {name:<19} => {value:4} {unit:<3}")?;
+
+            if let Some([min, max]) = range {
+                write!(self.text.buf, " ({min}-{max} {unit})")?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        let Some(curve) = &fan.fan_curve else { return Ok(()) };
+
+        writeln!(self.text.buf, " Fan Curve (hotspot temp. => fan speed):")?;
+
+        for p in &curve.points {
+            let fill = (p.speed.min(100) as usize * CHART_WIDTH) / 100;
+
+            writeln!(
+                self.text.buf,
+                "  {:>3} C [{:<CHART_WIDTH$}] {:>3}%",
+                p.temp,
+                "|".repeat(fill),
+                p.speed,
+            )?;
+        }
+
+        if let (Some([t_min, t_max]), Some([s_min, s_max])) = (curve.temp_range, curve.speed_range) {
+            writeln!(self.text.buf, "  (Range: {t_min}-{t_max} C, {s_min}-{s_max} %)")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn fan_name(index: usize) -> String {
+        format!("{} {index}", Self::FAN_TITLE)
+    }
+
+    pub fn cb_fan(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.fan ^= true;
+
+            visible = opt.fan;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::fan_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
@@ -19,6 +19,9 @@ const ENC_LABEL: &str = "ENC";
 const VCN_UNIFIED_LABEL: &str = "VCNU";
 const VPE_LABEL: &str = "VPE";
 const KFD_LABEL: &str = "KFD";
+const KFD_VRAM_LABEL: &str = "KVRAM";
+const CU_OCCUPANCY_LABEL: &str = "CU";
+const COMPUTE_QUEUE_LABEL: &str = "CQ";
 // const UVD_ENC_LABEL: &str = "UVD (ENC)";
 // const JPEG_LABEL: &str = "JPEG";
 
@@ -52,6 +55,10 @@ impl AppTextView {
             write!(self.text.buf, "{VPE_LABEL:^4}|")?;
         }
 
+        if stat.has_kfd_stat() {
+            write!(self.text.buf, "{KFD_VRAM_LABEL:^6}|{CU_OCCUPANCY_LABEL:^4}|{COMPUTE_QUEUE_LABEL:^4}|")?;
+        }
+
         writeln!(self.text.buf)?;
 
         stat.sort_proc_usage(sort, reverse);
@@ -62,6 +69,8 @@ impl AppTextView {
     }
 
     pub fn print_fdinfo_usage(&mut self, stat: &FdInfoStat) -> Result<(), fmt::Error> {
+        let has_kfd_stat = stat.has_kfd_stat();
+
         for pu in &stat.proc_usage {
             let utf16_count = pu.name.encode_utf16().filter(|&u| u >= 0x3000).count();
             let name_len = if pu.name.len() != utf16_count {
@@ -100,6 +109,20 @@ impl AppTextView {
                 write!(self.text.buf, "{:>3}%|", pu.usage.vpe)?;
             }
 
+            if has_kfd_stat {
+                if let Some(kfd) = &pu.kfd {
+                    write!(
+                        self.text.buf,
+                        "{:>5}M|{:>4}|{:>4}|",
+                        kfd.vram_usage >> 10,
+                        kfd.cu_occupancy,
+                        kfd.compute_queues,
+                    )?;
+                } else {
+                    write!(self.text.buf, "{:6}|{:4}|{:4}|", "", "", "")?;
+                }
+            }
+
             writeln!(self.text.buf)?;
         }
 
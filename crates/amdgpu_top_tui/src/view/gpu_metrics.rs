@@ -1,6 +1,6 @@
 use std::fmt::{self, Write};
 use libamdgpu_top::AMDGPU::{GpuMetrics, MetricsInfo};
-use libamdgpu_top::stat::{gpu_metrics_util::*, GpuActivity};
+use libamdgpu_top::stat::{gpu_metrics_util::*, GpuActivity, XgmiStat};
 
 use crate::AppTextView;
 
@@ -150,6 +150,20 @@ impl AppTextView {
         Ok(())
     }
 
+    pub fn print_xgmi(&mut self, xgmi: &XgmiStat) -> Result<(), fmt::Error> {
+        if xgmi.links.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.text.buf, "XGMI Throughput (MiB/s) => Total: {:.1}", xgmi.total())?;
+
+        for (i, link) in xgmi.links.iter().enumerate() {
+            writeln!(self.text.buf, "  Link{i}: Read {:>9.1}, Write {:>9.1}", link.read, link.write)?;
+        }
+
+        Ok(())
+    }
+
     fn gpu_metrics_v2_x(&mut self, metrics: &GpuMetrics) -> Result<(), fmt::Error> {
         let temp_gfx = metrics.get_temperature_gfx().map(|v| v.saturating_div(100));
         let temp_soc = metrics.get_temperature_soc().map(|v| v.saturating_div(100));
@@ -0,0 +1,61 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::GrbmSe;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const GRBM_SE_TITLE: &str = "GRBM SE Breakdown";
+
+    pub fn print_grbm_se(&mut self, grbm_se: &GrbmSe) -> Result<(), fmt::Error> {
+        const NAME_LEN: usize = 30;
+        self.text.clear();
+
+        write!(self.text.buf, " {:>NAME_LEN$}:", "")?;
+
+        for i in 0..grbm_se.se.len() {
+            write!(self.text.buf, " {:>5}", format!("SE{i}"))?;
+        }
+
+        writeln!(self.text.buf, " {:>5}", "Diff")?;
+
+        for (i, name) in grbm_se.bit_names().iter().enumerate() {
+            write!(self.text.buf, " {name:>NAME_LEN$}:")?;
+
+            for usage in grbm_se.usage(i) {
+                write!(self.text.buf, " {usage:>3} %")?;
+            }
+
+            writeln!(self.text.buf, " {:>3} %", grbm_se.imbalance(i))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn grbm_se_name(index: usize) -> String {
+        format!("{} {index}", Self::GRBM_SE_TITLE)
+    }
+
+    pub fn cb_grbm_se(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.grbm_se ^= true;
+
+            visible = opt.grbm_se;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::grbm_se_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
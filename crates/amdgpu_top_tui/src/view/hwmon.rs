@@ -0,0 +1,63 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::HwmonChannel;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const HWMON_TITLE: &str = "All Sensors (hwmon)";
+
+    pub fn print_hwmon_channels(&mut self, channels: &[HwmonChannel]) -> Result<(), fmt::Error> {
+        const NAME_LEN: usize = 16;
+        self.text.clear();
+
+        for ch in channels {
+            let Some(input) = ch.input else { continue };
+            let unit = ch.unit();
+
+            write!(
+                self.text.buf,
+                " {:<7} {:<NAME_LEN$} => {:>9.3} {unit:<3}",
+                format!("[{}{}]", ch.type_.prefix(), ch.index),
+                ch.name(),
+                ch.to_display_value(input),
+            )?;
+
+            for (name, val) in ch.limits() {
+                let Some(val) = val else { continue };
+                write!(self.text.buf, " ({name} {:.0})", ch.to_display_value(val))?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn hwmon_name(index: usize) -> String {
+        format!("{} {index}", Self::HWMON_TITLE)
+    }
+
+    pub fn cb_hwmon(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.hwmon ^= true;
+
+            visible = opt.hwmon;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::hwmon_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
@@ -26,6 +26,29 @@ pub use activity::*;
 
 mod memory_error_count;
 
+mod hwmon;
+
+mod fan;
+
+mod dpm;
+
+mod throttle_log;
+
+mod ras;
+
+mod grbm_se;
+
+mod power_state;
+
+mod runtime_pm;
+
+mod partition;
+
+mod display;
+
+mod power_ctrl;
+pub(crate) use power_ctrl::*;
+
 #[derive(Clone, Default)]
 pub(crate) struct AppTextView {
     pub text: Text,
@@ -0,0 +1,93 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::PartitionInfo;
+use libamdgpu_top::stat::PartitionStat;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const PARTITION_TITLE: &str = "Compute Partition";
+
+    pub fn print_partition(
+        &mut self,
+        info: &PartitionInfo,
+        stat: &PartitionStat,
+    ) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        write!(self.text.buf, " Compute: {}", info.compute_mode)?;
+
+        if let Some(memory_mode) = info.memory_mode {
+            write!(self.text.buf, ", Memory: {memory_mode}")?;
+        }
+
+        writeln!(self.text.buf)?;
+
+        for (p, usage) in info.partitions.iter().zip(stat.partitions.iter()) {
+            let xcc = match (p.xcc.first(), p.xcc.last()) {
+                (Some(first), Some(last)) if first != last => format!("XCC {first}-{last}"),
+                (Some(first), _) => format!("XCC {first}"),
+                _ => "XCC _".to_string(),
+            };
+
+            write!(
+                self.text.buf,
+                " [{}] {:<18} {xcc:<10}",
+                p.index,
+                p.render.display(),
+            )?;
+
+            match usage.avg_xcc_busy() {
+                Some(busy) => write!(self.text.buf, " Busy {busy:3}%")?,
+                None => write!(self.text.buf, " Busy ___%")?,
+            }
+
+            writeln!(
+                self.text.buf,
+                ", GFX {:3}%, Compute {:3}%, VRAM {:6} MiB, {} procs",
+                usage.gfx,
+                usage.compute,
+                usage.vram_usage >> 10,
+                usage.processes.len(),
+            )?;
+
+            if !usage.processes.is_empty() {
+                let procs: Vec<String> = usage.processes
+                    .iter()
+                    .map(|(pid, name)| format!("{name}({pid})"))
+                    .collect();
+
+                writeln!(self.text.buf, "     {}", procs.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn partition_name(index: usize) -> String {
+        format!("{} {index}", Self::PARTITION_TITLE)
+    }
+
+    pub fn cb_partition(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.partition ^= true;
+
+            visible = opt.partition;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::partition_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
@@ -36,7 +36,7 @@ impl PerfCounterView {
     pub fn resized_panel(&self, pc: &PerfCounter) -> ResizedPanel {
         const LEFT_LEN: usize = PANEL_WIDTH - PC_BAR_WIDTH;
 
-        let title = pc.pc_type.to_string();
+        let title = pc.name.clone();
         let mut sub_layout = LinearLayout::vertical();
         let label = |value: usize, (_, _): (usize, usize)| -> String {
             format!("[{val:^width$}]", width = PC_BAR_WIDTH - 2, val = format!("{value:3} %"))
@@ -66,7 +66,7 @@ impl PerfCounterView {
             SizeConstraint::Free,
             SizeConstraint::Free,
             panel,
-        ).with_name(pc_view_name(pc.pc_type, self.index))
+        ).with_name(pc_view_name(&pc.name, self.index))
     }
 
     pub fn set_value(&self, pc: &PerfCounter) {
@@ -76,8 +76,8 @@ impl PerfCounterView {
     }
 }
 
-pub fn pc_view_name(pc_type: PCType, index: usize) -> String {
-    format!("{pc_type} {index}")
+pub fn pc_view_name(name: &str, index: usize) -> String {
+    format!("{name} {index}")
 }
 
 pub fn pc_type_cb(pc_type: PCType) -> impl Fn(&mut cursive::Cursive) {
@@ -107,7 +107,7 @@ pub fn pc_type_cb(pc_type: PCType) -> impl Fn(&mut cursive::Cursive) {
         };
 
         for i in &indexes {
-            let name = pc_view_name(pc_type, *i);
+            let name = pc_view_name(&pc_type.to_string(), *i);
             if visible {
                 siv.call_on_name(&name, set_visible_height::<LinearLayout>);
             } else {
@@ -116,3 +116,26 @@ pub fn pc_type_cb(pc_type: PCType) -> impl Fn(&mut cursive::Cursive) {
         }
     }
 }
+
+pub fn extra_pc_cb(siv: &mut cursive::Cursive) {
+    use crate::{set_min_height, set_visible_height, Opt};
+    use cursive::views::LinearLayout;
+
+    let visible;
+    let names = {
+        let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+        opt.extra_pc ^= true;
+
+        visible = opt.extra_pc;
+
+        opt.extra_pc_names.clone()
+    };
+
+    for name in &names {
+        if visible {
+            siv.call_on_name(name, set_visible_height::<LinearLayout>);
+        } else {
+            siv.call_on_name(name, set_min_height::<LinearLayout>);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::{PerfLevel, PowerCapChange, PowerProfileTable, stat::Sensors};
+
+use crate::AppTextView;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PowerCtrlRequest {
+    NextPerfLevel,
+    NextPowerProfile,
+    IncreasePowerCap,
+    DecreasePowerCap,
+    ResetPowerCap,
+}
+
+impl PowerCtrlRequest {
+    const POWER_CAP_STEP: u32 = 5; // W
+
+    pub fn apply(&self, sensors: &Sensors) -> String {
+        let sysfs_path = &sensors.sysfs_path;
+        let r = match self {
+            Self::NextPerfLevel => {
+                let Some(cur) = sensors.perf_level else {
+                    return "power_dpm_force_performance_level is not supported".to_string();
+                };
+                let next = cur.next();
+
+                next.set(sysfs_path).map(|_| format!("perf_level: {cur} -> {next}"))
+            },
+            Self::NextPowerProfile => {
+                let Some(table) = &sensors.power_profile_table else {
+                    return "pp_power_profile_mode is not supported".to_string();
+                };
+                let Some(next) = table.next_index() else { return String::new() };
+                // the profile indexes have gaps on some ASICs (e.g. Renoir)
+                let name = table.modes.iter().find(|m| m.index == next).map_or("", |m| m.name.as_str());
+
+                PowerProfileTable::set(sysfs_path, next)
+                    .map(|_| format!("power_profile: {name}"))
+            },
+            Self::IncreasePowerCap | Self::DecreasePowerCap | Self::ResetPowerCap => {
+                let Some(cap) = &sensors.power_cap else {
+                    return "power1_cap is not supported".to_string();
+                };
+                let change = match self {
+                    Self::IncreasePowerCap =>
+                        PowerCapChange::new(cap, cap.current.saturating_add(Self::POWER_CAP_STEP)),
+                    Self::DecreasePowerCap =>
+                        PowerCapChange::new(cap, cap.current.saturating_sub(Self::POWER_CAP_STEP)),
+                    _ => PowerCapChange::reset_to_default(cap),
+                };
+
+                change.apply(&sensors.hwmon_path).map(|_| change.to_string())
+            },
+        };
+
+        r.unwrap_or_else(|e| e.to_string())
+    }
+}
+
+impl AppTextView {
+    pub const POWER_CTRL_TITLE: &str = "Power Control";
+
+    pub fn print_power_ctrl(&mut self, sensors: &Sensors, allow_power_ctrl: bool, msg: &str) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        if let Some(perf_level) = sensors.perf_level {
+            writeln!(self.text.buf, " Performance Level => {perf_level}")?;
+        }
+
+        if let Some(cap) = &sensors.power_cap {
+            writeln!(
+                self.text.buf,
+                " Power Cap. => {} W ({}-{} W, Default {} W)",
+                cap.current,
+                cap.min,
+                cap.max,
+                cap.default,
+            )?;
+        }
+
+        if let Some(table) = &sensors.power_profile_table {
+            for mode in &table.modes {
+                let mark = if mode.is_active { "*" } else { " " };
+
+                write!(self.text.buf, " {mark}{}:{}", mode.index, mode.name)?;
+            }
+
+            writeln!(self.text.buf, "\n")?;
+            writeln!(self.text.buf, "{}", table.table)?;
+        }
+
+        let writable = PerfLevel::is_writable(&sensors.sysfs_path)
+            || PowerProfileTable::is_writable(&sensors.sysfs_path)
+            || PowerCapChange::is_writable(&sensors.hwmon_path);
+
+        if !allow_power_ctrl {
+            writeln!(self.text.buf, "\n launch with `--allow-power-ctrl` to change the settings")?;
+        } else if writable {
+            writeln!(self.text.buf, "\n (L): next perf_level, (p): next power_profile")?;
+            writeln!(self.text.buf, " (+/-): power_cap +/-{} W, (=): reset power_cap", PowerCtrlRequest::POWER_CAP_STEP)?;
+        } else {
+            writeln!(self.text.buf, "\n root privileges are required to change the settings")?;
+        }
+
+        if !msg.is_empty() {
+            writeln!(self.text.buf, " {msg}")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn power_ctrl_name(index: usize) -> String {
+        format!("{} {index}", Self::POWER_CTRL_TITLE)
+    }
+
+    pub fn cb_power_ctrl(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.power_ctrl ^= true;
+
+            visible = opt.power_ctrl;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::power_ctrl_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
+
+pub(crate) fn power_req_cb(req: PowerCtrlRequest) -> impl Fn(&mut cursive::Cursive) {
+    use crate::Opt;
+
+    move |siv: &mut cursive::Cursive| {
+        let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+        opt.power_req = Some(req);
+    }
+}
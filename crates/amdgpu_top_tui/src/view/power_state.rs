@@ -0,0 +1,62 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::PowerStateMachine;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const POWER_STATE_TITLE: &str = "Power State";
+
+    pub fn print_power_state(&mut self, sm: &PowerStateMachine) -> Result<(), fmt::Error> {
+        const MAX_LOG_LINES: usize = 4;
+        self.text.clear();
+
+        writeln!(
+            self.text.buf,
+            " {} ({}) for {:.1}s",
+            sm.state,
+            sm.state.description(),
+            sm.duration().as_secs_f64(),
+        )?;
+
+        for t in sm.transitions.iter().rev().take(MAX_LOG_LINES) {
+            writeln!(
+                self.text.buf,
+                " {:>7.1}s: {:>13} -> {:<13} ({})",
+                t.time.as_secs_f64(),
+                t.from.to_string(),
+                t.to.to_string(),
+                t.reason,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn power_state_name(index: usize) -> String {
+        format!("{} {index}", Self::POWER_STATE_TITLE)
+    }
+
+    pub fn cb_power_state(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.power_state ^= true;
+
+            visible = opt.power_state;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::power_state_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
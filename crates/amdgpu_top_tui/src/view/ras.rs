@@ -0,0 +1,79 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::RasInfo;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const RAS_TITLE: &str = "RAS";
+
+    pub fn print_ras_info(&mut self, ras: &RasInfo) -> Result<(), fmt::Error> {
+        const MAX_BAD_PAGES: usize = 4;
+        self.text.clear();
+
+        if let Some(features) = ras.features {
+            write!(self.text.buf, " Features: {features:#x}")?;
+        }
+
+        if let Some(ver) = ras.eeprom.version {
+            write!(self.text.buf, ", EEPROM table version: {ver:#x}")?;
+        }
+
+        writeln!(self.text.buf)?;
+        writeln!(self.text.buf, " {:<10} {:>8} {:>8} {:>8} {:>8} {:>8}", "Block", "UE", "(+)", "CE", "(+)", "DE")?;
+
+        for b in &ras.blocks {
+            writeln!(
+                self.text.buf,
+                " {:<10} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                b.block.to_uppercase(),
+                b.ue,
+                b.ue_delta(),
+                b.ce,
+                b.ce_delta(),
+                b.de.map_or("-".to_string(), |de| de.to_string()),
+            )?;
+        }
+
+        if !ras.bad_pages.is_empty() {
+            writeln!(self.text.buf, " Retired Pages: {}", ras.bad_pages.len())?;
+
+            for p in ras.bad_pages.iter().take(MAX_BAD_PAGES) {
+                writeln!(self.text.buf, "   {:#014x} ({:#x} bytes): {}", p.address(), p.size, p.status)?;
+            }
+
+            if ras.bad_pages.len() > MAX_BAD_PAGES {
+                writeln!(self.text.buf, "   ...")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn ras_name(index: usize) -> String {
+        format!("{} {index}", Self::RAS_TITLE)
+    }
+
+    pub fn cb_ras(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.ras ^= true;
+
+            visible = opt.ras;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::ras_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
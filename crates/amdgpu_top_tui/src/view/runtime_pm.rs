@@ -0,0 +1,81 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::{RuntimePm, RuntimePmControl};
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const RUNTIME_PM_TITLE: &str = "Runtime PM";
+
+    pub fn print_runtime_pm(
+        &mut self,
+        runtime_pm: &RuntimePm,
+        pci_power_state: Option<&str>,
+    ) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        write!(self.text.buf, " Status: {:<10}", runtime_pm.status.to_string())?;
+
+        if let Some(control) = runtime_pm.control {
+            write!(self.text.buf, " Control: {control:<4}")?;
+        }
+
+        if let Some(delay) = runtime_pm.autosuspend_delay_ms {
+            write!(self.text.buf, " Autosuspend Delay: {delay} ms")?;
+        }
+
+        writeln!(self.text.buf)?;
+
+        if let Some(power_state) = pci_power_state {
+            writeln!(self.text.buf, " PCI Power State: {power_state}")?;
+        }
+
+        writeln!(
+            self.text.buf,
+            " Active   : {:>9.1}s ({:5.1}%), {:>10.1}s since boot",
+            runtime_pm.session_active_time().as_secs_f64(),
+            runtime_pm.active_percent(),
+            runtime_pm.active_time.as_secs_f64(),
+        )?;
+        writeln!(
+            self.text.buf,
+            " Suspended: {:>9.1}s ({:5.1}%), {:>10.1}s since boot",
+            runtime_pm.session_suspended_time().as_secs_f64(),
+            runtime_pm.suspended_percent(),
+            runtime_pm.suspended_time.as_secs_f64(),
+        )?;
+
+        if runtime_pm.control == Some(RuntimePmControl::On) {
+            writeln!(self.text.buf, " (runtime PM is disabled by `power/control`)")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn runtime_pm_name(index: usize) -> String {
+        format!("{} {index}", Self::RUNTIME_PM_TITLE)
+    }
+
+    pub fn cb_runtime_pm(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.runtime_pm ^= true;
+
+            visible = opt.runtime_pm;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::runtime_pm_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
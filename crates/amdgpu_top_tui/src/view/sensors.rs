@@ -1,7 +1,8 @@
 use super::PANEL_WIDTH;
 use std::fmt::{self, Write};
 
-use libamdgpu_top::stat::{Sensors, PcieBw};
+use libamdgpu_top::LinkHealth;
+use libamdgpu_top::stat::{GfxoffResidency, Sensors, PcieStat};
 
 const WIDTH: usize = PANEL_WIDTH / 2;
 
@@ -35,6 +36,26 @@ impl AppTextView {
         }
         if (c % 2) == 1 { writeln!(self.text.buf)?; }
 
+        let voltages: Vec<_> = [&sensors.voltage_in0, &sensors.voltage_in1]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        if !voltages.is_empty() || !sensors.currents.is_empty() {
+            write!(self.text.buf, " hwmon      =>")?;
+
+            for v in voltages {
+                write!(self.text.buf, " {}: {:4} mV,", v.name(), v.value.0)?;
+            }
+
+            for c in &sensors.currents {
+                write!(self.text.buf, " {}: {:6.3} A,", c.name(), c.value.as_amperes_f64())?;
+            }
+
+            let _ = self.text.buf.pop();
+            writeln!(self.text.buf)?;
+        }
+
         if sensors.average_power.is_some() || sensors.input_power.is_some() {
             write!(self.text.buf, " GPU Power  =>")?;
 
@@ -42,8 +63,8 @@ impl AppTextView {
                 let Some(power) = power else { continue };
                 write!(
                     self.text.buf,
-                    " {:3} W ({})",
-                    power.value,
+                    " {:6.2} W ({})",
+                    power.value.as_watts_f64(),
                     power.type_,
                 )?;
             }
@@ -57,21 +78,31 @@ impl AppTextView {
             writeln!(self.text.buf)?;
         }
 
+        if let Some(power2) = &sensors.power2 {
+            writeln!(
+                self.text.buf,
+                " {:<10} => {:6.2} W ({})",
+                power2.label.as_deref().unwrap_or("Power2"),
+                power2.value.as_watts_f64(),
+                power2.type_,
+            )?;
+        }
+
         for temp in [&sensors.edge_temp, &sensors.junction_temp, &sensors.memory_temp] {
             let Some(temp) = temp else { continue };
             let label = format!("{} Temp.", temp.type_);
-            write!(self.text.buf, " {label:<15} => {:3} C", temp.current)?;
+            write!(self.text.buf, " {label:<15} => {:5.1} C", temp.current.as_celsius_f64())?;
             if let Some(crit) = temp.critical {
-                write!(self.text.buf, " (Crit. {crit} C)")?;
+                write!(self.text.buf, " (Crit. {} C)", crit.as_celsius())?;
             }
             if let Some(e) = temp.emergency {
-                write!(self.text.buf, " (Emergency {e} C)")?;
+                write!(self.text.buf, " (Emergency {} C)", e.as_celsius())?;
             }
             writeln!(self.text.buf)?;
         }
 
         if let Some(tctl) = sensors.tctl {
-            write!(self.text.buf, " CPU Tctl   => {:3} C", tctl / 1000)?;
+            write!(self.text.buf, " CPU Tctl   => {:5.1} C", tctl.as_celsius_f64())?;
             writeln!(self.text.buf)?;
         }
 
@@ -84,13 +115,33 @@ impl AppTextView {
 
                 write!(self.text.buf, " (Max. {max_rpm} RPM)")?;
             }
+            if let Some(target_rpm) = sensors.fan.as_ref().and_then(|f| f.target_rpm) {
+                write!(self.text.buf, " (Target {target_rpm} RPM)")?;
+            }
+            if let Some(pwm) = sensors.fan.as_ref().and_then(|f| f.pwm_percent()) {
+                write!(self.text.buf, " (PWM {pwm:>3}%)")?;
+            }
             writeln!(self.text.buf)?;
         }
 
-        if let Some(cur) = sensors.current_link {
+        if let Some(power_state) = &sensors.pci_power_state {
+            writeln!(self.text.buf, " PCI Power State: {power_state}")?;
+        }
+
+        sensors.print_all_cpu_core_cur_freq(
+            &mut self.text.buf,
+            " CPU Core freq (MHz)",
+            false,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn print_pcie(&mut self, pcie: &PcieStat) -> Result<(), fmt::Error> {
+        if let Some(cur) = pcie.current_link {
             write!(self.text.buf, " PCIe Link Speed => Gen{}x{:<2}", cur.r#gen, cur.width)?;
 
-            if let [Some(min), Some(max)] = [sensors.min_dpm_link, sensors.max_dpm_link] {
+            if let [Some(min), Some(max)] = [pcie.min_link, pcie.max_link] {
                 write!(
                     self.text.buf,
                     " (Gen{}x{} - Gen{}x{})",
@@ -99,35 +150,81 @@ impl AppTextView {
                     max.r#gen,
                     max.width,
                 )?;
-            } else if let Some(max) = sensors.max_dpm_link {
+            } else if let Some(max) = pcie.max_link {
                 write!(self.text.buf, " (Max. Gen{}x{})", max.r#gen, max.width)?;
             }
 
             writeln!(self.text.buf)?;
         }
 
-        if let Some(power_state) = &sensors.pci_power_state {
-            writeln!(self.text.buf, " PCI Power State: {power_state}")?;
+        if let [Some(sent), Some(rec)] = [pcie.sent, pcie.received] {
+            writeln!(
+                self.text.buf,
+                " PCIe Bandwidth Usage => Sent: {sent:6} MiB/s, Received: {rec:6} MiB/s",
+            )?;
+        } else if let (Some(bw), Some(src)) = (pcie.bandwidth, pcie.bw_source) {
+            writeln!(self.text.buf, " PCIe Bandwidth Usage => {bw:6} MiB/s ({src})")?;
         }
 
-        sensors.print_all_cpu_core_cur_freq(
-            &mut self.text.buf,
-            " CPU Core freq (MHz)",
-            false,
-        )?;
+        if !pcie.counters.is_empty() {
+            write!(self.text.buf, " PCIe Counters =>")?;
+
+            for (i, c) in pcie.counters.iter().enumerate() {
+                let sep = if i == 0 { "" } else { "," };
+                write!(self.text.buf, "{sep} {}: +{} ({})", c.label, c.delta, c.total_delta)?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
 
         Ok(())
     }
 
-    pub fn print_pcie_bw(&mut self, pcie_bw: &PcieBw) -> Result<(), fmt::Error> {
-        let Some(mps) = pcie_bw.max_payload_size else { return Ok(()) };
-        let Some(sent) = pcie_bw.sent.map(|v| (v * mps as u64) >> 20) else { return Ok(()) };
-        let Some(rec) = pcie_bw.received.map(|v| (v * mps as u64) >> 20) else { return Ok(()) };
+    pub fn print_link_health(&mut self, link_health: &LinkHealth) -> Result<(), fmt::Error> {
+        if !link_health.has_warning() {
+            return writeln!(self.text.buf, " PCIe Link Health => OK");
+        }
 
-        writeln!(
-            self.text.buf,
-            " PCIe Bandwidth Usage => Sent: {sent:6} MiB/s, Received: {rec:6} MiB/s",
-        )?;
+        writeln!(self.text.buf, " [!] PCIe Link Health =>")?;
+
+        for issue in &link_health.issues {
+            writeln!(self.text.buf, "     {issue}")?;
+        }
+
+        write!(self.text.buf, "     Path:")?;
+
+        for (i, hop) in link_health.hops.iter().enumerate() {
+            let sep = if i == 0 { "" } else { " ->" };
+            write!(self.text.buf, "{sep} {}", hop.bdf)?;
+
+            if let Some(cur) = hop.current_link {
+                write!(self.text.buf, " (Gen{}x{})", cur.r#gen, cur.width)?;
+            }
+        }
+
+        writeln!(self.text.buf)
+    }
+
+    pub fn print_gfxoff_residency(&mut self, gfxoff: &GfxoffResidency) -> Result<(), fmt::Error> {
+        if let Some(note) = &gfxoff.note {
+            return writeln!(self.text.buf, " GFXOFF Residency: N/A ({note})");
+        }
+
+        write!(self.text.buf, " GFXOFF Residency =>")?;
+
+        if let Some(r) = gfxoff.residency {
+            write!(self.text.buf, " {r:5.1}%")?;
+        }
+
+        if let Some(r) = gfxoff.session_residency {
+            write!(self.text.buf, " (Session: {r:5.1}%)")?;
+        }
+
+        if let [Some(count), Some(session_count)] = [gfxoff.count, gfxoff.session_count] {
+            write!(self.text.buf, ", Entries: {session_count} ({count} since boot)")?;
+        }
+
+        writeln!(self.text.buf)?;
 
         Ok(())
     }
@@ -0,0 +1,74 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::{ThrottleLog, ThrottleReason};
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub const THROTTLE_LOG_TITLE: &str = "Throttle Log";
+
+    pub fn print_throttle_log(&mut self, log: &ThrottleLog) -> Result<(), fmt::Error> {
+        const MAX_LOG_LINES: usize = 8;
+        self.text.clear();
+
+        write!(self.text.buf, " Residency:")?;
+
+        for reason in ThrottleReason::ALL {
+            write!(self.text.buf, " {reason} {:4.1}%,", log.residency_percent(reason))?;
+        }
+
+        writeln!(self.text.buf)?;
+
+        // APU: `throttle_residency_*` of gpu_metrics v3.0
+        if !log.apu_residency.is_empty() {
+            write!(self.text.buf, " Counter (delta):")?;
+
+            for c in &log.apu_residency {
+                write!(self.text.buf, " {} +{},", c.label, c.delta)?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        for e in log.events.iter().rev().take(MAX_LOG_LINES) {
+            let end = e.end.map_or("...".to_string(), |end| format!("{:.1}s", end.as_secs_f64()));
+
+            writeln!(
+                self.text.buf,
+                " {:>7.1}s - {end:>8} ({:>5.1}s): {}",
+                e.start.as_secs_f64(),
+                e.duration(log.elapsed()).as_secs_f64(),
+                e.throttlers.join(", "),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn throttle_log_name(index: usize) -> String {
+        format!("{} {index}", Self::THROTTLE_LOG_TITLE)
+    }
+
+    pub fn cb_throttle_log(siv: &mut cursive::Cursive) {
+        use crate::{set_min_height, set_visible_height, Opt};
+        use cursive::views::TextView;
+
+        let visible;
+        let indexes = {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.throttle_log ^= true;
+
+            visible = opt.throttle_log;
+
+            opt.indexes.clone()
+        };
+
+        for i in &indexes {
+            let name = Self::throttle_log_name(*i);
+            if visible {
+                siv.call_on_name(&name, set_visible_height::<TextView>);
+            } else {
+                siv.call_on_name(&name, set_min_height::<TextView>);
+            }
+        }
+    }
+}
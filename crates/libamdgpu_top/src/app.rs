@@ -1,10 +1,15 @@
 use crate::drmVersion;
-use crate::AMDGPU::{DeviceHandle, GPU_INFO, GpuMetrics, MetricsInfo, RasBlock, RasErrorCount};
-use crate::{AppDeviceInfo, DevicePath, stat, xdna, VramUsage};
-use stat::{FdInfoStat, GpuActivity, Sensors, PcieBw, PerfCounter, ProcInfo};
+use crate::AMDGPU::{DeviceHandle, GPU_INFO, GpuMetrics, MetricsInfo, RasErrorCount};
+use crate::{AppDeviceInfo, DevicePath, DisplayMonitor, DisplayStat, KfdTopology, LinkHealth, LINK_CHECK_LOAD, RasInfo, stat, xdna, VramUsage};
+use crate::{SampleSource, Sampling, SamplingConfig, Scheduler};
+use stat::{FdInfoStat, GpuActivity, GrbmSe, MicroWatt, Sensors, PcieBw, PCRegTable, PCType, PerfCounter, ProcInfo, ThrottleLog};
+use stat::{GfxoffResidency, GfxoffResidencyLogger, PartitionStat, PcieStat, PowerState, PowerStateInput, PowerStateMachine, RuntimePm, XgmiStat};
 use xdna::{amdxdna_drm_get_resource_info, XdnaFdInfoStat};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::os::fd::AsRawFd;
 
 pub struct AppAmdgpuTop {
@@ -15,16 +20,21 @@ pub struct AppAmdgpuTop {
     pub xdna_fw_version: Option<String>,
     pub xdna_resouce_info: Option<amdxdna_drm_get_resource_info>,
     pub stat: AppAmdgpuTopStat,
-    buf_interval: Duration,
-    no_drop_device_handle: bool,
-    dynamic_no_pc: bool, // to transition APU into GFXOFF state or dGPU into D3Hot state
-    to_d3hot: bool,
+    scheduler: Scheduler,
+    gfxoff_logger: Option<GfxoffResidencyLogger>,
+    display_monitor: Option<DisplayMonitor>,
+    /// gpu_metrics does not report the media engine usage
+    media_from_fdinfo: bool,
+    power_state_log: Option<File>,
 }
 
 #[derive(Clone)]
 pub struct AppAmdgpuTopStat {
     pub grbm: PerfCounter,
     pub grbm2: PerfCounter,
+    /// other status registers, e.g. SRBM_STATUS, CP_STAT
+    pub extra_pc: Vec<PerfCounter>,
+    pub grbm_se: Option<GrbmSe>,
     pub vram_usage: VramUsage,
     pub sensors: Option<Sensors>,
     pub metrics: Option<GpuMetrics>,
@@ -35,13 +45,33 @@ pub struct AppAmdgpuTopStat {
     pub arc_xdna_proc_index: Arc<Mutex<Vec<ProcInfo>>>,
     pub arc_pcie_bw: Option<Arc<Mutex<PcieBw>>>,
     pub memory_error_count: Option<RasErrorCount>,
+    pub ras: Option<RasInfo>,
+    pub throttle_log: ThrottleLog,
+    pub power_state: PowerStateMachine,
+    pub runtime_pm: Option<RuntimePm>,
+    pub gfxoff: GfxoffResidency,
+    pub pcie: PcieStat,
+    pub link_health: Option<LinkHealth>,
+    pub xgmi: XgmiStat,
+    pub partition: Option<PartitionStat>,
+    pub display: Option<DisplayStat>,
 }
 
 impl AppAmdgpuTopStat {
 }
 
+#[derive(Clone)]
 pub struct AppOption {
     pub pcie_bw: bool,
+    pub sampling: SamplingConfig,
+    /// skip probing the extra perf counter registers
+    pub no_pc: bool,
+    /// user-supplied perf counter tables, tables with the same name replace the built-in ones
+    pub pc_tables: Vec<PCRegTable>,
+    /// append the power state transitions to the file
+    pub power_state_log: Option<PathBuf>,
+    /// start the GFXOFF residency logging of SMU (debugfs), not for dump modes
+    pub gfxoff_residency: bool,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -49,6 +79,11 @@ impl Default for AppOption {
     fn default() -> Self {
         Self {
             pcie_bw: false,
+            sampling: SamplingConfig::default(),
+            no_pc: false,
+            pc_tables: Vec::new(),
+            power_state_log: None,
+            gfxoff_residency: false,
         }
     }
 }
@@ -109,39 +144,66 @@ impl AppAmdgpuTop {
         let ext_info = amdgpu_dev.device_info().ok()?;
         let asic_name = ext_info.get_asic_name();
         let memory_info = amdgpu_dev.memory_info().ok()?;
-        let no_drop_device_handle = if let Ok(s) = std::env::var("AGT_NO_DROP") {
-            s == "1"
-        } else {
-            false
+        let sampling = opt.sampling.clone();
+
+        let pc_tables = {
+            let mut tables = PCRegTable::builtin(ext_info.get_chip_class());
+
+            // user-supplied tables, for new ASICs
+            PCRegTable::merge(&mut tables, opt.pc_tables.clone());
+
+            tables
         };
 
-        let [grbm, grbm2] = {
-            let chip_class = ext_info.get_chip_class();
+        let [grbm, grbm2] = [PCType::GRBM, PCType::GRBM2].map(|pc_type| {
+            let name = pc_type.to_string();
+            let table = pc_tables.iter().find(|t| t.name == name).unwrap();
 
-            [
-                PerfCounter::new_with_chip_class(stat::PCType::GRBM, chip_class),
-                PerfCounter::new_with_chip_class(stat::PCType::GRBM2, chip_class),
-            ]
+            PerfCounter::from_table(table)
+        });
+        let (extra_pc, grbm_se) = if opt.no_pc {
+            (Vec::new(), None)
+        } else {
+            let extra_pc: Vec<PerfCounter> = pc_tables
+                .iter()
+                .filter(|t| t.name != grbm.name && t.name != grbm2.name && !GrbmSe::is_se_table(t))
+                .map(PerfCounter::from_table)
+                .filter(|pc| pc.check_reg(&amdgpu_dev))
+                .collect();
+            let grbm_se = GrbmSe::from_tables(&pc_tables, ext_info.max_se() as usize, &amdgpu_dev);
+
+            (extra_pc, grbm_se)
         };
 
         let vram_usage = VramUsage::new(&memory_info);
-        let memory_error_count = RasErrorCount::get_from_sysfs_with_ras_block(&sysfs_path, RasBlock::UMC).ok();
+        let ras = RasInfo::new(&sysfs_path);
+        let memory_error_count = ras.as_ref().and_then(|ras| ras.umc_error_count());
+        let runtime_pm = RuntimePm::new(&sysfs_path);
+        let gfxoff_logger = opt.gfxoff_residency.then(|| GfxoffResidencyLogger::new(pci_bus));
 
         let sensors = Sensors::new(&amdgpu_dev, &pci_bus, &ext_info);
         let metrics = GpuMetrics::get_from_sysfs_path(&device_path.sysfs_path).ok();
         let activity = GpuActivity::get(&sysfs_path, asic_name);
+        let media_from_fdinfo = activity.media.is_none() || activity.media == Some(0);
+        let power_state_log = opt.power_state_log.as_ref().and_then(|path| {
+            OpenOptions::new().create(true).append(true).open(path).ok()
+        });
 
         let arc_pcie_bw = if opt.pcie_bw {
             let pcie_bw = PcieBw::new(&sysfs_path);
 
             if pcie_bw.check_pcie_bw_support(&ext_info) {
-                Some(pcie_bw.spawn_update_thread())
+                Some(pcie_bw.spawn_update_thread(sampling.get(SampleSource::PcieBw).interval))
             } else {
                 None
             }
         } else {
             None
         };
+        let mut pcie = PcieStat::new(arc_pcie_bw.is_some());
+        pcie.update(None, metrics.as_ref(), sensors.as_ref());
+        let link_health = if ext_info.is_apu() { None } else { LinkHealth::new(&sysfs_path) };
+        let display_monitor = DisplayMonitor::new(&device_path);
 
         let mut device_info = AppDeviceInfo::new(
             &amdgpu_dev,
@@ -156,10 +218,16 @@ impl AppAmdgpuTop {
                 device_path.get_gfx_target_version_from_kfd().map(|v| v.to_string());
         }
 
+        let partition = device_info.partition
+            .as_ref()
+            .filter(|info| !info.partitions.is_empty())
+            .map(|info| PartitionStat::new(info, &device_path.card));
+
         let fdinfo = FdInfoStat {
             has_vcn: device_info.has_vcn(),
             has_vcn_unified: device_info.has_vcn_unified(),
             has_vpe: device_info.has_vpe(),
+            kfd_gpu_ids: KfdTopology::new().gpu_ids(&pci_bus.to_string()),
             ..Default::default()
         };
         let xdna_fdinfo = XdnaFdInfoStat::default();
@@ -191,7 +259,7 @@ impl AppAmdgpuTop {
 
             stat::update_index_by_all_proc(
                 &mut proc_index,
-                &[&device_path.render, &device_path.card],
+                &device_path.drm_paths(),
                 &all_procs,
             );
 
@@ -216,6 +284,8 @@ impl AppAmdgpuTop {
             stat: AppAmdgpuTopStat {
                 grbm,
                 grbm2,
+                extra_pc,
+                grbm_se,
                 vram_usage,
                 sensors,
                 metrics,
@@ -226,11 +296,22 @@ impl AppAmdgpuTop {
                 arc_xdna_proc_index,
                 arc_pcie_bw,
                 memory_error_count,
+                ras,
+                throttle_log: ThrottleLog::default(),
+                power_state: PowerStateMachine::default(),
+                runtime_pm,
+                gfxoff: gfxoff_logger.as_ref().map(|l| l.stat.clone()).unwrap_or_default(),
+                pcie,
+                link_health,
+                xgmi: XgmiStat::default(),
+                partition,
+                display: display_monitor.as_ref().map(|m| m.stat.clone()),
             },
-            buf_interval: Duration::ZERO,
-            no_drop_device_handle,
-            dynamic_no_pc: false,
-            to_d3hot: false,
+            scheduler: Scheduler::new(sampling),
+            gfxoff_logger,
+            display_monitor,
+            media_from_fdinfo,
+            power_state_log,
         })
     }
 
@@ -246,12 +327,12 @@ impl AppAmdgpuTop {
 
         let input_power = self.stat.sensors.as_mut()?.input_power.as_mut()?;
 
-        if input_power.value != 0 {
+        if input_power.value.0 != 0 {
             return None;
         }
 
         let avg_socket_power = self.stat.metrics.as_ref()?.get_average_socket_power()?;
-        input_power.value = avg_socket_power;
+        input_power.value = MicroWatt::from_watts(avg_socket_power);
 
         None
     }
@@ -282,76 +363,128 @@ impl AppAmdgpuTop {
         None
     }
 
+    pub fn sampling_config(&self) -> &SamplingConfig {
+        self.scheduler.config()
+    }
+
+    pub fn set_sampling_config(&mut self, config: SamplingConfig) {
+        self.scheduler.set_config(config);
+    }
+
+    /// The number of the perf counter samples and the delay between them,
+    /// `update` should be called after `update_pc` is called `count` times.
+    pub fn pc_sampling(&self) -> Sampling {
+        self.scheduler.config().pc_sampling()
+    }
+
+    fn is_due(&self, src: SampleSource) -> bool {
+        self.stat.power_state.state.allows(src) && self.scheduler.is_due(src)
+    }
+
     pub fn update(&mut self, interval: Duration) {
-        if interval != Duration::ZERO {
+        self.scheduler.advance(interval);
+
+        if self.scheduler.is_due(SampleSource::PerfCounter) {
+            self.update_pc_usage();
+            self.clear_pc();
+            self.scheduler.reset(SampleSource::PerfCounter);
+        }
+
+        let mut fdinfo_updated = false;
+
+        if self.scheduler.is_due(SampleSource::Fdinfo) {
             let fdinfo_lock = self.stat.arc_proc_index.try_lock();
             let xdna_fdinfo_lock = self.stat.arc_xdna_proc_index.try_lock();
 
+            // If the index is being updated, the elapsed time is carried over to the next update.
             if let [Ok(proc_index), Ok(xdna_proc_index)] = [fdinfo_lock, xdna_fdinfo_lock] {
-                let fdinfo_interval = interval + self.buf_interval;
+                let fdinfo_interval = self.scheduler.elapsed(SampleSource::Fdinfo);
                 self.stat.fdinfo.interval = fdinfo_interval;
                 self.stat.xdna_fdinfo.interval = fdinfo_interval;
 
                 self.stat.fdinfo.update_proc_usage(&proc_index);
                 self.stat.xdna_fdinfo.update_proc_usage(&xdna_proc_index);
 
-                self.buf_interval = Duration::ZERO;
-            } else {
-                self.buf_interval += interval;
+                if let Some(partition) = &mut self.stat.partition {
+                    partition.update_usage(&proc_index, &self.stat.fdinfo.proc_usage);
+                }
+
+                self.scheduler.reset(SampleSource::Fdinfo);
+                fdinfo_updated = true;
             }
         }
 
-        let (fold_proc_usage, _, _, _) = self.stat.fdinfo.fold_fdinfo_usage();
-
         {
             // let no_process_using_vram = self.stat.fdinfo.no_process_using_vram();
+            let input = PowerStateInput::new(
+                &self.stat.fdinfo,
+                &self.stat.activity,
+                self.device_info.is_apu,
+            );
+            let transition = self.stat.power_state.update(
+                &self.scheduler.config().policies,
+                &input,
+                || self.device_path.check_if_device_is_active(),
+            );
+            let to_d3hot = transition.is_some_and(|t| t.to == PowerState::D3hot);
 
-            // The AMDKFD driver does not track queues and does not show them in fdinfo.
-            let has_kfd_process = self.stat.fdinfo.has_kfd_process();
-            let is_fdinfo_idle = fold_proc_usage.gfx == 0
-                && fold_proc_usage.compute == 0
-                && fold_proc_usage.media == 0
-                && fold_proc_usage.vpe == 0
-                && fold_proc_usage.dma == 0;
-
-            // TODO: those checks may not be enough
-            if self.stat.activity.is_all_idling()
-                && is_fdinfo_idle
-                && !has_kfd_process
-                && !self.to_d3hot
-                && !self.no_drop_device_handle
-                && !self.device_info.is_apu
+            if let Some(t) = transition
+                && let Some(log) = &mut self.power_state_log
             {
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+                let _ = writeln!(log, "{:.3} {} {t}", timestamp.as_secs_f64(), self.device_path.pci);
+            }
+
+            if to_d3hot {
                 self.stat.vram_usage.update_usage(&self.amdgpu_dev);
                 self.stat.vram_usage.update_usable_heap_size(&self.amdgpu_dev);
-
-                self.to_d3hot = true;
-            } else if self.to_d3hot
-                && (!is_fdinfo_idle || has_kfd_process)
-                // && !no_process_using_vram
-                && self.device_path.check_if_device_is_active()
-            {
-                self.to_d3hot = false;
             }
+        }
 
-            // RDNA 4 GPUs report 2% GFX usage with PC sampling only.
-            self.dynamic_no_pc = is_fdinfo_idle && !has_kfd_process;
+        if let Some(ref mut runtime_pm) = self.stat.runtime_pm {
+            runtime_pm.update();
         }
 
-        if self.to_d3hot {
+        if self.stat.power_state.state == PowerState::D3hot {
             if let Some(ref mut sensors) = self.stat.sensors {
                 sensors.update_for_idle();
             }
 
             self.stat.metrics = None;
+            self.stat.throttle_log.update(None);
+            self.stat.pcie.update_for_idle(self.stat.sensors.as_ref());
             return;
         };
 
-        if self.device_info.supports_gpu_metrics {
-            self.stat.metrics = GpuMetrics::get_from_sysfs_path(&self.device_info.sysfs_path).ok();
+        let metrics_due = self.is_due(SampleSource::GpuMetrics);
+        let sensors_due = self.is_due(SampleSource::Sensors);
+
+        if metrics_due {
+            if self.device_info.supports_gpu_metrics {
+                self.stat.metrics = GpuMetrics::get_from_sysfs_path(&self.device_info.sysfs_path).ok();
+            }
+
+            self.stat.throttle_log.update(self.stat.metrics.as_ref());
+
+            if let Some(metrics) = &self.stat.metrics {
+                self.stat.xgmi.update(metrics);
+
+                if let Some(partition) = &mut self.stat.partition {
+                    partition.update_xcc_busy(metrics);
+                }
+            }
+
+            self.update_activity();
+            self.scheduler.reset(SampleSource::GpuMetrics);
         }
 
-        {
+        // media engine usage from fdinfo, for when gpu_metrics does not report it
+        if (metrics_due || fdinfo_updated) && self.media_from_fdinfo {
+            let (fold_proc_usage, _, _, _) = self.stat.fdinfo.fold_fdinfo_usage();
+            self.stat.activity.media = fold_proc_usage.media.try_into().ok();
+        }
+
+        if sensors_due {
             self.stat.vram_usage.update_usage(&self.amdgpu_dev);
             self.stat.vram_usage.update_usable_heap_size(&self.amdgpu_dev);
 
@@ -364,18 +497,56 @@ impl AppAmdgpuTop {
                     &self.device_info.ext_info,
                 );
             }
+
+            // reading debugfs wakes the GFX, the residency of the idle period is read after the GPU becomes active
+            if let Some(gfxoff_logger) = &mut self.gfxoff_logger
+                && self.stat.power_state.state == PowerState::Active
+            {
+                gfxoff_logger.update();
+                self.stat.gfxoff = gfxoff_logger.stat.clone();
+            }
+
+            if let Some(display_monitor) = &mut self.display_monitor {
+                display_monitor.update();
+                self.stat.display = Some(display_monitor.stat.clone());
+            }
+
+            self.scheduler.reset(SampleSource::Sensors);
         }
 
-        self.workaround_for_smu_v13_0_5();
-        self.workaround_fan_rpm_for_smu_v14();
+        if self.is_due(SampleSource::CpuFreq) {
+            if let Some(ref mut sensors) = self.stat.sensors {
+                sensors.update_all_cpu_core_cur_freq();
+            }
 
-        if self.stat.memory_error_count.is_some() {
-            self.stat.memory_error_count = RasErrorCount::get_from_sysfs_with_ras_block(
-                &self.device_info.sysfs_path,
-                RasBlock::UMC,
-            ).ok();
+            self.scheduler.reset(SampleSource::CpuFreq);
         }
 
+        if metrics_due || sensors_due {
+            self.workaround_for_smu_v13_0_5();
+            self.workaround_fan_rpm_for_smu_v14();
+
+            let pcie_bw = self.stat.arc_pcie_bw.as_ref().and_then(|arc| arc.try_lock().ok());
+            self.stat.pcie.update(pcie_bw.as_deref(), self.stat.metrics.as_ref(), self.stat.sensors.as_ref());
+        }
+
+        if self.is_due(SampleSource::Ras) {
+            if let Some(ras) = &mut self.stat.ras {
+                ras.update();
+                self.stat.memory_error_count = ras.umc_error_count();
+            }
+
+            if let Some(link_health) = &mut self.stat.link_health {
+                // the link may be downclocked while the GPU is idle
+                let is_under_load = self.stat.activity.gfx.is_some_and(|gfx| gfx >= LINK_CHECK_LOAD);
+                link_health.update(is_under_load);
+            }
+
+            self.scheduler.reset(SampleSource::Ras);
+        }
+    }
+
+    fn update_activity(&mut self) {
         self.stat.activity = GpuActivity::get_with_option_gpu_metrics(
             &self.device_info.sysfs_path,
             self.device_info.asic_name,
@@ -395,26 +566,49 @@ impl AppAmdgpuTop {
             self.stat.activity.media = self.stat.activity.media.map(|v| v.saturating_div(100));
         }
 
-        if self.stat.activity.media.is_none() || self.stat.activity.media == Some(0) {
-            self.stat.activity.media = fold_proc_usage.media.try_into().ok();
-        }
+        self.media_from_fdinfo = self.stat.activity.media.is_none() || self.stat.activity.media == Some(0);
     }
 
+    /// Read the perf counters, the usage is updated by `update` at the interval of `pc`.
     pub fn update_pc(&mut self) {
-        if self.dynamic_no_pc || self.to_d3hot { return }
+        if !self.stat.power_state.state.allows(SampleSource::PerfCounter) { return }
 
         self.stat.grbm.read_reg(&self.amdgpu_dev);
         self.stat.grbm2.read_reg(&self.amdgpu_dev);
+
+        for pc in self.stat.extra_pc.iter_mut() {
+            pc.read_reg(&self.amdgpu_dev);
+        }
+
+        if let Some(grbm_se) = &mut self.stat.grbm_se {
+            grbm_se.read_reg(&self.amdgpu_dev);
+        }
     }
 
     pub fn clear_pc(&mut self) {
         self.stat.grbm.clear_pc();
         self.stat.grbm2.clear_pc();
+
+        for pc in self.stat.extra_pc.iter_mut() {
+            pc.clear_pc();
+        }
+
+        if let Some(grbm_se) = &mut self.stat.grbm_se {
+            grbm_se.clear_pc();
+        }
     }
 
     pub fn update_pc_usage(&mut self) {
         self.stat.grbm.update_pc_usage();
         self.stat.grbm2.update_pc_usage();
+
+        for pc in self.stat.extra_pc.iter_mut() {
+            pc.update_pc_usage();
+        }
+
+        if let Some(grbm_se) = &mut self.stat.grbm_se {
+            grbm_se.update_pc_usage();
+        }
     }
 
     pub fn get_drm_version_struct(&mut self) -> Option<drmVersion> {
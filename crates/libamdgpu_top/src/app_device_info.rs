@@ -8,7 +8,6 @@ use crate::AMDGPU::{
     GPU_INFO,
     HW_IP::{HW_IP_TYPE, HwIpInfo},
     HwId,
-    HwmonTemp,
     IpDieEntry,
     PowerCap,
     PowerProfile,
@@ -17,7 +16,7 @@ use crate::AMDGPU::{
     VBIOS::VbiosInfo,
     VIDEO_CAPS::{CAP_TYPE, VideoCapsInfo},
 };
-use crate::{DevicePath, get_hw_ip_info_list, PCI, stat::Sensors};
+use crate::{DevicePath, get_hw_ip_info_list, KfdNode, KfdTopology, OdClkVoltage, PartitionInfo, PCI, XgmiInfo, stat::{HwmonTemperature, Sensors}};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -36,13 +35,14 @@ pub struct AppDeviceInfo {
     pub min_mem_clk: u32,
     pub max_mem_clk: u32,
     pub max_od_mem_clk: Option<u32>,
+    pub od_clk_voltage: Option<OdClkVoltage>,
     pub marketing_name: String,
     pub asic_name: ASIC_NAME,
     pub pci_bus: PCI::BUS_INFO,
     pub sysfs_path: PathBuf,
-    pub edge_temp: Option<HwmonTemp>,
-    pub junction_temp: Option<HwmonTemp>,
-    pub memory_temp: Option<HwmonTemp>,
+    pub edge_temp: Option<HwmonTemperature>,
+    pub junction_temp: Option<HwmonTemperature>,
+    pub memory_temp: Option<HwmonTemperature>,
     pub power_cap: Option<PowerCap>,
     pub fan_max_rpm: Option<u32>,
     pub decode: Option<VideoCapsInfo>,
@@ -64,6 +64,9 @@ pub struct AppDeviceInfo {
     pub fw_versions: Vec<FwVer>,
     pub memory_vendor: Option<String>,
     pub supports_gpu_metrics: bool,
+    pub xgmi: Option<XgmiInfo>,
+    pub partition: Option<PartitionInfo>,
+    pub kfd_node: Option<KfdNode>,
 }
 
 impl AppDeviceInfo {
@@ -89,29 +92,27 @@ impl AppDeviceInfo {
         let asic_name = ext_info.get_asic_name();
         let gfx_target_version = ext_info.get_gfx_target_version().map(|v| v.to_string());
 
+        let od_clk_voltage = OdClkVoltage::from_sysfs(&sysfs_path);
         let max_od_gpu_clk;
         let max_od_mem_clk;
 
         {
-            let pp_od_clk_voltage = std::fs::read_to_string(sysfs_path.join("pp_od_clk_voltage"));
+            let max_range = |od: &OdClkVoltage, name: &str| {
+                od.range(name).and_then(|r| u32::try_from(r.max).ok())
+            };
 
             if is_apu {
                 max_od_gpu_clk = None;
                 max_od_mem_clk = None;
-            } else if chip_class == CHIP_CLASS::GFX12 && let Ok(s) = pp_od_clk_voltage {
+            } else if chip_class == CHIP_CLASS::GFX12 && let Some(od) = &od_clk_voltage {
                 // AMDGPU drivers do not expose reachable the boost clock of GFX12 to userspace,
                 // so we infer the boost clock from the sclk offset.
                 // https://gitlab.freedesktop.org/drm/amd/-/issues/4453
-                max_od_gpu_clk = Self::parse_od_sclk_offset(&s)
-                    .and_then(|[_min, max]| u32::try_from(max).ok())
-                    .map(|v| v + max_gpu_clk);
-                max_od_mem_clk = Self::parse_od_mclk(&s)
-                    .and_then(|[_min, max]| u32::try_from(max).ok());
-            } else if chip_class >= CHIP_CLASS::GFX10 && let Ok(s) = pp_od_clk_voltage {
-                max_od_gpu_clk = Self::parse_od_sclk(&s)
-                    .and_then(|[_min, max]| u32::try_from(max).ok());
-                max_od_mem_clk = Self::parse_od_mclk(&s)
-                    .and_then(|[_min, max]| u32::try_from(max).ok());
+                max_od_gpu_clk = max_range(od, "SCLK_OFFSET").map(|v| v + max_gpu_clk);
+                max_od_mem_clk = max_range(od, "MCLK");
+            } else if chip_class >= CHIP_CLASS::GFX10 && let Some(od) = &od_clk_voltage {
+                max_od_gpu_clk = max_range(od, "SCLK");
+                max_od_mem_clk = max_range(od, "MCLK");
             } else {
                 max_od_gpu_clk = None;
                 max_od_mem_clk = None;
@@ -144,6 +145,9 @@ impl AppDeviceInfo {
                 s
             });
         let supports_gpu_metrics = sysfs_path.join("gpu_metrics").exists();
+        let xgmi = XgmiInfo::from_sysfs(&sysfs_path);
+        let partition = PartitionInfo::new(&sysfs_path, device_path.pci);
+        let kfd_node = KfdTopology::new().find_by_render(&device_path.render).cloned();
 
         Self {
             ext_info: *ext_info,
@@ -160,6 +164,7 @@ impl AppDeviceInfo {
             min_mem_clk,
             max_mem_clk,
             max_od_mem_clk,
+            od_clk_voltage,
             marketing_name,
             asic_name,
             pci_bus: device_path.pci,
@@ -188,42 +193,12 @@ impl AppDeviceInfo {
             fw_versions,
             memory_vendor,
             supports_gpu_metrics,
+            xgmi,
+            partition,
+            kfd_node,
         }
     }
 
-    fn parse_mhz(s: &str) -> Option<i32> {
-        let len = s.len();
-        s.get(..len-3)?.parse::<i32>().ok()
-    }
-
-    fn parse_range(s: &str, start_str: &str) -> Option<[i32; 2]> {
-        let mut lines = s.lines();
-        let s_range = lines.find(|l| l.starts_with(start_str))?;
-        let mut split = s_range
-            .trim_start_matches(start_str)
-            .split_whitespace();
-
-        if let [Some(min), Some(max)] = [split.next(), split.next()]
-            .map(|v| v.and_then(Self::parse_mhz))
-        {
-            Some([min, max])
-        } else {
-            None
-        }
-    }
-
-    fn parse_od_sclk(s: &str) -> Option<[i32; 2]> {
-        Self::parse_range(s, "SCLK:")
-    }
-
-    fn parse_od_sclk_offset(s: &str) -> Option<[i32; 2]> {
-        Self::parse_range(s, "SCLK_OFFSET:")
-    }
-
-    fn parse_od_mclk(s: &str) -> Option<[i32; 2]> {
-        Self::parse_range(s, "MCLK:")
-    }
-
     pub fn get_fw_versions(amdgpu_dev: &DeviceHandle) -> Vec<FwVer> {
         const FW_LIST: &[FW_TYPE] = &[
             FW_TYPE::VCE,
@@ -5,6 +5,8 @@ use crate::{
         DeviceHandle,
         GfxTargetVersion,
     },
+    KfdTopology,
+    PartitionInfo,
     PCI,
 };
 use crate::stat::ProcInfo;
@@ -26,6 +28,8 @@ pub struct DevicePath {
     pub render: PathBuf,
     pub card: PathBuf,
     pub accel: PathBuf,
+    /// render nodes of the secondary compute partitions (MI300 series)
+    pub partition_render: Vec<PathBuf>,
     pub pci: PCI::BUS_INFO,
     pub sysfs_path: PathBuf,
     pub device_id: Option<u32>,
@@ -108,36 +112,7 @@ impl DevicePath {
     }
 
     pub fn get_gfx_target_version_from_kfd(&self) -> Option<GfxTargetVersion> {
-        let drm_render_minor = {
-            const PRE: &str = "/dev/dri/renderD";
-            const PRE_LEN: usize = PRE.len();
-            let render = self.render.to_str()?;
-            if !render.starts_with(PRE) { return None }
-
-            format!("drm_render_minor {}", &render.get(PRE_LEN..)?)
-        };
-
-        let dirs = fs::read_dir("/sys/class/kfd/kfd/topology/nodes/").ok()?;
-        let mut gfx_target_version = String::new();
-
-        'node: for dir_entry in dirs.flatten() {
-            let Ok(s) = fs::read_to_string(dir_entry.path().join("properties")) else {
-                continue
-            };
-            let mut lines = s.lines();
-            let Some(ver_str) = lines
-                .find(|&l| l.starts_with("gfx_target_version")) else { continue };
-
-            if lines.any(|l| l.starts_with(&drm_render_minor)) {
-                gfx_target_version = ver_str.to_string();
-                break 'node;
-            }
-        }
-
-        const PRE_GFX_VER_LEN: usize = "gfx_target_version ".len();
-        let gfx_target_version: u32 = gfx_target_version.get(PRE_GFX_VER_LEN..)?.parse().ok()?;
-
-        Some(GfxTargetVersion::from(gfx_target_version))
+        KfdTopology::new().find_by_render(&self.render)?.gfx_target()
     }
 
     pub fn check_if_device_is_active(&self) -> bool {
@@ -152,6 +127,18 @@ impl DevicePath {
         s.starts_with("active")
     }
 
+    /// DRM nodes to find the processes using the device
+    pub fn drm_paths(&self) -> Vec<&PathBuf> {
+        if !self.is_amdgpu() {
+            return vec![&self.accel];
+        }
+
+        let mut paths = vec![&self.render, &self.card];
+        paths.extend(self.partition_render.iter());
+
+        paths
+    }
+
     pub fn menu_entry(&self) -> String {
         format!("{} ({})", self.device_name, self.pci)
     }
@@ -173,6 +160,9 @@ impl TryFrom<PCI::BUS_INFO> for DevicePath {
         let card = pci.get_drm_card_path()?;
         let accel = PathBuf::new();
         let sysfs_path = pci.get_sysfs_path();
+        let partition_render = PartitionInfo::new(&sysfs_path, pci)
+            .map(|info| info.secondary_render_paths())
+            .unwrap_or_default();
         let [device_id, revision_id] = [pci.get_device_id(), pci.get_revision_id()];
         let device_name = String::new();
         let arc_proc_index = Arc::new(Mutex::new(Vec::new()));
@@ -183,6 +173,7 @@ impl TryFrom<PCI::BUS_INFO> for DevicePath {
             render,
             card,
             accel,
+            partition_render,
             pci,
             sysfs_path,
             device_id,
@@ -202,6 +193,7 @@ impl fmt::Debug for DevicePath {
             .field("render", &self.render)
             .field("card", &self.card)
             .field("accel", &self.accel)
+            .field("partition_render", &self.partition_render)
             .field("pci", &self.pci.to_string())
             .field("sysfs_path", &self.sysfs_path)
             .field("device_id", &self.device_id)
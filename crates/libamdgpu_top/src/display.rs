@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::time::{Duration, Instant};
+use crate::{
+    ConnectorInfo,
+    DevicePath,
+    LibDrm,
+    drmModeConnection,
+    drmModeModeInfo,
+    DRM_MODE_OBJECT_CRTC,
+    get_drm_mode_object_properties,
+};
+use crate::drm_mode::connector_info_from_fd;
+
+// Live state of the connectors and CRTCs, re-polled to detect hotplug and mode changes.
+// `drmModeGetConnectorCurrent` does not force-probe the connectors,
+// so polling does not cause flickering; the connection status is updated by the hotplug handler of the driver.
+// `VRR_ENABLED` is a CRTC property, the others are connector properties.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayMode {
+    pub hdisplay: u16,
+    pub vdisplay: u16,
+    /// Hz
+    pub refresh_rate: f32,
+}
+
+impl DisplayMode {
+    pub fn from_mode_info(mode: &drmModeModeInfo) -> Self {
+        let total = mode.htotal as f32 * mode.vtotal as f32;
+        let refresh_rate = if total != 0.0 {
+            mode.clock as f32 * 1000.0 / total
+        } else {
+            mode.vrefresh as f32
+        };
+
+        Self { hdisplay: mode.hdisplay, vdisplay: mode.vdisplay, refresh_rate }
+    }
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}@{:.2}", self.hdisplay, self.vdisplay, self.refresh_rate)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DisplayState {
+    pub connector_id: u32,
+    /// e.g. "DisplayPort-1"
+    pub name: String,
+    pub connected: bool,
+    pub crtc_id: Option<u32>,
+    /// current mode of the CRTC, `None` if the CRTC is disabled
+    pub mode: Option<DisplayMode>,
+    pub vrr_capable: Option<bool>,
+    pub vrr_enabled: Option<bool>,
+    pub max_bpc: Option<u64>,
+    pub colorspace: Option<String>,
+    /// `HDR_OUTPUT_METADATA` blob is set
+    pub hdr_output_metadata: bool,
+    pub dpms: Option<String>,
+    pub link_status: Option<String>,
+}
+
+impl DisplayState {
+    /// `crtc_props`: (name, value) of the CRTC properties
+    pub fn new(conn: &ConnectorInfo, crtc_props: &[(String, u64)]) -> Self {
+        let mut state = Self {
+            connector_id: conn.connector_id,
+            name: format!("{}-{}", conn.connector_type, conn.connector_type_id),
+            connected: conn.connection == drmModeConnection::DRM_MODE_CONNECTED,
+            ..Default::default()
+        };
+
+        if let Some(crtc) = conn.crtc {
+            state.crtc_id = Some(crtc.crtc_id);
+            state.mode = (crtc.mode_valid != 0).then(|| DisplayMode::from_mode_info(&crtc.mode));
+        }
+
+        for (prop, value) in &conn.mode_props {
+            let value = *value;
+
+            match prop.name.as_str() {
+                "vrr_capable" => state.vrr_capable = Some(value != 0),
+                "max bpc" => state.max_bpc = Some(value),
+                "Colorspace" => state.colorspace = prop.enum_name(value),
+                "HDR_OUTPUT_METADATA" => state.hdr_output_metadata = value != 0,
+                "DPMS" => state.dpms = prop.enum_name(value),
+                "link-status" => state.link_status = prop.enum_name(value),
+                _ => {},
+            }
+        }
+
+        state.vrr_enabled = crtc_props
+            .iter()
+            .find(|(name, _)| name == "VRR_ENABLED")
+            .map(|(_, value)| *value != 0);
+
+        state
+    }
+
+    /// Changes from `pre`, e.g. "VRR: off -> on"
+    pub fn changes(&self, pre: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+        let on_off = |b: bool| if b { "on" } else { "off" };
+        let opt = |s: &Option<String>| s.clone().unwrap_or_else(|| "_".to_string());
+
+        if self.connected != pre.connected {
+            changes.push(if self.connected { "connected" } else { "disconnected" }.to_string());
+        }
+
+        if self.mode != pre.mode {
+            let mode = |m: &Option<DisplayMode>| m.map(|m| m.to_string()).unwrap_or_else(|| "disabled".to_string());
+            changes.push(format!("mode: {} -> {}", mode(&pre.mode), mode(&self.mode)));
+        }
+
+        if let (Some(pre_vrr), Some(vrr)) = (pre.vrr_enabled, self.vrr_enabled) && pre_vrr != vrr {
+            changes.push(format!("VRR: {} -> {}", on_off(pre_vrr), on_off(vrr)));
+        }
+
+        if self.max_bpc != pre.max_bpc {
+            let bpc = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_else(|| "_".to_string());
+            changes.push(format!("max bpc: {} -> {}", bpc(pre.max_bpc), bpc(self.max_bpc)));
+        }
+
+        if self.colorspace != pre.colorspace {
+            changes.push(format!("Colorspace: {} -> {}", opt(&pre.colorspace), opt(&self.colorspace)));
+        }
+
+        if self.hdr_output_metadata != pre.hdr_output_metadata {
+            changes.push(format!(
+                "HDR metadata: {} -> {}",
+                on_off(pre.hdr_output_metadata),
+                on_off(self.hdr_output_metadata),
+            ));
+        }
+
+        if self.dpms != pre.dpms {
+            changes.push(format!("DPMS: {} -> {}", opt(&pre.dpms), opt(&self.dpms)));
+        }
+
+        if self.link_status != pre.link_status {
+            changes.push(format!("link-status: {} -> {}", opt(&pre.link_status), opt(&self.link_status)));
+        }
+
+        changes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayEvent {
+    /// elapsed time since the session started
+    pub time: Duration,
+    pub connector: String,
+    pub change: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DisplayStat {
+    start: Instant,
+    pub displays: Vec<DisplayState>,
+    pub events: VecDeque<DisplayEvent>,
+}
+
+impl DisplayStat {
+    pub const MAX_EVENTS: usize = 128;
+
+    pub fn new(displays: Vec<DisplayState>) -> Self {
+        Self {
+            start: Instant::now(),
+            displays,
+            events: VecDeque::with_capacity(Self::MAX_EVENTS),
+        }
+    }
+
+    pub fn connected(&self) -> impl Iterator<Item = &DisplayState> {
+        self.displays.iter().filter(|d| d.connected)
+    }
+
+    pub fn update(&mut self, displays: Vec<DisplayState>) {
+        self.update_with_elapsed(self.start.elapsed(), displays);
+    }
+
+    pub fn update_with_elapsed(&mut self, now: Duration, displays: Vec<DisplayState>) {
+        let mut events: Vec<(String, String)> = Vec::new();
+
+        for cur in &displays {
+            match self.displays.iter().find(|pre| pre.connector_id == cur.connector_id) {
+                Some(pre) => events.extend(cur.changes(pre).into_iter().map(|c| (cur.name.clone(), c))),
+                // e.g. DP MST
+                None => events.push((cur.name.clone(), "added".to_string())),
+            }
+        }
+
+        for pre in &self.displays {
+            if !displays.iter().any(|cur| cur.connector_id == pre.connector_id) {
+                events.push((pre.name.clone(), "removed".to_string()));
+            }
+        }
+
+        for (connector, change) in events {
+            if self.events.len() >= Self::MAX_EVENTS {
+                self.events.pop_front();
+            }
+
+            self.events.push_back(DisplayEvent { time: now, connector, change });
+        }
+
+        self.displays = displays;
+    }
+}
+
+pub struct DisplayMonitor {
+    libdrm: LibDrm,
+    card: File,
+    pub stat: DisplayStat,
+}
+
+impl DisplayMonitor {
+    /// `None` if the device has no connectors
+    pub fn new(device_path: &DevicePath) -> Option<Self> {
+        let libdrm = device_path.libdrm_amdgpu.clone().map(LibDrm::from)?;
+        let card = File::open(&device_path.card).ok()?;
+
+        libdrm.set_all_client_caps(card.as_raw_fd());
+
+        let mut monitor = Self { libdrm, card, stat: DisplayStat::new(Vec::new()) };
+        let displays = monitor.read_displays();
+
+        if displays.is_empty() {
+            return None;
+        }
+
+        monitor.stat.displays = displays;
+
+        Some(monitor)
+    }
+
+    fn read_displays(&self) -> Vec<DisplayState> {
+        let fd = self.card.as_raw_fd();
+
+        connector_info_from_fd(&self.libdrm, fd, false).iter().map(|conn| {
+            let crtc_props = conn.crtc
+                .and_then(|crtc| get_drm_mode_object_properties(fd, crtc.crtc_id, DRM_MODE_OBJECT_CRTC).ok())
+                .unwrap_or_default();
+
+            DisplayState::new(conn, &crtc_props)
+        }).collect()
+    }
+
+    pub fn update(&mut self) {
+        let displays = self.read_displays();
+
+        self.stat.update(displays);
+    }
+}
+
+#[test]
+fn test_display_stat() {
+    let secs = Duration::from_secs;
+    let dp = DisplayState {
+        connector_id: 100,
+        name: "DisplayPort-1".to_string(),
+        connected: true,
+        crtc_id: Some(80),
+        mode: Some(DisplayMode { hdisplay: 2560, vdisplay: 1440, refresh_rate: 143.91 }),
+        vrr_capable: Some(true),
+        vrr_enabled: Some(false),
+        max_bpc: Some(8),
+        dpms: Some("On".to_string()),
+        link_status: Some("Good".to_string()),
+        ..Default::default()
+    };
+    let hdmi = DisplayState { connector_id: 110, name: "HDMI-A-1".to_string(), ..Default::default() };
+    let mut stat = DisplayStat::new(vec![dp.clone(), hdmi.clone()]);
+
+    stat.update_with_elapsed(secs(1), vec![dp.clone(), hdmi.clone()]);
+    assert!(stat.events.is_empty());
+
+    let dp_vrr = DisplayState { vrr_enabled: Some(true), max_bpc: Some(10), ..dp.clone() };
+    let hdmi_connected = DisplayState {
+        connected: true,
+        mode: Some(DisplayMode { hdisplay: 1920, vdisplay: 1080, refresh_rate: 60.0 }),
+        ..hdmi.clone()
+    };
+    stat.update_with_elapsed(secs(2), vec![dp_vrr, hdmi_connected]);
+
+    let changes: Vec<&str> = stat.events.iter().map(|e| e.change.as_str()).collect();
+    assert_eq!(changes, vec![
+        "VRR: off -> on",
+        "max bpc: 8 -> 10",
+        "connected",
+        "mode: disabled -> 1920x1080@60.00",
+    ]);
+    assert_eq!(stat.connected().count(), 2);
+
+    stat.update_with_elapsed(secs(3), vec![dp]);
+    assert_eq!(stat.events.back().unwrap().change, "removed");
+    assert_eq!(stat.events.back().unwrap().time, secs(3));
+}
@@ -8,8 +8,9 @@ use crate::{
     drmModeCrtc,
     drmModeModeInfo,
     drm_mode_property_enum,
+    get_drm_mode_property_blob,
 };
-use crate::DevicePath;
+use crate::{DevicePath, Edid};
 
 #[derive(Debug, Clone)]
 pub struct ConnectorInfo {
@@ -20,6 +21,7 @@ pub struct ConnectorInfo {
     pub mode_info: Vec<drmModeModeInfo>,
     pub mode_props: Vec<(ModeProp, u64)>,
     pub crtc: Option<drmModeCrtc>,
+    pub edid: Option<Edid>,
 }
 
 impl ConnectorInfo {
@@ -55,6 +57,11 @@ impl ModeProp {
 
         s
     }
+
+    /// name of the enum value, e.g. "On" for DPMS, "Good" for link-status
+    pub fn enum_name(&self, value: u64) -> Option<String> {
+        self.enums.iter().find(|enum_| enum_.value == value).map(|enum_| enum_.name())
+    }
 }
 
 pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
@@ -67,6 +74,12 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
     let fd = f.as_raw_fd();
 
     libdrm.set_all_client_caps(fd);
+
+    connector_info_from_fd(&libdrm, fd, true)
+}
+
+/// `with_edid`: fetch and decode the EDID blob, not needed for the periodic display monitoring
+pub(crate) fn connector_info_from_fd(libdrm: &LibDrm, fd: i32, with_edid: bool) -> Vec<ConnectorInfo> {
     let Some(drm_mode_res) = libdrm.get_drm_mode_resources(fd) else { return Vec::new() };
     let current_connectors = drm_mode_res.get_drm_mode_all_connector_current(fd);
 
@@ -115,6 +128,16 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
             None
         };
 
+        let edid = if with_edid {
+            mode_props
+                .iter()
+                .find(|prop| prop.0.name == "EDID" && prop.1 != 0)
+                .and_then(|prop| get_drm_mode_property_blob(fd, prop.1 as u32).ok())
+                .and_then(|blob| Edid::parse(&blob))
+        } else {
+            None
+        };
+
         Some(ConnectorInfo {
             connector_id,
             connector_type,
@@ -123,6 +146,7 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
             mode_info,
             mode_props,
             crtc,
+            edid,
         })
     }).collect();
 
@@ -0,0 +1,291 @@
+use std::ffi::CStr;
+use nix::{errno::Errno, ioctl_readwrite, ioctl_write_ptr};
+
+// The properties of CRTCs and planes, the property blobs, encoders, planes and framebuffers
+// are not available from libdrm_amdgpu_sys, so they are read with DRM_IOCTL_MODE_OBJ_GETPROPERTIES,
+// DRM_IOCTL_MODE_GETPROPERTY, DRM_IOCTL_MODE_GETPROPBLOB, DRM_IOCTL_MODE_GETRESOURCES,
+// DRM_IOCTL_MODE_GETENCODER, DRM_IOCTL_MODE_GETPLANERESOURCES, DRM_IOCTL_MODE_GETPLANE
+// and DRM_IOCTL_MODE_GETFB2.
+// ref: include/uapi/drm/drm.h, include/uapi/drm/drm_mode.h
+
+const DRM_IOCTL_BASE: u8 = b'd';
+
+pub const DRM_MODE_OBJECT_CRTC: u32 = 0xcccccccc;
+pub const DRM_MODE_OBJECT_CONNECTOR: u32 = 0xc0c0c0c0;
+pub const DRM_MODE_OBJECT_ENCODER: u32 = 0xe0e0e0e0;
+pub const DRM_MODE_OBJECT_PLANE: u32 = 0xeeeeeeee;
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_obj_get_properties {
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_props: u32,
+    obj_id: u32,
+    obj_type: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_get_property {
+    values_ptr: u64,
+    enum_blob_ptr: u64,
+    prop_id: u32,
+    flags: u32,
+    name: [u8; 32],
+    count_values: u32,
+    count_enum_blobs: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_get_blob {
+    blob_id: u32,
+    length: u32,
+    data: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_card_res {
+    fb_id_ptr: u64,
+    crtc_id_ptr: u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr: u64,
+    count_fbs: u32,
+    count_crtcs: u32,
+    count_connectors: u32,
+    count_encoders: u32,
+    min_width: u32,
+    max_width: u32,
+    min_height: u32,
+    max_height: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct drm_mode_get_encoder {
+    pub encoder_id: u32,
+    pub encoder_type: u32,
+    pub crtc_id: u32,
+    pub possible_crtcs: u32,
+    pub possible_clones: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_get_plane_res {
+    plane_id_ptr: u64,
+    count_planes: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_get_plane {
+    plane_id: u32,
+    crtc_id: u32,
+    fb_id: u32,
+    possible_crtcs: u32,
+    gamma_size: u32,
+    count_format_types: u32,
+    format_type_ptr: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_mode_fb_cmd2 {
+    fb_id: u32,
+    width: u32,
+    height: u32,
+    pixel_format: u32,
+    flags: u32,
+    handles: [u32; 4],
+    pitches: [u32; 4],
+    offsets: [u32; 4],
+    modifier: [u64; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct drm_gem_close {
+    handle: u32,
+    pad: u32,
+}
+
+const DRM_MODE_FB_MODIFIERS: u32 = 1 << 1;
+
+ioctl_write_ptr!(drm_gem_close_ioctl, DRM_IOCTL_BASE, 0x09, drm_gem_close);
+ioctl_readwrite!(drm_mode_getresources, DRM_IOCTL_BASE, 0xA0, drm_mode_card_res);
+ioctl_readwrite!(drm_mode_getencoder, DRM_IOCTL_BASE, 0xA6, drm_mode_get_encoder);
+ioctl_readwrite!(drm_mode_getplaneresources, DRM_IOCTL_BASE, 0xB5, drm_mode_get_plane_res);
+ioctl_readwrite!(drm_mode_getplane, DRM_IOCTL_BASE, 0xB6, drm_mode_get_plane);
+ioctl_readwrite!(drm_mode_getfb2, DRM_IOCTL_BASE, 0xCE, drm_mode_fb_cmd2);
+ioctl_readwrite!(drm_mode_getpropblob, DRM_IOCTL_BASE, 0xAC, drm_mode_get_blob);
+ioctl_readwrite!(drm_mode_obj_getproperties, DRM_IOCTL_BASE, 0xB9, drm_mode_obj_get_properties);
+ioctl_readwrite!(drm_mode_getproperty, DRM_IOCTL_BASE, 0xAA, drm_mode_get_property);
+
+/// (name, value) of the properties of the DRM mode object
+pub fn get_drm_mode_object_properties(
+    fd: i32,
+    obj_id: u32,
+    obj_type: u32,
+) -> Result<Vec<(String, u64)>, Errno> {
+    let mut arg = drm_mode_obj_get_properties { obj_id, obj_type, ..Default::default() };
+
+    unsafe { drm_mode_obj_getproperties(fd, &mut arg)?; }
+
+    let len = arg.count_props as usize;
+    let mut props = vec![0u32; len];
+    let mut values = vec![0u64; len];
+
+    arg.props_ptr = props.as_mut_ptr() as u64;
+    arg.prop_values_ptr = values.as_mut_ptr() as u64;
+
+    unsafe { drm_mode_obj_getproperties(fd, &mut arg)?; }
+
+    // the number of properties may be decreased between the calls
+    let len = len.min(arg.count_props as usize);
+
+    let props = props[..len].iter().zip(values).filter_map(|(prop_id, value)| {
+        let mut prop = drm_mode_get_property { prop_id: *prop_id, ..Default::default() };
+
+        unsafe { drm_mode_getproperty(fd, &mut prop).ok()?; }
+
+        let name = CStr::from_bytes_until_nul(&prop.name).ok()?.to_string_lossy().to_string();
+
+        Some((name, value))
+    }).collect();
+
+    Ok(props)
+}
+
+/// data of the property blob, e.g. `EDID`
+pub fn get_drm_mode_property_blob(fd: i32, blob_id: u32) -> Result<Vec<u8>, Errno> {
+    let mut arg = drm_mode_get_blob { blob_id, ..Default::default() };
+
+    unsafe { drm_mode_getpropblob(fd, &mut arg)?; }
+
+    let mut data = vec![0u8; arg.length as usize];
+
+    arg.data = data.as_mut_ptr() as u64;
+
+    unsafe { drm_mode_getpropblob(fd, &mut arg)?; }
+
+    data.truncate(arg.length as usize);
+
+    Ok(data)
+}
+
+pub fn get_drm_mode_encoders(fd: i32) -> Result<Vec<drm_mode_get_encoder>, Errno> {
+    let mut res = drm_mode_card_res::default();
+
+    unsafe { drm_mode_getresources(fd, &mut res)?; }
+
+    let mut encoder_ids = vec![0u32; res.count_encoders as usize];
+    // only the encoder ids are requested
+    let mut res = drm_mode_card_res {
+        encoder_id_ptr: encoder_ids.as_mut_ptr() as u64,
+        count_encoders: res.count_encoders,
+        ..Default::default()
+    };
+
+    unsafe { drm_mode_getresources(fd, &mut res)?; }
+
+    encoder_ids.truncate(res.count_encoders as usize);
+
+    let encoders = encoder_ids.iter().filter_map(|encoder_id| {
+        let mut encoder = drm_mode_get_encoder { encoder_id: *encoder_id, ..Default::default() };
+
+        unsafe { drm_mode_getencoder(fd, &mut encoder).ok()?; }
+
+        Some(encoder)
+    }).collect();
+
+    Ok(encoders)
+}
+
+/// requires `DRM_CLIENT_CAP_UNIVERSAL_PLANES` to get the primary and cursor planes
+pub fn get_drm_mode_plane_ids(fd: i32) -> Result<Vec<u32>, Errno> {
+    let mut res = drm_mode_get_plane_res::default();
+
+    unsafe { drm_mode_getplaneresources(fd, &mut res)?; }
+
+    let mut plane_ids = vec![0u32; res.count_planes as usize];
+
+    res.plane_id_ptr = plane_ids.as_mut_ptr() as u64;
+
+    unsafe { drm_mode_getplaneresources(fd, &mut res)?; }
+
+    plane_ids.truncate(res.count_planes as usize);
+
+    Ok(plane_ids)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrmModePlane {
+    pub plane_id: u32,
+    /// 0 if the plane is disabled
+    pub crtc_id: u32,
+    /// 0 if the plane is disabled
+    pub fb_id: u32,
+    pub possible_crtcs: u32,
+    /// fourcc
+    pub formats: Vec<u32>,
+}
+
+pub fn get_drm_mode_plane(fd: i32, plane_id: u32) -> Result<DrmModePlane, Errno> {
+    let mut arg = drm_mode_get_plane { plane_id, ..Default::default() };
+
+    unsafe { drm_mode_getplane(fd, &mut arg)?; }
+
+    let mut formats = vec![0u32; arg.count_format_types as usize];
+
+    arg.format_type_ptr = formats.as_mut_ptr() as u64;
+
+    unsafe { drm_mode_getplane(fd, &mut arg)?; }
+
+    formats.truncate(arg.count_format_types as usize);
+
+    Ok(DrmModePlane {
+        plane_id,
+        crtc_id: arg.crtc_id,
+        fb_id: arg.fb_id,
+        possible_crtcs: arg.possible_crtcs,
+        formats,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrmModeFb {
+    pub fb_id: u32,
+    pub width: u32,
+    pub height: u32,
+    /// fourcc
+    pub pixel_format: u32,
+    pub pitch: u32,
+    /// `None` if the framebuffer was created without a modifier
+    pub modifier: Option<u64>,
+}
+
+pub fn get_drm_mode_fb(fd: i32, fb_id: u32) -> Result<DrmModeFb, Errno> {
+    let mut arg = drm_mode_fb_cmd2 { fb_id, ..Default::default() };
+
+    unsafe { drm_mode_getfb2(fd, &mut arg)?; }
+
+    // GEM handles are returned only to DRM master or CAP_SYS_ADMIN, and they are not needed
+    for (i, handle) in arg.handles.iter().enumerate() {
+        if *handle == 0 || arg.handles[..i].contains(handle) { continue }
+
+        let arg = drm_gem_close { handle: *handle, ..Default::default() };
+        let _ = unsafe { drm_gem_close_ioctl(fd, &arg) };
+    }
+
+    Ok(DrmModeFb {
+        fb_id,
+        width: arg.width,
+        height: arg.height,
+        pixel_format: arg.pixel_format,
+        pitch: arg.pitches[0],
+        modifier: (arg.flags & DRM_MODE_FB_MODIFIERS != 0).then_some(arg.modifier[0]),
+    })
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use crate::{
+    DevicePath,
+    LibDrm,
+    drmModeCrtc,
+    DrmModeFb,
+    drm_mode_get_encoder,
+    DRM_MODE_OBJECT_CRTC,
+    DRM_MODE_OBJECT_PLANE,
+    get_drm_mode_encoders,
+    get_drm_mode_fb,
+    get_drm_mode_object_properties,
+    get_drm_mode_plane,
+    get_drm_mode_plane_ids,
+    get_drm_mode_property_blob,
+};
+
+// CRTCs, encoders, planes and the framebuffers scanned out by the planes.
+// ref: include/uapi/drm/drm_mode.h, include/uapi/drm/drm_fourcc.h, include/drm/drm_blend.h
+
+/// fourcc of the pixel format, e.g. "XR24"
+pub fn fourcc_name(fourcc: u32) -> String {
+    // DRM_FORMAT_BIG_ENDIAN
+    let big_endian = fourcc & (1 << 31) != 0;
+    let s: String = (fourcc & !(1 << 31)).to_le_bytes().iter().map(|c| *c as char).collect();
+
+    if big_endian { format!("{s}_BE") } else { s }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrmFormatModifier(pub u64);
+
+impl DrmFormatModifier {
+    pub const LINEAR: u64 = 0;
+    pub const INVALID: u64 = 0x00FF_FFFF_FFFF_FFFF;
+    const VENDOR_AMD: u64 = 0x02;
+
+    pub fn vendor(&self) -> u64 {
+        self.0 >> 56
+    }
+
+    fn amd_tile_name(tile_version: u64, tile: u64) -> Option<&'static str> {
+        Some(match (tile_version, tile) {
+            // GFX12
+            (5, 1) => "GFX12_256B_2D",
+            (5, 2) => "GFX12_4K_2D",
+            (5, 3) => "GFX12_64K_2D",
+            (5, 4) => "GFX12_256K_2D",
+            (_, 9) => "GFX9_64K_S",
+            (_, 10) => "GFX9_64K_D",
+            (_, 25) => "GFX9_64K_S_X",
+            (_, 26) => "GFX9_64K_D_X",
+            (_, 27) => "GFX9_64K_R_X",
+            (_, 31) => "GFX11_256K_R_X",
+            _ => return None,
+        })
+    }
+
+    /// e.g. "AMD(GFX10_RBPLUS, GFX9_64K_R_X, DCC, DCC_INDEPENDENT_64B, ...)"
+    pub fn name(&self) -> String {
+        match self.0 {
+            Self::LINEAR => return "LINEAR".to_string(),
+            Self::INVALID => return "INVALID".to_string(),
+            _ => {},
+        }
+
+        if self.vendor() != Self::VENDOR_AMD {
+            return format!("{:#018X}", self.0);
+        }
+
+        // AMD_FMT_MOD
+        let field = |shift: u32, mask: u64| (self.0 >> shift) & mask;
+        let tile_version = field(0, 0xFF);
+        let tile = field(8, 0x1F);
+        let mut v = vec![
+            match tile_version {
+                1 => "GFX9".to_string(),
+                2 => "GFX10".to_string(),
+                3 => "GFX10_RBPLUS".to_string(),
+                4 => "GFX11".to_string(),
+                5 => "GFX12".to_string(),
+                _ => format!("TILE_VERSION={tile_version}"),
+            },
+            Self::amd_tile_name(tile_version, tile).map_or_else(|| format!("TILE={tile}"), |s| s.to_string()),
+        ];
+
+        for (shift, name) in [
+            (13, "DCC"),
+            (14, "DCC_RETILE"),
+            (15, "DCC_PIPE_ALIGN"),
+            (16, "DCC_INDEPENDENT_64B"),
+            (17, "DCC_INDEPENDENT_128B"),
+            (20, "DCC_CONSTANT_ENCODE"),
+        ] {
+            if field(shift, 0x1) != 0 {
+                v.push(name.to_string());
+            }
+        }
+
+        if field(13, 0x1) != 0 {
+            v.push(format!("DCC_MAX_COMPRESSED_BLOCK={}", field(18, 0x3)));
+        }
+
+        for (shift, name) in [
+            (21, "PIPE_XOR_BITS"),
+            (24, "BANK_XOR_BITS"),
+            (27, "PACKERS"),
+            (30, "RB"),
+            (33, "PIPE"),
+        ] {
+            let val = field(shift, 0x7);
+
+            if val != 0 {
+                v.push(format!("{name}={val}"));
+            }
+        }
+
+        format!("AMD({})", v.join(", "))
+    }
+}
+
+impl fmt::Display for DrmFormatModifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// (modifier, formats) from the `IN_FORMATS` blob (struct drm_format_modifier_blob)
+pub fn parse_in_formats(blob: &[u8]) -> Vec<(DrmFormatModifier, Vec<u32>)> {
+    let u32_at = |pos: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(blob.get(pos..pos+4)?.try_into().ok()?))
+    };
+    let u64_at = |pos: usize| -> Option<u64> {
+        Some(u64::from_le_bytes(blob.get(pos..pos+8)?.try_into().ok()?))
+    };
+    let (Some(count_formats), Some(formats_offset), Some(count_modifiers), Some(modifiers_offset)) =
+        (u32_at(8), u32_at(12), u32_at(16), u32_at(20)) else { return Vec::new() };
+    let formats: Vec<u32> = (0..count_formats as usize)
+        .map_while(|i| u32_at(formats_offset as usize + i * 4))
+        .collect();
+
+    // struct drm_format_modifier { __u64 formats; __u32 offset; __u32 pad; __u64 modifier; }
+    (0..count_modifiers as usize).map_while(|i| {
+        let pos = modifiers_offset as usize + i * 24;
+        let bitmask = u64_at(pos)?;
+        let offset = u32_at(pos + 8)? as usize;
+        let modifier = u64_at(pos + 16)?;
+        let formats = (0..64)
+            .filter(|bit| bitmask & (1 << bit) != 0)
+            .filter_map(|bit| formats.get(offset + bit).copied())
+            .collect();
+
+        Some((DrmFormatModifier(modifier), formats))
+    }).collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct CrtcInfo {
+    pub crtc: drmModeCrtc,
+    pub props: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncoderInfo {
+    pub encoder_id: u32,
+    pub encoder_type: u32,
+    /// 0 if the encoder is not used
+    pub crtc_id: u32,
+    pub possible_crtcs: u32,
+    pub possible_clones: u32,
+}
+
+impl EncoderInfo {
+    pub fn encoder_type_name(&self) -> &'static str {
+        match self.encoder_type {
+            0 => "None",
+            1 => "DAC",
+            2 => "TMDS",
+            3 => "LVDS",
+            4 => "TVDAC",
+            5 => "Virtual",
+            6 => "DSI",
+            7 => "DPMST",
+            8 => "DPI",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl From<drm_mode_get_encoder> for EncoderInfo {
+    fn from(e: drm_mode_get_encoder) -> Self {
+        Self {
+            encoder_id: e.encoder_id,
+            encoder_type: e.encoder_type,
+            crtc_id: e.crtc_id,
+            possible_crtcs: e.possible_crtcs,
+            possible_clones: e.possible_clones,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaneInfo {
+    pub plane_id: u32,
+    /// `type` property, 0: Overlay, 1: Primary, 2: Cursor
+    pub plane_type: Option<u64>,
+    /// 0 if the plane is disabled
+    pub crtc_id: u32,
+    pub possible_crtcs: u32,
+    /// fourcc
+    pub formats: Vec<u32>,
+    /// from `IN_FORMATS`
+    pub in_formats: Vec<(DrmFormatModifier, Vec<u32>)>,
+    pub fb: Option<DrmModeFb>,
+    pub zpos: Option<u64>,
+    /// bitmask of `rotation`
+    pub rotation: Option<u64>,
+    /// 0x0 - 0xFFFF
+    pub alpha: Option<u64>,
+    pub props: Vec<(String, u64)>,
+}
+
+impl PlaneInfo {
+    pub fn plane_type_name(&self) -> &'static str {
+        match self.plane_type {
+            Some(0) => "Overlay",
+            Some(1) => "Primary",
+            Some(2) => "Cursor",
+            _ => "Unknown",
+        }
+    }
+
+    /// e.g. "rotate-0, reflect-x"
+    pub fn rotation_string(&self) -> Option<String> {
+        let rotation = self.rotation?;
+        let names: Vec<&str> = [
+            "rotate-0",
+            "rotate-90",
+            "rotate-180",
+            "rotate-270",
+            "reflect-x",
+            "reflect-y",
+        ]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| rotation & (1 << i) != 0)
+            .map(|(_, name)| *name)
+            .collect();
+
+        Some(names.join(", "))
+    }
+
+    fn new(fd: i32, plane_id: u32) -> Option<Self> {
+        let plane = get_drm_mode_plane(fd, plane_id).ok()?;
+        let props = get_drm_mode_object_properties(fd, plane_id, DRM_MODE_OBJECT_PLANE).unwrap_or_default();
+        let prop = |name: &str| props.iter().find(|(n, _)| n == name).map(|(_, v)| *v);
+        let in_formats = prop("IN_FORMATS")
+            .filter(|blob_id| *blob_id != 0)
+            .and_then(|blob_id| get_drm_mode_property_blob(fd, blob_id as u32).ok())
+            .map(|blob| parse_in_formats(&blob))
+            .unwrap_or_default();
+        let fb = if plane.fb_id != 0 { get_drm_mode_fb(fd, plane.fb_id).ok() } else { None };
+
+        Some(Self {
+            plane_id,
+            plane_type: prop("type"),
+            crtc_id: plane.crtc_id,
+            possible_crtcs: plane.possible_crtcs,
+            formats: plane.formats,
+            in_formats,
+            fb,
+            zpos: prop("zpos"),
+            rotation: prop("rotation"),
+            alpha: prop("alpha"),
+            props,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DrmObjectsInfo {
+    pub crtcs: Vec<CrtcInfo>,
+    pub encoders: Vec<EncoderInfo>,
+    pub planes: Vec<PlaneInfo>,
+}
+
+impl DrmObjectsInfo {
+    pub fn new(device_path: &DevicePath) -> Self {
+        let Some(libdrm) = device_path.libdrm_amdgpu.clone().map(LibDrm::from) else {
+            return Self::default();
+        };
+        let Some(f) = File::open(&device_path.card).ok() else { return Self::default() };
+        let fd = f.as_raw_fd();
+
+        // for DRM_CLIENT_CAP_UNIVERSAL_PLANES and DRM_CLIENT_CAP_ATOMIC
+        libdrm.set_all_client_caps(fd);
+
+        let crtcs = libdrm
+            .get_drm_mode_resources(fd)
+            .map(|res| res.get_drm_mode_all_crtcs(fd))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|crtc| {
+                let props = get_drm_mode_object_properties(fd, crtc.crtc_id, DRM_MODE_OBJECT_CRTC)
+                    .unwrap_or_default();
+
+                CrtcInfo { crtc, props }
+            })
+            .collect();
+        let encoders = get_drm_mode_encoders(fd)
+            .unwrap_or_default()
+            .into_iter()
+            .map(EncoderInfo::from)
+            .collect();
+        let planes = get_drm_mode_plane_ids(fd)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|plane_id| PlaneInfo::new(fd, plane_id))
+            .collect();
+
+        Self { crtcs, encoders, planes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.crtcs.is_empty() && self.encoders.is_empty() && self.planes.is_empty()
+    }
+}
+
+#[test]
+fn test_in_formats() {
+    const XR24: u32 = u32::from_le_bytes(*b"XR24");
+    const AR24: u32 = u32::from_le_bytes(*b"AR24");
+    const NV12: u32 = u32::from_le_bytes(*b"NV12");
+    // GFX10_RBPLUS, GFX9_64K_R_X, DCC, DCC_INDEPENDENT_64B, DCC_MAX_COMPRESSED_BLOCK=1 (128B), PIPE_XOR_BITS=3, PACKERS=2
+    const AMD_DCC: u64 = (0x02 << 56) | 3 | (27 << 8) | (1 << 13) | (1 << 16) | (1 << 18) | (3 << 21) | (2 << 27);
+
+    let mut blob = Vec::new();
+    // version, flags, count_formats, formats_offset, count_modifiers, modifiers_offset
+    for v in [1u32, 0, 3, 24, 2, 40] {
+        blob.extend(v.to_le_bytes());
+    }
+    for v in [XR24, AR24, NV12, 0] {
+        blob.extend(v.to_le_bytes());
+    }
+    for (formats, modifier) in [(0b111u64, DrmFormatModifier::LINEAR), (0b011, AMD_DCC)] {
+        blob.extend(formats.to_le_bytes());
+        blob.extend([0u8; 8]);
+        blob.extend(modifier.to_le_bytes());
+    }
+
+    let in_formats = parse_in_formats(&blob);
+
+    assert_eq!(in_formats.len(), 2);
+    assert_eq!(in_formats[0].0.to_string(), "LINEAR");
+    assert_eq!(in_formats[0].1, vec![XR24, AR24, NV12]);
+    assert_eq!(in_formats[1].1, vec![XR24, AR24]);
+    assert_eq!(
+        in_formats[1].0.to_string(),
+        "AMD(GFX10_RBPLUS, GFX9_64K_R_X, DCC, DCC_INDEPENDENT_64B, DCC_MAX_COMPRESSED_BLOCK=1, PIPE_XOR_BITS=3, PACKERS=2)",
+    );
+    assert_eq!(fourcc_name(NV12), "NV12");
+    assert!(parse_in_formats(&blob[..16]).is_empty());
+}
@@ -0,0 +1,462 @@
+use std::fmt;
+
+// ref: VESA E-EDID Standard Release A2 (EDID 1.4)
+// ref: CTA-861-H
+// ref: VESA DisplayID Standard v2.0
+// ref: https://git.linuxtv.org/edid-decode.git
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+const EDID_BLOCK_SIZE: usize = 128;
+
+const CTA_EXTENSION_TAG: u8 = 0x02;
+const DISPLAYID_EXTENSION_TAG: u8 = 0x70;
+
+// bytes 35-37
+const ESTABLISHED_TIMINGS: [&str; 17] = [
+    "720x400@70", "720x400@88", "640x480@60", "640x480@67",
+    "640x480@72", "640x480@75", "800x600@56", "800x600@60",
+    "800x600@72", "800x600@75", "832x624@75", "1024x768@87i",
+    "1024x768@60", "1024x768@70", "1024x768@75", "1280x1024@75",
+    "1152x870@75",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdidTiming {
+    pub hactive: u16,
+    pub vactive: u16,
+    /// Hz
+    pub refresh_rate: f32,
+}
+
+impl EdidTiming {
+    // 18-byte detailed timing descriptor, `None` for display descriptors
+    fn from_dtd(d: &[u8]) -> Option<Self> {
+        let clock = u16::from_le_bytes([*d.first()?, *d.get(1)?]) as f32 * 10_000.0;
+
+        if clock == 0.0 { return None }
+
+        let hactive = d[2] as u16 | ((d[4] as u16 >> 4) << 8);
+        let hblank = d[3] as u16 | ((d[4] as u16 & 0xF) << 8);
+        let vactive = d[5] as u16 | ((d[7] as u16 >> 4) << 8);
+        let vblank = d[6] as u16 | ((d[7] as u16 & 0xF) << 8);
+        let total = (hactive + hblank) as f32 * (vactive + vblank) as f32;
+        let refresh_rate = if total != 0.0 { clock / total } else { 0.0 };
+
+        Some(Self { hactive, vactive, refresh_rate })
+    }
+
+    // 2-byte standard timing, `None` for unused entries
+    fn from_standard_timing(b: [u8; 2], version: (u8, u8)) -> Option<Self> {
+        if b == [0x01, 0x01] || b[0] == 0 { return None }
+
+        let hactive = (b[0] as u16 + 31) * 8;
+        let vactive = match b[1] >> 6 {
+            0 if version < (1, 3) => hactive,
+            0 => hactive * 10 / 16,
+            1 => hactive * 3 / 4,
+            2 => hactive * 4 / 5,
+            _ => hactive * 9 / 16,
+        };
+        let refresh_rate = ((b[1] & 0x3F) + 60) as f32;
+
+        Some(Self { hactive, vactive, refresh_rate })
+    }
+}
+
+impl fmt::Display for EdidTiming {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}@{:.2}", self.hactive, self.vactive, self.refresh_rate)
+    }
+}
+
+/// Display Range Limits descriptor (tag 0xFD)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdidRangeLimits {
+    /// Hz
+    pub min_vrate: u16,
+    pub max_vrate: u16,
+    /// kHz
+    pub min_hrate: u16,
+    pub max_hrate: u16,
+    /// MHz
+    pub max_pixel_clock: u16,
+}
+
+impl EdidRangeLimits {
+    fn from_descriptor(d: &[u8]) -> Option<Self> {
+        let flags = *d.get(4)?;
+        // offsets for the rates > 255
+        let add = |bit_min: u8, bit_max: u8| -> (u16, u16) {
+            (
+                if flags & bit_min != 0 { 255 } else { 0 },
+                if flags & bit_max != 0 { 255 } else { 0 },
+            )
+        };
+        let (vmin_add, vmax_add) = add(0b0001, 0b0010);
+        let (hmin_add, hmax_add) = add(0b0100, 0b1000);
+
+        Some(Self {
+            min_vrate: *d.get(5)? as u16 + vmin_add,
+            max_vrate: *d.get(6)? as u16 + vmax_add,
+            min_hrate: *d.get(7)? as u16 + hmin_add,
+            max_hrate: *d.get(8)? as u16 + hmax_add,
+            max_pixel_clock: *d.get(9)? as u16 * 10,
+        })
+    }
+}
+
+/// HDR Static Metadata Data Block of CTA-861
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrStaticMetadata {
+    pub eotf: Vec<&'static str>,
+    /// cd/m^2
+    pub max_luminance: Option<f32>,
+    pub max_frame_avg_luminance: Option<f32>,
+    pub min_luminance: Option<f32>,
+}
+
+impl HdrStaticMetadata {
+    fn parse(payload: &[u8]) -> Option<Self> {
+        let eotf_bits = *payload.first()?;
+        let eotf = [
+            (0b0001, "SDR"),
+            (0b0010, "HDR"),
+            (0b0100, "SMPTE ST2084"),
+            (0b1000, "HLG"),
+        ]
+            .iter()
+            .filter(|(bit, _)| eotf_bits & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        let luminance = |cv: Option<&u8>| cv.filter(|cv| **cv != 0).map(|cv| 50.0 * 2f32.powf(*cv as f32 / 32.0));
+        let max_luminance = luminance(payload.get(2));
+        let max_frame_avg_luminance = luminance(payload.get(3));
+        let min_luminance = max_luminance
+            .zip(payload.get(4))
+            .map(|(max, cv)| max * (*cv as f32 / 255.0).powi(2) / 100.0);
+
+        Some(Self { eotf, max_luminance, max_frame_avg_luminance, min_luminance })
+    }
+}
+
+/// CTA-861 extension block
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CtaExtension {
+    pub revision: u8,
+    /// Short Video Descriptors (VIC)
+    pub vics: Vec<u8>,
+    pub detailed_timings: Vec<EdidTiming>,
+    pub hdr_static_metadata: Option<HdrStaticMetadata>,
+    /// (min, max) Hz, from the AMD Vendor-Specific Data Block (FreeSync)
+    pub amd_vrr_range: Option<(u8, u8)>,
+    /// the display has the HDMI Forum VSDB
+    pub hdmi_forum: bool,
+}
+
+impl CtaExtension {
+    const AMD_OUI: [u8; 3] = [0x1A, 0x00, 0x00];
+    const HDMI_FORUM_OUI: [u8; 3] = [0xD8, 0x5D, 0xC4];
+
+    fn parse(block: &[u8]) -> Self {
+        let mut cta = Self { revision: block.get(1).copied().unwrap_or(0), ..Default::default() };
+        let dtd_offset = block.get(2).map_or(0, |v| *v as usize);
+        let mut pos = 4;
+
+        while pos < dtd_offset.min(block.len()) {
+            let header = block[pos];
+            let tag = header >> 5;
+            let len = (header & 0x1F) as usize;
+            let Some(payload) = block.get(pos+1..pos+1+len) else { break };
+
+            match tag {
+                // Video Data Block
+                2 => cta.vics.extend(payload.iter().map(|svd| if *svd & 0x7F <= 64 { svd & 0x7F } else { *svd })),
+                // Vendor-Specific Data Block
+                3 if payload.get(..3) == Some(&Self::AMD_OUI) => {
+                    cta.amd_vrr_range = payload.get(5).zip(payload.get(6)).map(|(min, max)| (*min, *max));
+                },
+                // Extended tag
+                7 => match payload.first() {
+                    // HDR Static Metadata Data Block
+                    Some(0x06) => cta.hdr_static_metadata = HdrStaticMetadata::parse(&payload[1..]),
+                    // HDMI Forum Sink Capability Data Block
+                    Some(0x79) => cta.hdmi_forum = true,
+                    _ => {},
+                },
+                _ => {},
+            }
+
+            if tag == 3 && payload.get(..3) == Some(&Self::HDMI_FORUM_OUI) {
+                cta.hdmi_forum = true;
+            }
+
+            pos += 1 + len;
+        }
+
+        if dtd_offset >= 4 {
+            cta.detailed_timings = block
+                .get(dtd_offset..EDID_BLOCK_SIZE - 1)
+                .unwrap_or_default()
+                .chunks_exact(18)
+                .map_while(EdidTiming::from_dtd)
+                .collect();
+        }
+
+        cta
+    }
+}
+
+/// DisplayID extension block
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DisplayIdExtension {
+    /// e.g. 0x20 (v2.0), 0x12 (v1.2)
+    pub version: u8,
+    pub data_block_tags: Vec<u8>,
+    /// (min, max) Hz, from the Adaptive-Sync Data Block (v2.0)
+    pub adaptive_sync_range: Option<(u16, u16)>,
+}
+
+impl DisplayIdExtension {
+    fn parse(block: &[u8]) -> Self {
+        let mut displayid = Self { version: block.get(1).copied().unwrap_or(0), ..Default::default() };
+        let end = block.get(2).map_or(0, |len| 5 + *len as usize).min(EDID_BLOCK_SIZE - 1);
+        let mut pos = 5;
+
+        while pos + 3 <= end {
+            let tag = block[pos];
+            let len = block[pos+2] as usize;
+
+            if tag == 0 { break }
+
+            let Some(payload) = block.get(pos+3..pos+3+len) else { break };
+
+            displayid.data_block_tags.push(tag);
+
+            // Adaptive-Sync Data Block, 6-byte descriptors
+            if tag == 0x2B && displayid.adaptive_sync_range.is_none() && let Some(d) = payload.get(..6) {
+                let max = 1 + d[3] as u16 + ((d[4] as u16 & 0x3) << 8);
+
+                displayid.adaptive_sync_range = Some((d[2] as u16, max));
+            }
+
+            pos += 3 + len;
+        }
+
+        displayid
+    }
+
+    pub fn version_string(&self) -> String {
+        format!("{}.{}", self.version >> 4, self.version & 0xF)
+    }
+
+    pub fn data_block_name(tag: u8) -> &'static str {
+        match tag {
+            0x00 => "Product Identification",
+            0x01 => "Display Parameters",
+            0x03 => "Type I Timing",
+            0x0D => "Video Timing Range Limits",
+            0x20 => "Product Identification",
+            0x21 => "Display Parameters",
+            0x22 => "Type VII Timing",
+            0x25 => "Dynamic Video Timing Range Limits",
+            0x26 => "Display Interface Features",
+            0x27 => "Stereo Display Interface",
+            0x28 => "Tiled Display Topology",
+            0x29 => "ContainerID",
+            0x2B => "Adaptive-Sync",
+            0x7E => "Vendor-Specific",
+            0x81 => "CTA-861 DisplayID",
+            _ => "Unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edid {
+    /// PNP ID, e.g. "DEL"
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub serial_number: u32,
+    pub serial_string: Option<String>,
+    pub monitor_name: Option<String>,
+    /// `None` if not specified
+    pub week: Option<u8>,
+    pub year: u16,
+    /// `year` is the model year
+    pub model_year: bool,
+    pub version: (u8, u8),
+    /// cm, (0, 0) for projectors
+    pub size: (u8, u8),
+    pub range_limits: Option<EdidRangeLimits>,
+    pub established_timings: Vec<&'static str>,
+    pub standard_timings: Vec<EdidTiming>,
+    pub detailed_timings: Vec<EdidTiming>,
+    pub cta: Option<CtaExtension>,
+    pub displayid: Option<DisplayIdExtension>,
+}
+
+impl Edid {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let base = data.get(..EDID_BLOCK_SIZE)?;
+
+        if base[..8] != EDID_HEADER { return None }
+
+        let manufacturer = {
+            let id = u16::from_be_bytes([base[8], base[9]]);
+
+            [(id >> 10) & 0x1F, (id >> 5) & 0x1F, id & 0x1F]
+                .iter()
+                .map(|c| (b'A' - 1 + *c as u8) as char)
+                .collect()
+        };
+        let version = (base[18], base[19]);
+        let established_timings = {
+            let bits = u32::from_be_bytes([base[35], base[36], base[37], 0]);
+
+            ESTABLISHED_TIMINGS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| bits & (1 << (31 - i)) != 0)
+                .map(|(_, t)| *t)
+                .collect()
+        };
+        let standard_timings = base[38..54]
+            .chunks_exact(2)
+            .filter_map(|b| EdidTiming::from_standard_timing([b[0], b[1]], version))
+            .collect();
+
+        let mut edid = Self {
+            manufacturer,
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: u32::from_le_bytes([base[12], base[13], base[14], base[15]]),
+            serial_string: None,
+            monitor_name: None,
+            week: (base[16] != 0 && base[16] != 0xFF).then_some(base[16]),
+            year: base[17] as u16 + 1990,
+            model_year: base[16] == 0xFF,
+            version,
+            size: (base[21], base[22]),
+            range_limits: None,
+            established_timings,
+            standard_timings,
+            detailed_timings: Vec::new(),
+            cta: None,
+            displayid: None,
+        };
+
+        for d in base[54..126].chunks_exact(18) {
+            if let Some(timing) = EdidTiming::from_dtd(d) {
+                edid.detailed_timings.push(timing);
+                continue;
+            }
+
+            match d[3] {
+                0xFF => edid.serial_string = Some(Self::descriptor_string(d)),
+                0xFC => edid.monitor_name = Some(Self::descriptor_string(d)),
+                0xFD => edid.range_limits = EdidRangeLimits::from_descriptor(d),
+                _ => {},
+            }
+        }
+
+        for block in data[EDID_BLOCK_SIZE..].chunks_exact(EDID_BLOCK_SIZE) {
+            match block[0] {
+                CTA_EXTENSION_TAG if edid.cta.is_none() => edid.cta = Some(CtaExtension::parse(block)),
+                DISPLAYID_EXTENSION_TAG if edid.displayid.is_none() => {
+                    edid.displayid = Some(DisplayIdExtension::parse(block));
+                },
+                _ => {},
+            }
+        }
+
+        Some(edid)
+    }
+
+    fn descriptor_string(d: &[u8]) -> String {
+        let s = &d[5..18];
+        let end = s.iter().position(|c| *c == b'\n').unwrap_or(s.len());
+
+        String::from_utf8_lossy(&s[..end]).trim().to_string()
+    }
+
+    /// (min, max) Hz, from the AMD VSDB, the DisplayID Adaptive-Sync Data Block or the range limits
+    pub fn vrr_range(&self) -> Option<(u16, u16)> {
+        self.cta.as_ref()
+            .and_then(|cta| cta.amd_vrr_range)
+            .map(|(min, max)| (min as u16, max as u16))
+            .or_else(|| self.displayid.as_ref().and_then(|d| d.adaptive_sync_range))
+            .or_else(|| self.range_limits.map(|r| (r.min_vrate, r.max_vrate)))
+            .filter(|(min, max)| min < max)
+    }
+
+    /// inch
+    pub fn diagonal(&self) -> Option<f32> {
+        let (h, v) = (self.size.0 as f32, self.size.1 as f32);
+
+        (h != 0.0 && v != 0.0).then(|| (h * h + v * v).sqrt() / 2.54)
+    }
+
+    pub fn hdr_static_metadata(&self) -> Option<&HdrStaticMetadata> {
+        self.cta.as_ref().and_then(|cta| cta.hdr_static_metadata.as_ref())
+    }
+}
+
+#[test]
+fn test_edid() {
+    let mut base = [0u8; 128];
+    let mut cta = [0u8; 128];
+
+    base[..8].copy_from_slice(&EDID_HEADER);
+    // "DEL", 0xA0C2, serial
+    base[8..16].copy_from_slice(&[0x10, 0xAC, 0xC2, 0xA0, 0x4C, 0x50, 0x42, 0x30]);
+    // week 12, 2022, EDID 1.4
+    base[16..20].copy_from_slice(&[12, 32, 1, 4]);
+    // 60 x 34 cm
+    base[21] = 60;
+    base[22] = 34;
+    // 640x480@60, 800x600@60, 1024x768@60
+    base[35..38].copy_from_slice(&[0x21, 0x08, 0x00]);
+    base[38..54].copy_from_slice(&[0xD1, 0xC0, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01]);
+    // 2560x1440@59.95 (241.5 MHz)
+    base[54..72].copy_from_slice(&[0x56, 0x5E, 0x00, 0xA0, 0xA0, 0xA0, 0x29, 0x50, 0x30, 0x20, 0x35, 0x00, 0x55, 0x50, 0x21, 0x00, 0x00, 0x1A]);
+    // range limits: 48-144 Hz
+    base[72..90].copy_from_slice(&[0, 0, 0, 0xFD, 0, 48, 144, 30, 230, 60, 0, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    base[90..108].copy_from_slice(&[0, 0, 0, 0xFC, 0, b'D', b'E', b'L', b'L', b' ', b'S', b'2', b'7', b'2', b'1', b'D', b'G', b'F']);
+    base[108..126].copy_from_slice(&[0, 0, 0, 0xFF, 0, b'A', b'B', b'C', b'1', b'2', b'3', b'\n', 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    base[126] = 1;
+
+    cta[..4].copy_from_slice(&[CTA_EXTENSION_TAG, 3, 0, 0]);
+    let blocks: &[u8] = &[
+        // Video Data Block: VIC 16 (native), 4
+        0x42, 0x90, 0x04,
+        // HDR Static Metadata: SDR, ST2084, max 603 cd/m^2
+        0xE6, 0x06, 0x05, 0x01, 0x73, 0x73, 0x00,
+        // AMD VSDB: FreeSync 48-165 Hz
+        0x68, 0x1A, 0x00, 0x00, 0x01, 0x01, 48, 165,
+    ];
+    cta[4..4 + blocks.len()].copy_from_slice(blocks);
+    cta[2] = 4 + blocks.len() as u8;
+
+    let data = [base, cta].concat();
+    let edid = Edid::parse(&data).unwrap();
+
+    assert_eq!(edid.manufacturer, "DEL");
+    assert_eq!(edid.product_code, 0xA0C2);
+    assert_eq!(edid.week, Some(12));
+    assert_eq!(edid.year, 2022);
+    assert_eq!(edid.monitor_name.as_deref(), Some("DELL S2721DGF"));
+    assert_eq!(edid.serial_string.as_deref(), Some("ABC123"));
+    assert_eq!(edid.established_timings, vec!["640x480@60", "800x600@60", "1024x768@60"]);
+    assert_eq!(edid.standard_timings[0].to_string(), "1920x1080@60.00");
+    assert_eq!(edid.detailed_timings[0].hactive, 2560);
+    assert_eq!(edid.detailed_timings[0].vactive, 1440);
+    assert_eq!(edid.range_limits.unwrap().max_vrate, 144);
+
+    let cta = edid.cta.as_ref().unwrap();
+    let hdr = edid.hdr_static_metadata().unwrap();
+
+    assert_eq!(cta.vics, vec![16, 4]);
+    assert_eq!(hdr.eotf, vec!["SDR", "SMPTE ST2084"]);
+    assert_eq!(hdr.max_luminance.map(|v| v as u32), Some(603));
+    assert_eq!(edid.vrr_range(), Some((48, 165)));
+    assert!(Edid::parse(&data[1..]).is_none());
+}
@@ -0,0 +1,319 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::stat::parse_hwmon;
+use crate::write_sysfs;
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#fan-curve
+// ref: drivers/gpu/drm/amd/pm/amdgpu_pm.c
+
+fn parse_range<T: std::str::FromStr>(s: &str, suffix: &str) -> Option<[T; 2]> {
+    let (_name, range) = s.split_once(':')?;
+    let mut split = range.split_whitespace();
+    let min = split.next()?.trim_end_matches(suffix).parse().ok()?;
+    let max = split.next()?.trim_end_matches(suffix).parse().ok()?;
+
+    Some([min, max])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanPwmMode {
+    NoControl, // full speed
+    Manual,
+    Auto,
+}
+
+impl FanPwmMode {
+    fn from_pwm_enable(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(Self::NoControl),
+            1 => Some(Self::Manual),
+            2 => Some(Self::Auto),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FanPwmMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Single value files in `gpu_od/fan_ctrl/` (RDNA 3 and later)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanOdValue {
+    pub value: u32,
+    pub range: Option<[u32; 2]>,
+}
+
+impl FanOdValue {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut lines = s.lines();
+        let _header = lines.next()?;
+        let value = lines.next()?.trim().parse().ok()?;
+        let range = lines
+            .skip_while(|l| !l.starts_with("OD_RANGE"))
+            .nth(1)
+            .and_then(|l| parse_range(l, ""));
+
+        Some(Self { value, range })
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanCurvePoint {
+    pub temp: u32, // C
+    pub speed: u32, // %
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FanCurve {
+    pub points: Vec<FanCurvePoint>,
+    pub temp_range: Option<[u32; 2]>,
+    pub speed_range: Option<[u32; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FanCurveError {
+    NumberOfPoints { expected: usize, found: usize },
+    TempOutOfRange { index: usize, temp: u32, range: [u32; 2] },
+    SpeedOutOfRange { index: usize, speed: u32, range: [u32; 2] },
+    NoRange,
+}
+
+impl fmt::Display for FanCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NumberOfPoints { expected, found } =>
+                write!(f, "expected {expected} points, found {found} points"),
+            Self::TempOutOfRange { index, temp, range } =>
+                write!(f, "point {index}: {temp}C is out of range ({}C - {}C)", range[0], range[1]),
+            Self::SpeedOutOfRange { index, speed, range } =>
+                write!(f, "point {index}: {speed}% is out of range ({}% - {}%)", range[0], range[1]),
+            Self::NoRange => write!(f, "OD_RANGE is not reported"),
+        }
+    }
+}
+
+impl FanCurve {
+    /// parse `gpu_od/fan_ctrl/fan_curve`
+    /// ```text
+    /// OD_FAN_CURVE:
+    /// 0: 0C 0%
+    /// ...
+    /// OD_RANGE:
+    /// FAN_CURVE(hotspot temp): 25C 100C
+    /// FAN_CURVE(fan speed): 20% 100%
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut lines = s.lines();
+
+        if !lines.next()?.starts_with("OD_FAN_CURVE") {
+            return None;
+        }
+
+        let mut points = Vec::new();
+        let mut temp_range = None;
+        let mut speed_range = None;
+
+        for l in lines {
+            if l.starts_with("FAN_CURVE(hotspot temp)") {
+                temp_range = parse_range(l, "C");
+            } else if l.starts_with("FAN_CURVE(fan speed)") {
+                speed_range = parse_range(l, "%");
+            } else if let Some((_index, point)) = l.split_once(':')
+                && let Some((temp, speed)) = point.trim().split_once(' ')
+                && let Ok(temp) = temp.trim_end_matches('C').parse()
+                && let Ok(speed) = speed.trim().trim_end_matches('%').parse()
+            {
+                points.push(FanCurvePoint { temp, speed });
+            }
+        }
+
+        Some(Self { points, temp_range, speed_range })
+    }
+
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let s = fs::read_to_string(sysfs_path.into().join(FAN_CURVE_PATH)).ok()?;
+
+        Self::parse(&s)
+    }
+
+    /// parse "temp:speed,temp:speed,..."
+    pub fn points_from_str(s: &str) -> Option<Vec<FanCurvePoint>> {
+        s.split(',').map(|p| {
+            let (temp, speed) = p.trim().split_once(':')?;
+
+            Some(FanCurvePoint {
+                temp: temp.trim().parse().ok()?,
+                speed: speed.trim().parse().ok()?,
+            })
+        }).collect()
+    }
+
+    pub fn validate(&self, points: &[FanCurvePoint]) -> Result<(), FanCurveError> {
+        let (Some(temp_range), Some(speed_range)) = (self.temp_range, self.speed_range) else {
+            return Err(FanCurveError::NoRange);
+        };
+
+        if points.len() != self.points.len() {
+            return Err(FanCurveError::NumberOfPoints {
+                expected: self.points.len(),
+                found: points.len(),
+            });
+        }
+
+        for (index, p) in points.iter().enumerate() {
+            if !(temp_range[0]..=temp_range[1]).contains(&p.temp) {
+                return Err(FanCurveError::TempOutOfRange { index, temp: p.temp, range: temp_range });
+            }
+
+            if !(speed_range[0]..=speed_range[1]).contains(&p.speed) {
+                return Err(FanCurveError::SpeedOutOfRange { index, speed: p.speed, range: speed_range });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write each point and commit (`c`), requires root.
+    pub fn apply<P: Into<PathBuf>>(
+        &self,
+        sysfs_path: P,
+        points: &[FanCurvePoint],
+    ) -> io::Result<()> {
+        self.validate(points).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+
+        let path = sysfs_path.into().join(FAN_CURVE_PATH);
+
+        for (i, p) in points.iter().enumerate() {
+            write_sysfs(&path, &format!("{i} {} {}", p.temp, p.speed))?;
+        }
+
+        write_sysfs(&path, "c")
+    }
+
+    /// Restore the default fan curve (`r`), requires root.
+    pub fn reset<P: Into<PathBuf>>(sysfs_path: P) -> io::Result<()> {
+        let path = sysfs_path.into().join(FAN_CURVE_PATH);
+
+        write_sysfs(&path, "r")?;
+        write_sysfs(&path, "c")
+    }
+}
+
+const FAN_CTRL_PATH: &str = "gpu_od/fan_ctrl";
+const FAN_CURVE_PATH: &str = "gpu_od/fan_ctrl/fan_curve";
+
+#[derive(Debug, Clone)]
+pub struct FanInfo {
+    hwmon_path: PathBuf,
+    sysfs_path: PathBuf,
+    pub pwm_mode: Option<FanPwmMode>,
+    pub pwm: Option<u8>, // 0-255
+    pub target_rpm: Option<u32>,
+    pub min_rpm: Option<u32>,
+    pub zero_rpm_enable: Option<FanOdValue>,
+    pub zero_rpm_stop_temp: Option<FanOdValue>,
+    pub acoustic_limit_rpm: Option<FanOdValue>,
+    pub acoustic_target_rpm: Option<FanOdValue>,
+    pub fan_curve: Option<FanCurve>,
+}
+
+impl FanInfo {
+    pub fn new<P: Into<PathBuf>, Q: Into<PathBuf>>(hwmon_path: P, sysfs_path: Q) -> Option<Self> {
+        let hwmon_path = hwmon_path.into();
+        let sysfs_path = sysfs_path.into();
+
+        // APUs do not have a fan controlled by the GPU
+        if !hwmon_path.join("pwm1").exists() && !hwmon_path.join("fan1_input").exists() {
+            return None;
+        }
+
+        let mut s = Self {
+            hwmon_path,
+            sysfs_path,
+            pwm_mode: None,
+            pwm: None,
+            target_rpm: None,
+            min_rpm: None,
+            zero_rpm_enable: None,
+            zero_rpm_stop_temp: None,
+            acoustic_limit_rpm: None,
+            acoustic_target_rpm: None,
+            fan_curve: None,
+        };
+
+        s.update();
+        s.update_od();
+
+        Some(s)
+    }
+
+    pub fn update(&mut self) {
+        self.pwm_mode = parse_hwmon(self.hwmon_path.join("pwm1_enable"))
+            .and_then(FanPwmMode::from_pwm_enable);
+        self.pwm = parse_hwmon(self.hwmon_path.join("pwm1"));
+        self.target_rpm = parse_hwmon(self.hwmon_path.join("fan1_target"));
+        self.min_rpm = parse_hwmon(self.hwmon_path.join("fan1_min"));
+    }
+
+    pub fn update_od(&mut self) {
+        let fan_ctrl = self.sysfs_path.join(FAN_CTRL_PATH);
+
+        self.zero_rpm_enable = FanOdValue::from_path(fan_ctrl.join("fan_zero_rpm_enable"));
+        self.zero_rpm_stop_temp = FanOdValue::from_path(fan_ctrl.join("fan_zero_rpm_stop_temperature"));
+        self.acoustic_limit_rpm = FanOdValue::from_path(fan_ctrl.join("acoustic_limit_rpm_threshold"));
+        self.acoustic_target_rpm = FanOdValue::from_path(fan_ctrl.join("acoustic_target_rpm_threshold"));
+        self.fan_curve = FanCurve::from_sysfs(&self.sysfs_path);
+    }
+
+    /// clear all values read from hwmon and `gpu_od/fan_ctrl/`, call `update` and `update_od` to read them again
+    pub fn clear(&mut self) {
+        self.pwm_mode = None;
+        self.pwm = None;
+        self.target_rpm = None;
+        self.min_rpm = None;
+        self.zero_rpm_enable = None;
+        self.zero_rpm_stop_temp = None;
+        self.acoustic_limit_rpm = None;
+        self.acoustic_target_rpm = None;
+        self.fan_curve = None;
+    }
+
+    pub fn pwm_percent(&self) -> Option<u32> {
+        self.pwm.map(|pwm| pwm as u32 * 100 / 255)
+    }
+
+    /// The fan is stopped by the zero RPM feature
+    pub fn is_zero_rpm(&self, fan_rpm: Option<u32>) -> bool {
+        self.zero_rpm_enable.is_some_and(|z| z.value == 1) && fan_rpm == Some(0)
+    }
+
+    pub fn sysfs_path(&self) -> &Path {
+        &self.sysfs_path
+    }
+}
+
+#[test]
+fn test_fan_curve_parse() {
+    let s = std::fs::read_to_string("src/fan_curve_sample.txt").unwrap();
+    let fan_curve = FanCurve::parse(&s).unwrap();
+
+    assert_eq!(fan_curve.points.len(), 5);
+    assert_eq!(fan_curve.points[4], FanCurvePoint { temp: 90, speed: 100 });
+    assert_eq!(fan_curve.temp_range, Some([25, 100]));
+    assert_eq!(fan_curve.speed_range, Some([20, 100]));
+
+    let points = FanCurve::points_from_str("30:20, 45:30, 60:50, 75:70, 110:100").unwrap();
+    assert_eq!(
+        fan_curve.validate(&points),
+        Err(FanCurveError::TempOutOfRange { index: 4, temp: 110, range: [25, 100] }),
+    );
+}
@@ -0,0 +1,462 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::AMDGPU::GfxTargetVersion;
+use crate::KfdIoLink;
+
+// KFD topology nodes (HSA agents), as `rocminfo` reads them
+//
+//  * `nodes/<N>/properties`: compute units, wave slots, LDS, clocks, firmware versions, ...
+//  * `nodes/<N>/mem_banks/<M>/properties`: memory pools
+//  * `nodes/<N>/caches/<M>/properties`: cache hierarchy
+//  * `nodes/<N>/io_links/<M>/properties`, `p2p_links`: links to other nodes
+//
+// GPU nodes are mapped to the DRM render node by `drm_render_minor`.
+//
+// ref: drivers/gpu/drm/amd/amdkfd/kfd_topology.c
+// ref: drivers/gpu/drm/amd/amdkfd/kfd_topology.h
+// ref: https://github.com/ROCm/rocminfo
+
+pub(crate) const KFD_TOPOLOGY_NODES: &str = "/sys/class/kfd/kfd/topology/nodes/";
+
+// "name value\n"
+pub(crate) fn parse_properties(s: &str) -> impl Iterator<Item = (&str, u64)> {
+    s.lines().filter_map(|line| {
+        let (name, val) = line.split_once(' ')?;
+
+        Some((name, val.trim().parse().ok()?))
+    })
+}
+
+// "/dev/dri/renderD128" -> 128
+pub(crate) fn render_minor<P: AsRef<Path>>(render: P) -> Option<u64> {
+    render.as_ref().file_name()?.to_str()?.strip_prefix("renderD")?.parse().ok()
+}
+
+// read `<dir>/*/properties`, sorted by the index
+fn read_sub_properties(path: &Path) -> Vec<String> {
+    let Ok(dirs) = fs::read_dir(path) else { return Vec::new() };
+    let mut v: Vec<(u64, String)> = dirs.flatten().filter_map(|dir_entry| {
+        let index: u64 = dir_entry.file_name().to_str()?.parse().ok()?;
+        let s = fs::read_to_string(dir_entry.path().join("properties")).ok()?;
+
+        Some((index, s))
+    }).collect();
+
+    v.sort_by_key(|(index, _)| *index);
+
+    v.into_iter().map(|(_, s)| s).collect()
+}
+
+// HSA_CAP_*
+const CAPABILITY_FLAGS: &[(u64, &str)] = &[
+    (0x0000_0001, "HOT_PLUGGABLE"),
+    (0x0000_0002, "ATS_PRESENT"),
+    (0x0000_0004, "SHARED_WITH_GRAPHICS"),
+    (0x0000_0008, "QUEUE_SIZE_POW2"),
+    (0x0000_0010, "QUEUE_SIZE_32BIT"),
+    (0x0000_0020, "QUEUE_IDLE_EVENT"),
+    (0x0000_0040, "VA_LIMIT"),
+    (0x0000_0080, "WATCH_POINTS_SUPPORTED"),
+    (0x0000_4000, "AQL_QUEUE_DOUBLE_MAP"),
+    (0x0000_8000, "TRAP_DEBUG_SUPPORT"),
+    (0x0001_0000, "TRAP_DEBUG_WAVE_LAUNCH_TRAP_OVERRIDE_SUPPORTED"),
+    (0x0002_0000, "TRAP_DEBUG_WAVE_LAUNCH_MODE_SUPPORTED"),
+    (0x0004_0000, "TRAP_DEBUG_PRECISE_MEMORY_OPERATIONS_SUPPORTED"),
+    // 0x0008_0000: reserved (was SRAM_EDCSUPPORTED)
+    (0x0010_0000, "MEM_EDCSUPPORTED"),
+    (0x0020_0000, "RASEVENTNOTIFY"),
+    // 0x03C0_0000: ASIC_REVISION_MASK
+    (0x0400_0000, "SRAM_EDCSUPPORTED"),
+    (0x0800_0000, "SVMAPI_SUPPORTED"),
+    (0x1000_0000, "FLAGS_COHERENTHOSTACCESS"),
+    (0x2000_0000, "TRAP_DEBUG_FIRMWARE_SUPPORTED"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KfdMemBank {
+    pub heap_type: u64,
+    pub size_in_bytes: u64,
+    pub flags: u64,
+    pub width: u64,
+    /// MHz
+    pub mem_clk_max: u64,
+}
+
+impl KfdMemBank {
+    pub fn parse(s: &str) -> Self {
+        let mut bank = Self::default();
+
+        for (name, val) in parse_properties(s) {
+            match name {
+                "heap_type" => bank.heap_type = val,
+                "size_in_bytes" => bank.size_in_bytes = val,
+                "flags" => bank.flags = val,
+                "width" => bank.width = val,
+                "mem_clk_max" => bank.mem_clk_max = val,
+                _ => {},
+            }
+        }
+
+        bank
+    }
+
+    // HSA_MEM_HEAP_TYPE_*
+    pub fn heap_type_name(&self) -> &'static str {
+        match self.heap_type {
+            0 => "SYSTEM",
+            1 => "FB_PUBLIC",
+            2 => "FB_PRIVATE",
+            3 => "GPU_GDS",
+            4 => "GPU_LDS",
+            5 => "GPU_SCRATCH",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KfdCache {
+    pub processor_id_low: u64,
+    pub level: u64,
+    /// KiB
+    pub size: u64,
+    pub cache_line_size: u64,
+    pub cache_lines_per_tag: u64,
+    pub association: u64,
+    pub latency: u64,
+    pub cache_type: u64,
+}
+
+impl KfdCache {
+    pub fn parse(s: &str) -> Self {
+        let mut cache = Self::default();
+
+        for (name, val) in parse_properties(s) {
+            match name {
+                "processor_id_low" => cache.processor_id_low = val,
+                "level" => cache.level = val,
+                "size" => cache.size = val,
+                "cache_line_size" => cache.cache_line_size = val,
+                "cache_lines_per_tag" => cache.cache_lines_per_tag = val,
+                "association" => cache.association = val,
+                "latency" => cache.latency = val,
+                "type" => cache.cache_type = val,
+                _ => {},
+            }
+        }
+
+        cache
+    }
+
+    // HSA_CACHE_TYPE_*
+    pub fn type_names(&self) -> Vec<&'static str> {
+        [(0x1, "Data"), (0x2, "Instruction"), (0x4, "CPU"), (0x8, "HSACU")]
+            .iter()
+            .filter(|(bit, _)| self.cache_type & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KfdNode {
+    pub index: u64,
+    /// `nodes/<N>/name`
+    pub name: String,
+    /// `nodes/<N>/gpu_id`, used in `/sys/class/kfd/kfd/proc/<pid>/`, 0 for CPU nodes
+    pub gpu_id: u64,
+    pub cpu_cores_count: u64,
+    pub simd_count: u64,
+    pub mem_banks_count: u64,
+    pub caches_count: u64,
+    pub io_links_count: u64,
+    pub capability: u64,
+    pub max_waves_per_simd: u64,
+    pub lds_size_in_kb: u64,
+    pub gds_size_in_kb: u64,
+    pub num_gws: u64,
+    pub wave_front_size: u64,
+    pub array_count: u64,
+    pub simd_arrays_per_engine: u64,
+    pub cu_per_simd_array: u64,
+    pub simd_per_cu: u64,
+    pub max_slots_scratch_cu: u64,
+    pub gfx_target_version: u64,
+    pub vendor_id: u64,
+    pub device_id: u64,
+    pub location_id: u64,
+    pub domain: u64,
+    pub drm_render_minor: u64,
+    pub hive_id: u64,
+    pub num_sdma_engines: u64,
+    pub num_sdma_xgmi_engines: u64,
+    pub num_sdma_queues_per_engine: u64,
+    pub num_cp_queues: u64,
+    /// MHz
+    pub max_engine_clk_fcompute: u64,
+    /// MHz
+    pub max_engine_clk_ccompute: u64,
+    pub local_mem_size: u64,
+    pub fw_version: u64,
+    pub sdma_fw_version: u64,
+    pub unique_id: u64,
+    pub num_xcc: u64,
+    pub mem_banks: Vec<KfdMemBank>,
+    pub caches: Vec<KfdCache>,
+    /// `io_links` and `p2p_links`
+    pub io_links: Vec<KfdIoLink>,
+}
+
+impl KfdNode {
+    pub fn parse(index: u64, name: &str, s: &str) -> Self {
+        let mut node = Self { index, name: name.trim().to_string(), ..Default::default() };
+
+        for (name, val) in parse_properties(s) {
+            let field = match name {
+                "cpu_cores_count" => &mut node.cpu_cores_count,
+                "simd_count" => &mut node.simd_count,
+                "mem_banks_count" => &mut node.mem_banks_count,
+                "caches_count" => &mut node.caches_count,
+                "io_links_count" => &mut node.io_links_count,
+                "capability" => &mut node.capability,
+                "max_waves_per_simd" => &mut node.max_waves_per_simd,
+                "lds_size_in_kb" => &mut node.lds_size_in_kb,
+                "gds_size_in_kb" => &mut node.gds_size_in_kb,
+                "num_gws" => &mut node.num_gws,
+                "wave_front_size" => &mut node.wave_front_size,
+                "array_count" => &mut node.array_count,
+                "simd_arrays_per_engine" => &mut node.simd_arrays_per_engine,
+                "cu_per_simd_array" => &mut node.cu_per_simd_array,
+                "simd_per_cu" => &mut node.simd_per_cu,
+                "max_slots_scratch_cu" => &mut node.max_slots_scratch_cu,
+                "gfx_target_version" => &mut node.gfx_target_version,
+                "vendor_id" => &mut node.vendor_id,
+                "device_id" => &mut node.device_id,
+                "location_id" => &mut node.location_id,
+                "domain" => &mut node.domain,
+                "drm_render_minor" => &mut node.drm_render_minor,
+                "hive_id" => &mut node.hive_id,
+                "num_sdma_engines" => &mut node.num_sdma_engines,
+                "num_sdma_xgmi_engines" => &mut node.num_sdma_xgmi_engines,
+                "num_sdma_queues_per_engine" => &mut node.num_sdma_queues_per_engine,
+                "num_cp_queues" => &mut node.num_cp_queues,
+                "max_engine_clk_fcompute" => &mut node.max_engine_clk_fcompute,
+                "max_engine_clk_ccompute" => &mut node.max_engine_clk_ccompute,
+                "local_mem_size" => &mut node.local_mem_size,
+                "fw_version" => &mut node.fw_version,
+                "sdma_fw_version" => &mut node.sdma_fw_version,
+                "unique_id" => &mut node.unique_id,
+                "num_xcc" => &mut node.num_xcc,
+                _ => continue,
+            };
+
+            *field = val;
+        }
+
+        node
+    }
+
+    /// `with_sub_nodes`: read `mem_banks`, `caches`, `io_links` and `p2p_links`
+    fn from_sysfs(index: u64, path: &Path, with_sub_nodes: bool) -> Option<Self> {
+        let s = fs::read_to_string(path.join("properties")).ok()?;
+        let name = fs::read_to_string(path.join("name")).unwrap_or_default();
+        let mut node = Self::parse(index, &name, &s);
+
+        node.gpu_id = fs::read_to_string(path.join("gpu_id"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0);
+
+        if !with_sub_nodes {
+            return Some(node);
+        }
+
+        node.mem_banks = read_sub_properties(&path.join("mem_banks"))
+            .iter()
+            .map(|s| KfdMemBank::parse(s))
+            .collect();
+        node.caches = read_sub_properties(&path.join("caches"))
+            .iter()
+            .map(|s| KfdCache::parse(s))
+            .collect();
+        node.io_links = ["io_links", "p2p_links"]
+            .iter()
+            .flat_map(|links| read_sub_properties(&path.join(links)))
+            .filter_map(|s| KfdIoLink::parse(&s))
+            .collect();
+
+        Some(node)
+    }
+
+    pub fn is_gpu(&self) -> bool {
+        self.simd_count != 0
+    }
+
+    pub fn compute_units(&self) -> u64 {
+        if self.is_gpu() {
+            self.simd_count.checked_div(self.simd_per_cu).unwrap_or(0)
+        } else {
+            self.cpu_cores_count
+        }
+    }
+
+    pub fn max_waves_per_cu(&self) -> u64 {
+        self.max_waves_per_simd * self.simd_per_cu
+    }
+
+    pub fn gfx_target(&self) -> Option<GfxTargetVersion> {
+        (self.gfx_target_version != 0).then(|| GfxTargetVersion::from(self.gfx_target_version as u32))
+    }
+
+    /// "/dev/dri/renderD128", `None` for CPU nodes
+    pub fn render_path(&self) -> Option<PathBuf> {
+        (self.is_gpu() && self.drm_render_minor != 0)
+            .then(|| PathBuf::from(format!("/dev/dri/renderD{}", self.drm_render_minor)))
+    }
+
+    /// "0000:03:00.0", from `domain` and `location_id` (PCI_DEVID(bus, devfn))
+    pub fn pci_bus(&self) -> String {
+        format!(
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.domain,
+            self.location_id >> 8,
+            (self.location_id >> 3) & 0x1F,
+            self.location_id & 0x7,
+        )
+    }
+
+    pub fn capability_names(&self) -> Vec<&'static str> {
+        CAPABILITY_FLAGS
+            .iter()
+            .filter(|(bit, _)| self.capability & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// KiB, (level, total size)
+    pub fn cache_size_per_level(&self) -> Vec<(u64, u64)> {
+        let mut v: Vec<(u64, u64)> = Vec::new();
+
+        for cache in &self.caches {
+            if let Some((_, size)) = v.iter_mut().find(|(level, _)| *level == cache.level) {
+                *size += cache.size;
+            } else {
+                v.push((cache.level, cache.size));
+            }
+        }
+
+        v.sort_by_key(|(level, _)| *level);
+
+        v
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KfdTopology {
+    /// sorted by the node index
+    pub nodes: Vec<KfdNode>,
+}
+
+impl KfdTopology {
+    /// empty if the KFD is not available
+    pub fn new() -> Self {
+        Self::from_path(KFD_TOPOLOGY_NODES)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(nodes_path: P) -> Self {
+        Self::read_nodes(nodes_path, true)
+    }
+
+    /// Only `properties` and `gpu_id` of the nodes, without caches, mem_banks and io_links
+    pub fn properties_from_path<P: AsRef<Path>>(nodes_path: P) -> Self {
+        Self::read_nodes(nodes_path, false)
+    }
+
+    fn read_nodes<P: AsRef<Path>>(nodes_path: P, with_sub_nodes: bool) -> Self {
+        let Ok(dirs) = fs::read_dir(nodes_path) else { return Self::default() };
+        let mut nodes: Vec<KfdNode> = dirs.flatten().filter_map(|dir_entry| {
+            let index: u64 = dir_entry.file_name().to_str()?.parse().ok()?;
+
+            KfdNode::from_sysfs(index, &dir_entry.path(), with_sub_nodes)
+        }).collect();
+
+        nodes.sort_by_key(|node| node.index);
+
+        Self { nodes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn gpu_nodes(&self) -> impl Iterator<Item = &KfdNode> {
+        self.nodes.iter().filter(|node| node.is_gpu())
+    }
+
+    pub fn find_by_render<P: AsRef<Path>>(&self, render: P) -> Option<&KfdNode> {
+        let minor = render_minor(render)?;
+
+        self.gpu_nodes().find(|node| node.drm_render_minor == minor)
+    }
+
+    /// `gpu_id`s of the PCI device, to read the KFD process stats
+    pub fn gpu_ids(&self, pci: &str) -> Vec<u64> {
+        self.find_by_pci(pci).map(|node| node.gpu_id).filter(|id| *id != 0).collect()
+    }
+
+    /// GPU nodes of the PCI device, multiple nodes for the compute partitions
+    pub fn find_by_pci<'a>(&'a self, pci: &'a str) -> impl Iterator<Item = &'a KfdNode> {
+        self.gpu_nodes().filter(move |node| node.pci_bus() == pci)
+    }
+}
+
+#[test]
+fn test_kfd_topology() {
+    let root = std::env::temp_dir().join(format!("amdgpu_top_kfd_topology_{}", std::process::id()));
+    let write = |path: PathBuf, s: &str| {
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("properties"), s).unwrap();
+    };
+
+    write(root.join("0"), "cpu_cores_count 16\nsimd_count 0\nmem_banks_count 1\n");
+    write(
+        root.join("1"),
+        "cpu_cores_count 0\nsimd_count 192\nlds_size_in_kb 64\nwave_front_size 32\nsimd_per_cu 2\n\
+        max_waves_per_simd 16\ngfx_target_version 110000\nlocation_id 768\ndomain 0\n\
+        drm_render_minor 128\ncapability 671654016\nfw_version 2190\n",
+    );
+    fs::write(root.join("1/gpu_id"), "4386\n").unwrap();
+    write(root.join("1/mem_banks/0"), "heap_type 1\nsize_in_bytes 25753026560\nflags 0\nwidth 384\nmem_clk_max 1250\n");
+    write(root.join("1/caches/0"), "processor_id_low 1\nlevel 1\nsize 16\ncache_line_size 64\ntype 1\nsibling_map 1,0\n");
+    write(root.join("1/caches/1"), "processor_id_low 1\nlevel 2\nsize 3072\ncache_line_size 128\ntype 1\n");
+    write(root.join("1/caches/2"), "processor_id_low 2\nlevel 1\nsize 16\ncache_line_size 64\ntype 1\n");
+    write(root.join("1/io_links/0"), "type 2\nnode_from 1\nnode_to 0\nweight 20\n");
+
+    let topology = KfdTopology::from_path(&root);
+    let gpu = topology.find_by_render("/dev/dri/renderD128").unwrap();
+
+    assert_eq!(topology.nodes.len(), 2);
+    assert_eq!(topology.gpu_nodes().count(), 1);
+    assert_eq!(topology.nodes[0].compute_units(), 16);
+    assert_eq!(gpu.compute_units(), 96);
+    assert_eq!(gpu.max_waves_per_cu(), 32);
+    assert_eq!(gpu.pci_bus(), "0000:03:00.0");
+    assert_eq!(gpu.mem_banks[0].heap_type_name(), "FB_PUBLIC");
+    assert_eq!(gpu.cache_size_per_level(), vec![(1, 32), (2, 3072)]);
+    assert_eq!(gpu.io_links.len(), 1);
+    assert_eq!(
+        gpu.capability_names(),
+        ["WATCH_POINTS_SUPPORTED", "TRAP_DEBUG_SUPPORT", "SVMAPI_SUPPORTED", "TRAP_DEBUG_FIRMWARE_SUPPORTED"],
+    );
+    assert_eq!(topology.find_by_pci("0000:03:00.0").count(), 1);
+    assert_eq!(topology.gpu_ids("0000:03:00.0"), vec![4386]);
+    assert!(topology.find_by_render("/dev/dri/renderD129").is_none());
+
+    let properties = KfdTopology::properties_from_path(&root);
+    let gpu = properties.find_by_render("/dev/dri/renderD128").unwrap();
+
+    assert_eq!(gpu.gpu_id, 4386);
+    assert!(gpu.mem_banks.is_empty() && gpu.caches.is_empty() && gpu.io_links.is_empty());
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -25,9 +25,51 @@ pub use device_path::{DeviceType, DevicePath};
 mod drm_mode;
 pub use drm_mode::*;
 
+mod drm_mode_object;
+pub use drm_mode_object::*;
+
+mod drm_objects;
+pub use drm_objects::*;
+
+mod display;
+pub use display::*;
+
+mod edid;
+pub use edid::*;
+
 mod ppfeaturemask;
 pub use ppfeaturemask::*;
 
+mod pp_table;
+pub use pp_table::*;
+
+mod fan;
+pub use fan::*;
+
+mod power_ctrl;
+pub use power_ctrl::*;
+
+mod od;
+pub use od::*;
+
+mod ras;
+pub use ras::*;
+
+mod sampling;
+pub use sampling::*;
+
+mod link_health;
+pub use link_health::*;
+
+mod kfd_topology;
+pub use kfd_topology::*;
+
+mod topology;
+pub use topology::*;
+
+mod partition;
+pub use partition::*;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 pub enum GuiMode {
@@ -54,10 +96,15 @@ pub struct UiArgs {
     pub device_path_list: Vec<DevicePath>,
     pub update_process_index: u64,
     pub no_pc: bool,
+    pub pc_tables: Vec<stat::PCRegTable>,
+    pub power_state_log: Option<PathBuf>,
     pub is_dark_mode: Option<bool>, // TUI, GUI
     pub hide_fdinfo: bool, // TUI
     pub gui_wgpu_backend: GuiWgpuBackend, // GUI
     pub gui_mode: GuiMode, // GUI
+    pub restore_power_settings: bool, // TUI, GUI
+    pub allow_power_ctrl: bool, // TUI, GUI
+    pub sampling: SamplingConfig,
 }
 
 pub struct Sampling {
@@ -168,6 +215,15 @@ pub fn get_hw_ip_info_list(
     hw_ip_list
 }
 
+// for `/sys/class/drm/card*/device/` and hwmon files
+pub(crate) fn write_sysfs(path: &std::path::Path, s: &str) -> std::io::Result<()> {
+    std::fs::write(path, s).map_err(|e| if e.kind() == std::io::ErrorKind::PermissionDenied {
+        std::io::Error::new(e.kind(), format!("{}: root privileges are required", path.display()))
+    } else {
+        e
+    })
+}
+
 pub fn get_rocm_version() -> Option<String> {
     let rocm_path = std::env::var("ROCM_PATH").unwrap_or("/opt/rocm".to_string());
     let rocm_path = PathBuf::from(rocm_path);
@@ -0,0 +1,325 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::PCI;
+
+// PCIe link health of the GPU and each upstream bridge up to the root port.
+//
+//  * negotiated link: `current_link_{speed,width}`
+//  * capability: `max_link_{speed,width}`, the link is capable of the lower of both ends
+//  * AER: `aer_dev_{correctable,nonfatal,fatal}`, requires CONFIG_PCIEAER
+//
+// The link may be downclocked by ASPM/DPM while the GPU is idle,
+// so a downgraded link is only reported when it is checked under load.
+//
+// ref: https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-bus-pci
+// ref: https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-bus-pci-devices-aer_stats
+
+/// GFX activity (%) regarded as under load
+pub const LINK_CHECK_LOAD: u16 = 50;
+
+// "16.0 GT/s PCIe", "8.0 GT/s PCIe", "2.5 GT/s", "Unknown"
+fn parse_link_speed(s: &str) -> Option<u8> {
+    let gt_s = s.trim().split_once(' ')?.0;
+
+    let r#gen = match gt_s {
+        "2.5" => 1,
+        "5.0" | "5" => 2,
+        "8.0" | "8" => 3,
+        "16.0" | "16" => 4,
+        "32.0" | "32" => 5,
+        "64.0" | "64" => 6,
+        _ => return None,
+    };
+
+    Some(r#gen)
+}
+
+fn read_link<P: AsRef<Path>>(path: P, prefix: &str) -> Option<PCI::LINK> {
+    let path = path.as_ref();
+    let speed = fs::read_to_string(path.join(format!("{prefix}_link_speed"))).ok()?;
+    let width = fs::read_to_string(path.join(format!("{prefix}_link_width"))).ok()?;
+    let r#gen = parse_link_speed(&speed)?;
+    let width: u8 = width.trim().parse().ok().filter(|w| *w != 0)?;
+
+    Some(PCI::LINK { r#gen, width })
+}
+
+// "0000:03:00.0"
+fn is_pci_bdf(s: &str) -> bool {
+    let b = s.as_bytes();
+
+    b.len() == 12
+        && b[4] == b':'
+        && b[7] == b':'
+        && b[10] == b'.'
+        && b.iter().enumerate()
+            .filter(|(i, _)| ![4, 7, 10].contains(i))
+            .all(|(_, c)| c.is_ascii_hexdigit())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AerCounts {
+    pub correctable: Option<u64>,
+    pub nonfatal: Option<u64>,
+    pub fatal: Option<u64>,
+}
+
+impl AerCounts {
+    pub fn from_sysfs<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let [correctable, nonfatal, fatal] = ["correctable", "nonfatal", "fatal"].map(|name| {
+            let s = fs::read_to_string(path.join(format!("aer_dev_{name}"))).ok()?;
+
+            Self::parse_total(&s)
+        });
+
+        Self { correctable, nonfatal, fatal }
+    }
+
+    // "RxErr 0\nBadTLP 0\n...\nTOTAL_ERR_COR 0\n"
+    fn parse_total(s: &str) -> Option<u64> {
+        s.lines().find_map(|line| {
+            let (name, val) = line.trim().split_once(' ')?;
+
+            if name.starts_with("TOTAL_ERR_") {
+                val.trim().parse().ok()
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.correctable.is_some() || self.nonfatal.is_some() || self.fatal.is_some()
+    }
+
+    pub fn uncorrectable(&self) -> u64 {
+        self.nonfatal.unwrap_or(0) + self.fatal.unwrap_or(0)
+    }
+}
+
+impl fmt::Display for AerCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.is_available() {
+            return write!(f, "AER: N/A");
+        }
+
+        let [cor, nonfatal, fatal] = [self.correctable, self.nonfatal, self.fatal]
+            .map(|v| v.map_or("_".to_string(), |v| v.to_string()));
+
+        write!(f, "AER: Correctable {cor}, Non-Fatal {nonfatal}, Fatal {fatal}")
+    }
+}
+
+/// A PCI device on the path from the root port to the GPU
+#[derive(Debug, Clone)]
+pub struct PcieHop {
+    pub bdf: String,
+    pub sysfs_path: PathBuf,
+    pub current_link: Option<PCI::LINK>,
+    pub max_link: Option<PCI::LINK>,
+    pub aer: AerCounts,
+    start_aer: AerCounts,
+}
+
+impl PcieHop {
+    fn from_sysfs(bdf: &str, sysfs_path: PathBuf) -> Self {
+        let aer = AerCounts::from_sysfs(&sysfs_path);
+
+        Self {
+            bdf: bdf.to_string(),
+            current_link: read_link(&sysfs_path, "current"),
+            max_link: read_link(&sysfs_path, "max"),
+            sysfs_path,
+            aer,
+            start_aer: aer,
+        }
+    }
+
+    fn update(&mut self) {
+        self.current_link = read_link(&self.sysfs_path, "current");
+        self.aer = AerCounts::from_sysfs(&self.sysfs_path);
+    }
+
+    /// increase of correctable errors since the session started
+    pub fn correctable_delta(&self) -> u64 {
+        self.aer.correctable.unwrap_or(0).saturating_sub(self.start_aer.correctable.unwrap_or(0))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LinkIssue {
+    /// The link between the device and its upstream port is running below its capability
+    Downgraded { bdf: String, current: PCI::LINK, capable: PCI::LINK },
+    /// Non-fatal/fatal errors, or correctable errors increased during the session
+    AerErrors { bdf: String, aer: AerCounts, correctable_delta: u64 },
+}
+
+impl fmt::Display for LinkIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Downgraded { bdf, current, capable } => write!(
+                f,
+                "{bdf}: link is Gen{}x{}, capable of Gen{}x{}",
+                current.r#gen,
+                current.width,
+                capable.r#gen,
+                capable.width,
+            ),
+            Self::AerErrors { bdf, aer, correctable_delta } =>
+                write!(f, "{bdf}: {aer} (+{correctable_delta} correctable)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkHealth {
+    /// from the root port to the GPU
+    pub hops: Vec<PcieHop>,
+    pub issues: Vec<LinkIssue>,
+    /// A downgraded link is reported only if it is checked under load at least once
+    pub checked_under_load: bool,
+}
+
+impl LinkHealth {
+    /// `None` if the device is not on a PCIe bus (e.g. APU, or the path is not found)
+    pub fn new<P: AsRef<Path>>(sysfs_path: P) -> Option<Self> {
+        // "/sys/devices/pci0000:00/0000:00:01.1/0000:01:00.0/0000:02:00.0/0000:03:00.0"
+        let path = fs::canonicalize(sysfs_path).ok()?;
+        let mut hops = Vec::new();
+        let mut cur = PathBuf::new();
+
+        for comp in path.components() {
+            cur.push(comp);
+
+            let Some(name) = comp.as_os_str().to_str() else { continue };
+
+            if is_pci_bdf(name) {
+                hops.push(PcieHop::from_sysfs(name, cur.clone()));
+            }
+        }
+
+        // the GPU is directly attached to the root complex
+        if hops.len() < 2 {
+            return None;
+        }
+
+        let mut link_health = Self { hops, issues: Vec::new(), checked_under_load: false };
+        link_health.check(false);
+
+        Some(link_health)
+    }
+
+    pub fn update(&mut self, is_under_load: bool) {
+        for hop in self.hops.iter_mut() {
+            hop.update();
+        }
+
+        self.check(is_under_load);
+    }
+
+    fn check(&mut self, is_under_load: bool) {
+        let prev_downgraded: Vec<_> = self.issues
+            .iter()
+            .filter(|issue| matches!(issue, LinkIssue::Downgraded { .. }))
+            .cloned()
+            .collect();
+
+        self.issues.clear();
+
+        if is_under_load {
+            self.checked_under_load = true;
+            self.issues.extend(self.downgraded_links());
+        } else {
+            // keep the result of the last check under load
+            self.issues.extend(prev_downgraded);
+        }
+
+        for hop in &self.hops {
+            let correctable_delta = hop.correctable_delta();
+
+            if hop.aer.uncorrectable() != 0 || correctable_delta != 0 {
+                self.issues.push(LinkIssue::AerErrors {
+                    bdf: hop.bdf.clone(),
+                    aer: hop.aer,
+                    correctable_delta,
+                });
+            }
+        }
+    }
+
+    /// Links running below the capability, regardless of the load
+    pub fn downgraded_links(&self) -> Vec<LinkIssue> {
+        self.hops.windows(2).filter_map(|w| {
+            let [upstream, dev] = [&w[0], &w[1]];
+            let current = dev.current_link?;
+            let [up_max, dev_max] = [upstream.max_link?, dev.max_link?];
+            let capable = PCI::LINK {
+                r#gen: up_max.r#gen.min(dev_max.r#gen),
+                width: up_max.width.min(dev_max.width),
+            };
+
+            (current.r#gen < capable.r#gen || current.width < capable.width)
+                .then(|| LinkIssue::Downgraded { bdf: dev.bdf.clone(), current, capable })
+        }).collect()
+    }
+
+    pub fn has_warning(&self) -> bool {
+        !self.issues.is_empty()
+    }
+
+    pub fn gpu(&self) -> Option<&PcieHop> {
+        self.hops.last()
+    }
+}
+
+#[test]
+fn test_link_health() {
+    assert_eq!(parse_link_speed("16.0 GT/s PCIe\n"), Some(4));
+    assert_eq!(parse_link_speed("2.5 GT/s"), Some(1));
+    assert_eq!(parse_link_speed("Unknown"), None);
+
+    assert!(is_pci_bdf("0000:03:00.0"));
+    assert!(!is_pci_bdf("pci0000:00"));
+    assert!(!is_pci_bdf("0000:03:00.0:pcie002"));
+
+    let aer = "RxErr 2\nBadTLP 0\nBadDLLP 1\nTOTAL_ERR_COR 3\n";
+    assert_eq!(AerCounts::parse_total(aer), Some(3));
+    assert_eq!(AerCounts::parse_total("Undefined 0\n"), None);
+
+    let root = std::env::temp_dir().join(format!("amdgpu_top_link_health_{}", std::process::id()));
+    let port = root.join("pci0000:00").join("0000:00:01.1");
+    let gpu = port.join("0000:01:00.0");
+    let write = |path: &Path, name: &str, s: &str| fs::write(path.join(name), s).unwrap();
+
+    fs::create_dir_all(&gpu).unwrap();
+
+    for (path, speed, width) in [(&port, "16.0 GT/s PCIe", "16"), (&gpu, "2.5 GT/s PCIe", "4")] {
+        write(path, "max_link_speed", "16.0 GT/s PCIe\n");
+        write(path, "max_link_width", "16\n");
+        write(path, "current_link_speed", speed);
+        write(path, "current_link_width", width);
+    }
+
+    write(&gpu, "aer_dev_correctable", "RxErr 0\nTOTAL_ERR_COR 5\n");
+    write(&gpu, "aer_dev_nonfatal", "Undefined 0\nTOTAL_ERR_NONFATAL 0\n");
+    write(&gpu, "aer_dev_fatal", "Undefined 0\nTOTAL_ERR_FATAL 0\n");
+
+    let mut link_health = LinkHealth::new(&gpu).unwrap();
+    assert_eq!(link_health.hops.len(), 2);
+    assert_eq!(link_health.downgraded_links().len(), 1);
+    // idle, and no new AER errors
+    assert!(!link_health.has_warning());
+
+    write(&gpu, "aer_dev_correctable", "RxErr 2\nTOTAL_ERR_COR 7\n");
+    link_health.update(true);
+    assert_eq!(link_health.issues.len(), 2);
+    assert_eq!(link_health.gpu().unwrap().correctable_delta(), 2);
+
+    link_health.update(false);
+    assert!(link_health.checked_under_load);
+    assert_eq!(link_health.issues.len(), 2);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,547 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use crate::power_ctrl::is_writable_sysfs;
+use crate::write_sysfs;
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#pp-od-clk-voltage
+// ref: drivers/gpu/drm/amd/pm/swsmu/smu11/navi10_ppt.c
+// ref: drivers/gpu/drm/amd/pm/swsmu/smu13/smu_v13_0_0_ppt.c
+// ref: drivers/gpu/drm/amd/pm/swsmu/smu14/smu_v14_0_2_ppt.c
+
+const OD_CLK_VOLTAGE_PATH: &str = "pp_od_clk_voltage";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum OdUnit {
+    MHz,
+    MV,
+}
+
+impl OdUnit {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::MHz => "MHz",
+            Self::MV => "mV",
+        }
+    }
+}
+
+impl fmt::Display for OdUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// "800Mhz", "1000MHz", "724mV", "-450mv"
+fn parse_value(s: &str) -> Option<(i32, OdUnit)> {
+    let s = s.to_ascii_lowercase();
+
+    if let Some(v) = s.strip_suffix("mhz") {
+        Some((v.parse().ok()?, OdUnit::MHz))
+    } else if let Some(v) = s.strip_suffix("mv") {
+        Some((v.parse().ok()?, OdUnit::MV))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OdPoint {
+    pub index: usize,
+    pub mhz: Option<i32>,
+    pub mv: Option<i32>,
+}
+
+impl OdPoint {
+    // "0: 808Mhz", "0:        852Mhz        800mV", "0: 800MHz @ 711mV"
+    fn parse(line: &str) -> Option<Self> {
+        let (index, values) = line.split_once(':')?;
+        let index = index.trim().parse().ok()?;
+        let mut point = Self { index, mhz: None, mv: None };
+
+        for (val, unit) in values.split_whitespace().filter_map(parse_value) {
+            match unit {
+                OdUnit::MHz => point.mhz = Some(val),
+                OdUnit::MV => point.mv = Some(val),
+            }
+        }
+
+        Some(point)
+    }
+}
+
+/// `OD_RANGE` entry, e.g. `SCLK`, `MCLK`, `VDDC_CURVE_VOLT[0]`, `VDDGFX_OFFSET`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdRange {
+    pub name: String,
+    pub min: i32,
+    pub max: i32,
+    pub unit: OdUnit,
+}
+
+impl OdRange {
+    // "SCLK:     808Mhz       2200Mhz", "VDDGFX_OFFSET:    -450mv          0mv"
+    fn parse(line: &str) -> Option<Self> {
+        let (name, values) = line.split_once(':')?;
+        let mut split = values.split_whitespace().filter_map(parse_value);
+        let (min, unit) = split.next()?;
+        let (max, _) = split.next()?;
+
+        Some(Self { name: name.trim().to_string(), min, max, unit })
+    }
+
+    pub fn contains(&self, val: i32) -> bool {
+        (self.min..=self.max).contains(&val)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OdSection {
+    Sclk,
+    Mclk,
+    VddcCurve,
+    VddgfxOffset,
+    SclkOffset,
+    Range,
+    Unknown,
+}
+
+impl OdSection {
+    fn from_header(s: &str) -> Self {
+        match s {
+            "OD_SCLK:" => Self::Sclk,
+            "OD_MCLK:" => Self::Mclk,
+            "OD_VDDC_CURVE:" => Self::VddcCurve,
+            "OD_VDDGFX_OFFSET:" => Self::VddgfxOffset,
+            "OD_SCLK_OFFSET:" => Self::SclkOffset,
+            "OD_RANGE:" => Self::Range,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// `pp_od_clk_voltage`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OdClkVoltage {
+    /// min/max (GFX10 and later), or all levels with voltage (Vega10)
+    pub sclk: Vec<OdPoint>,
+    pub mclk: Vec<OdPoint>,
+    /// Vega20, Navi1x
+    pub vddc_curve: Vec<OdPoint>,
+    /// mV, Navi2x and later
+    pub vddgfx_offset: Option<i32>,
+    /// MHz, GFX12
+    pub sclk_offset: Option<i32>,
+    pub ranges: Vec<OdRange>,
+}
+
+impl OdClkVoltage {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut od = Self::default();
+        let mut section = OdSection::Unknown;
+
+        for line in s.lines() {
+            let line = line.trim();
+
+            if line.starts_with("OD_") && line.ends_with(':') {
+                section = OdSection::from_header(line);
+                continue;
+            }
+
+            match section {
+                OdSection::Sclk => od.sclk.extend(OdPoint::parse(line)),
+                OdSection::Mclk => od.mclk.extend(OdPoint::parse(line)),
+                OdSection::VddcCurve => od.vddc_curve.extend(OdPoint::parse(line)),
+                OdSection::VddgfxOffset => od.vddgfx_offset = parse_value(line).map(|(v, _)| v),
+                OdSection::SclkOffset => od.sclk_offset = parse_value(line).map(|(v, _)| v),
+                OdSection::Range => od.ranges.extend(OdRange::parse(line)),
+                OdSection::Unknown => {},
+            }
+        }
+
+        if od == Self::default() {
+            return None;
+        }
+
+        Some(od)
+    }
+
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let s = fs::read_to_string(sysfs_path.into().join(OD_CLK_VOLTAGE_PATH)).ok()?;
+
+        Self::parse(&s)
+    }
+
+    pub fn range(&self, name: &str) -> Option<&OdRange> {
+        self.ranges.iter().find(|r| r.name == name)
+    }
+
+    pub fn is_writable<P: Into<PathBuf>>(sysfs_path: P) -> bool {
+        is_writable_sysfs(sysfs_path.into().join(OD_CLK_VOLTAGE_PATH))
+    }
+
+    /// Restore the default OD settings (`r`) and commit (`c`), requires root.
+    pub fn reset<P: Into<PathBuf>>(sysfs_path: P) -> io::Result<()> {
+        let path = sysfs_path.into().join(OD_CLK_VOLTAGE_PATH);
+
+        write_sysfs(&path, "r")?;
+        write_sysfs(&path, "c")
+    }
+
+    fn check_range(&self, name: &str, val: i32) -> Result<(), OdError> {
+        // some sections have no range (e.g. OD_VDDGFX_OFFSET on Navi2x)
+        match self.range(name) {
+            Some(r) if !r.contains(val) => Err(OdError::OutOfRange {
+                name: name.to_string(),
+                value: val,
+                range: [r.min, r.max],
+                unit: r.unit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_index(
+        points: &[OdPoint],
+        section: &'static str,
+        index: usize,
+    ) -> Result<(), OdError> {
+        if points.is_empty() {
+            return Err(OdError::NotSupported(section));
+        }
+
+        if !points.iter().any(|p| p.index == index) {
+            return Err(OdError::InvalidIndex { section, index });
+        }
+
+        Ok(())
+    }
+
+    pub fn validate(&self, edit: &OdEdit) -> Result<(), OdError> {
+        match *edit {
+            OdEdit::Sclk { index, mhz, mv } | OdEdit::Mclk { index, mhz, mv } => {
+                let (points, section, name) = if matches!(edit, OdEdit::Sclk { .. }) {
+                    (&self.sclk, "OD_SCLK", "SCLK")
+                } else {
+                    (&self.mclk, "OD_MCLK", "MCLK")
+                };
+
+                Self::check_index(points, section, index)?;
+                self.check_range(name, mhz)?;
+
+                if let Some(mv) = mv {
+                    self.check_range("VDDC", mv)?;
+                }
+            },
+            OdEdit::VddcCurve { index, mhz, mv } => {
+                Self::check_index(&self.vddc_curve, "OD_VDDC_CURVE", index)?;
+                self.check_range(&format!("VDDC_CURVE_SCLK[{index}]"), mhz)?;
+                self.check_range(&format!("VDDC_CURVE_VOLT[{index}]"), mv)?;
+            },
+            OdEdit::VddgfxOffset(mv) => {
+                if self.vddgfx_offset.is_none() {
+                    return Err(OdError::NotSupported("OD_VDDGFX_OFFSET"));
+                }
+
+                self.check_range("VDDGFX_OFFSET", mv)?;
+            },
+            OdEdit::SclkOffset(mhz) => {
+                if self.sclk_offset.is_none() {
+                    return Err(OdError::NotSupported("OD_SCLK_OFFSET"));
+                }
+
+                self.check_range("SCLK_OFFSET", mhz)?;
+            },
+        }
+
+        Ok(())
+    }
+
+    fn apply_edit(&mut self, edit: &OdEdit) {
+        let set_point = |points: &mut [OdPoint], index: usize, mhz: i32, mv: Option<i32>| {
+            if let Some(p) = points.iter_mut().find(|p| p.index == index) {
+                p.mhz = Some(mhz);
+                p.mv = mv.or(p.mv);
+            }
+        };
+
+        match *edit {
+            OdEdit::Sclk { index, mhz, mv } => set_point(&mut self.sclk, index, mhz, mv),
+            OdEdit::Mclk { index, mhz, mv } => set_point(&mut self.mclk, index, mhz, mv),
+            OdEdit::VddcCurve { index, mhz, mv } => set_point(&mut self.vddc_curve, index, mhz, Some(mv)),
+            OdEdit::VddgfxOffset(mv) => self.vddgfx_offset = Some(mv),
+            OdEdit::SclkOffset(mhz) => self.sclk_offset = Some(mhz),
+        }
+    }
+
+    /// List the values changed from `self` to `other`
+    pub fn diff(&self, other: &Self) -> Vec<OdDiff> {
+        let mut diff = Vec::new();
+
+        for (section, cur, new) in [
+            ("OD_SCLK", &self.sclk, &other.sclk),
+            ("OD_MCLK", &self.mclk, &other.mclk),
+            ("OD_VDDC_CURVE", &self.vddc_curve, &other.vddc_curve),
+        ] {
+            for (c, n) in cur.iter().zip(new.iter()) {
+                for (from, to, unit) in [(c.mhz, n.mhz, OdUnit::MHz), (c.mv, n.mv, OdUnit::MV)] {
+                    let (Some(from), Some(to)) = (from, to) else { continue };
+
+                    if from != to {
+                        diff.push(OdDiff { name: format!("{section}[{}]", c.index), from, to, unit });
+                    }
+                }
+            }
+        }
+
+        for (section, from, to, unit) in [
+            ("OD_VDDGFX_OFFSET", self.vddgfx_offset, other.vddgfx_offset, OdUnit::MV),
+            ("OD_SCLK_OFFSET", self.sclk_offset, other.sclk_offset, OdUnit::MHz),
+        ] {
+            let (Some(from), Some(to)) = (from, to) else { continue };
+
+            if from != to {
+                diff.push(OdDiff { name: section.to_string(), from, to, unit });
+            }
+        }
+
+        diff
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdDiff {
+    pub name: String,
+    pub from: i32,
+    pub to: i32,
+    pub unit: OdUnit,
+}
+
+impl fmt::Display for OdDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} {unit} -> {} {unit}", self.name, self.from, self.to, unit = self.unit)
+    }
+}
+
+/// A single command for `pp_od_clk_voltage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OdEdit {
+    /// `s <index> <MHz> [<mV>]`, the voltage is required for Vega10
+    Sclk { index: usize, mhz: i32, mv: Option<i32> },
+    /// `m <index> <MHz> [<mV>]`
+    Mclk { index: usize, mhz: i32, mv: Option<i32> },
+    /// `vc <index> <MHz> <mV>`
+    VddcCurve { index: usize, mhz: i32, mv: i32 },
+    /// `vo <mV>`
+    VddgfxOffset(i32),
+    /// `s <MHz>` (GFX12)
+    SclkOffset(i32),
+}
+
+impl OdEdit {
+    fn is_same_target(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Sclk { index: a, .. }, Self::Sclk { index: b, .. }) |
+            (Self::Mclk { index: a, .. }, Self::Mclk { index: b, .. }) |
+            (Self::VddcCurve { index: a, .. }, Self::VddcCurve { index: b, .. }) => a == b,
+            (Self::VddgfxOffset(_), Self::VddgfxOffset(_)) |
+            (Self::SclkOffset(_), Self::SclkOffset(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for OdEdit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sclk { index, mhz, mv } | Self::Mclk { index, mhz, mv } => {
+                let cmd = if matches!(self, Self::Sclk { .. }) { "s" } else { "m" };
+
+                write!(f, "{cmd} {index} {mhz}")?;
+
+                if let Some(mv) = mv {
+                    write!(f, " {mv}")?;
+                }
+
+                Ok(())
+            },
+            Self::VddcCurve { index, mhz, mv } => write!(f, "vc {index} {mhz} {mv}"),
+            Self::VddgfxOffset(mv) => write!(f, "vo {mv}"),
+            Self::SclkOffset(mhz) => write!(f, "s {mhz}"),
+        }
+    }
+}
+
+impl FromStr for OdEdit {
+    type Err = ();
+
+    // "s 1 2600", "m 1 1250 900", "vc 2 1900 1100", "vo -50", "s 200"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split: Vec<&str> = s.split_whitespace().collect();
+        let nums: Vec<i32> = split.iter()
+            .skip(1)
+            .map(|v| v.parse().map_err(|_| ()))
+            .collect::<Result<_, _>>()?;
+        let index = |i: i32| usize::try_from(i).map_err(|_| ());
+
+        let edit = match (split.first().copied(), nums.as_slice()) {
+            (Some("s"), &[mhz]) => Self::SclkOffset(mhz),
+            (Some("s"), &[i, mhz]) => Self::Sclk { index: index(i)?, mhz, mv: None },
+            (Some("s"), &[i, mhz, mv]) => Self::Sclk { index: index(i)?, mhz, mv: Some(mv) },
+            (Some("m"), &[i, mhz]) => Self::Mclk { index: index(i)?, mhz, mv: None },
+            (Some("m"), &[i, mhz, mv]) => Self::Mclk { index: index(i)?, mhz, mv: Some(mv) },
+            (Some("vc"), &[i, mhz, mv]) => Self::VddcCurve { index: index(i)?, mhz, mv },
+            (Some("vo"), &[mv]) => Self::VddgfxOffset(mv),
+            _ => return Err(()),
+        };
+
+        Ok(edit)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OdError {
+    NotSupported(&'static str),
+    InvalidIndex { section: &'static str, index: usize },
+    OutOfRange { name: String, value: i32, range: [i32; 2], unit: OdUnit },
+}
+
+impl fmt::Display for OdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported(section) => write!(f, "{section} is not supported"),
+            Self::InvalidIndex { section, index } => write!(f, "{section}: invalid index {index}"),
+            Self::OutOfRange { name, value, range, unit } =>
+                write!(f, "{name}: {value} {unit} is out of range ({} - {} {unit})", range[0], range[1]),
+        }
+    }
+}
+
+/// Edits are validated and kept until commit,
+/// so the changes can be checked (dry-run) before writing them to `pp_od_clk_voltage`.
+#[derive(Debug, Clone)]
+pub struct OdStaging {
+    pub current: OdClkVoltage,
+    pub edits: Vec<OdEdit>,
+}
+
+impl OdStaging {
+    pub fn new(current: OdClkVoltage) -> Self {
+        Self { current, edits: Vec::new() }
+    }
+
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        OdClkVoltage::from_sysfs(sysfs_path).map(Self::new)
+    }
+
+    /// Replace the staged edit for the same target.
+    /// For Vega10, the voltage of the current level is used if it is not specified.
+    pub fn stage(&mut self, mut edit: OdEdit) -> Result<(), OdError> {
+        let point_mv = |points: &[OdPoint], index: usize| {
+            points.iter().find(|p| p.index == index).and_then(|p| p.mv)
+        };
+
+        match &mut edit {
+            OdEdit::Sclk { index, mv, .. } if mv.is_none() => *mv = point_mv(&self.current.sclk, *index),
+            OdEdit::Mclk { index, mv, .. } if mv.is_none() => *mv = point_mv(&self.current.mclk, *index),
+            _ => {},
+        }
+
+        self.current.validate(&edit)?;
+        self.edits.retain(|e| !e.is_same_target(&edit));
+        self.edits.push(edit);
+
+        Ok(())
+    }
+
+    pub fn staged(&self) -> OdClkVoltage {
+        let mut od = self.current.clone();
+
+        for edit in &self.edits {
+            od.apply_edit(edit);
+        }
+
+        od
+    }
+
+    pub fn diff(&self) -> Vec<OdDiff> {
+        self.current.diff(&self.staged())
+    }
+
+    pub fn discard(&mut self) {
+        self.edits.clear();
+    }
+
+    /// Write the staged edits and commit (`c`), requires root.
+    pub fn commit<P: Into<PathBuf>>(&mut self, sysfs_path: P) -> io::Result<()> {
+        let sysfs_path = sysfs_path.into();
+        let path = sysfs_path.join(OD_CLK_VOLTAGE_PATH);
+
+        for edit in &self.edits {
+            write_sysfs(&path, &edit.to_string())?;
+        }
+
+        write_sysfs(&path, "c")?;
+
+        self.edits.clear();
+
+        if let Some(od) = OdClkVoltage::from_sysfs(&sysfs_path) {
+            self.current = od;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_od_clk_voltage_parse() {
+    let read = |name: &str| {
+        let s = std::fs::read_to_string(format!("src/pp_od_clk_voltage_sample/{name}.txt")).unwrap();
+        OdClkVoltage::parse(&s).unwrap()
+    };
+
+    let vega10 = read("vega10");
+    assert_eq!(vega10.sclk.len(), 8);
+    assert_eq!(vega10.sclk[7], OdPoint { index: 7, mhz: Some(1630), mv: Some(1200) });
+    assert_eq!(vega10.range("VDDC").map(|r| [r.min, r.max]), Some([800, 1200]));
+
+    let vega20 = read("vega20");
+    assert_eq!(vega20.mclk, vec![OdPoint { index: 1, mhz: Some(1000), mv: None }]);
+    assert_eq!(vega20.vddc_curve.len(), 3);
+    assert_eq!(vega20.ranges.len(), 8);
+
+    let navi10 = read("navi10");
+    assert_eq!(navi10.vddc_curve[2], OdPoint { index: 2, mhz: Some(2100), mv: Some(1200) });
+
+    let navi21 = read("navi21");
+    assert_eq!(navi21.vddgfx_offset, Some(0));
+    assert!(navi21.range("VDDGFX_OFFSET").is_none());
+
+    let navi31 = read("navi31");
+    assert_eq!(navi31.range("VDDGFX_OFFSET").map(|r| [r.min, r.max]), Some([-450, 0]));
+
+    let navi48 = read("navi48");
+    assert!(navi48.sclk.is_empty());
+    assert_eq!(navi48.sclk_offset, Some(0));
+    assert_eq!(navi48.range("SCLK_OFFSET").map(|r| [r.min, r.max]), Some([-500, 1000]));
+}
+
+#[test]
+fn test_od_staging() {
+    let s = std::fs::read_to_string("src/pp_od_clk_voltage_sample/vega10.txt").unwrap();
+    let mut staging = OdStaging::new(OdClkVoltage::parse(&s).unwrap());
+
+    staging.stage("s 7 1650".parse().unwrap()).unwrap();
+    staging.stage("s 7 1700".parse().unwrap()).unwrap();
+    assert_eq!(staging.edits, vec![OdEdit::Sclk { index: 7, mhz: 1700, mv: Some(1200) }]);
+    assert_eq!(staging.edits[0].to_string(), "s 7 1700 1200");
+    assert_eq!(staging.diff()[0].to_string(), "OD_SCLK[7]: 1630 MHz -> 1700 MHz");
+
+    assert!(matches!(staging.stage("m 3 1600".parse().unwrap()), Err(OdError::OutOfRange { .. })));
+    assert_eq!(staging.stage("vo -50".parse().unwrap()), Err(OdError::NotSupported("OD_VDDGFX_OFFSET")));
+    assert_eq!("s 200".parse(), Ok(OdEdit::SclkOffset(200)));
+    assert_eq!("x 1 2".parse::<OdEdit>(), Err(()));
+}
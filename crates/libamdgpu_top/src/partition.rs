@@ -0,0 +1,181 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{KfdTopology, PCI};
+use crate::kfd_topology::KFD_TOPOLOGY_NODES;
+
+// Compute partition (SPX/DPX/TPX/QPX/CPX) and memory partition (NPS1/NPS2/NPS4/NPS8) of MI300 series
+//
+// Each compute partition (XCP) has its own render node and KFD topology node.
+// The render node of the first partition is the one of the PCI device,
+// other partitions are exposed as `amdgpu_xcp` platform devices.
+// The partitions are mapped from the KFD topology nodes with the same PCI location.
+//
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_xcp.c
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_gfx.c (`current_compute_partition`)
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_gmc.c (`current_memory_partition`)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputePartitionMode {
+    SPX,
+    DPX,
+    TPX,
+    QPX,
+    CPX,
+}
+
+impl ComputePartitionMode {
+    pub fn from_sysfs(s: &str) -> Option<Self> {
+        let mode = match s.trim() {
+            "SPX" => Self::SPX,
+            "DPX" => Self::DPX,
+            "TPX" => Self::TPX,
+            "QPX" => Self::QPX,
+            "CPX" => Self::CPX,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+}
+
+impl fmt::Display for ComputePartitionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryPartitionMode {
+    NPS1,
+    NPS2,
+    NPS4,
+    NPS8,
+}
+
+impl MemoryPartitionMode {
+    pub fn from_sysfs(s: &str) -> Option<Self> {
+        let mode = match s.trim() {
+            "NPS1" => Self::NPS1,
+            "NPS2" => Self::NPS2,
+            "NPS4" => Self::NPS4,
+            "NPS8" => Self::NPS8,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+}
+
+impl fmt::Display for MemoryPartitionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// "SPX, DPX, CPX", "NPS1, NPS4"
+fn parse_available<T, F: Fn(&str) -> Option<T>>(s: &str, f: F) -> Vec<T> {
+    s.split(',').filter_map(f).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputePartition {
+    pub index: usize,
+    pub render: PathBuf,
+    pub kfd_node: u64,
+    /// XCC instances of the partition
+    pub xcc: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionInfo {
+    pub compute_mode: ComputePartitionMode,
+    pub available_compute_modes: Vec<ComputePartitionMode>,
+    pub memory_mode: Option<MemoryPartitionMode>,
+    pub available_memory_modes: Vec<MemoryPartitionMode>,
+    /// empty if the KFD topology is not available
+    pub partitions: Vec<ComputePartition>,
+}
+
+impl PartitionInfo {
+    /// `None` if the GPU does not support the compute partition
+    pub fn new<P: AsRef<Path>>(sysfs_path: P, pci: PCI::BUS_INFO) -> Option<Self> {
+        Self::from_path(sysfs_path, KFD_TOPOLOGY_NODES, &pci.to_string())
+    }
+
+    fn from_path<P: AsRef<Path>, Q: AsRef<Path>>(sysfs_path: P, kfd_nodes_path: Q, pci: &str) -> Option<Self> {
+        let sysfs_path = sysfs_path.as_ref();
+        let read = |name: &str| fs::read_to_string(sysfs_path.join(name)).ok();
+        let compute_mode = ComputePartitionMode::from_sysfs(&read("current_compute_partition")?)?;
+
+        Some(Self {
+            compute_mode,
+            available_compute_modes: read("available_compute_partition")
+                .map(|s| parse_available(&s, ComputePartitionMode::from_sysfs))
+                .unwrap_or_default(),
+            memory_mode: read("current_memory_partition").and_then(|s| MemoryPartitionMode::from_sysfs(&s)),
+            available_memory_modes: read("available_memory_partition")
+                .map(|s| parse_available(&s, MemoryPartitionMode::from_sysfs))
+                .unwrap_or_default(),
+            partitions: Self::read_kfd_partitions(kfd_nodes_path, pci),
+        })
+    }
+
+    fn read_kfd_partitions<P: AsRef<Path>>(kfd_nodes_path: P, pci: &str) -> Vec<ComputePartition> {
+        let kfd = KfdTopology::properties_from_path(kfd_nodes_path);
+        let mut xcc_start = 0;
+
+        kfd.find_by_pci(pci)
+            .filter_map(|node| Some((node.index, node.render_path()?, node.num_xcc.max(1))))
+            .enumerate()
+            .map(|(i, (kfd_node, render, num_xcc))| {
+                let xcc = (xcc_start..xcc_start + num_xcc as u32).collect();
+                xcc_start += num_xcc as u32;
+
+                ComputePartition { index: i, render, kfd_node, xcc }
+            })
+            .collect()
+    }
+
+    /// Render nodes of the partitions other than the first one
+    pub fn secondary_render_paths(&self) -> Vec<PathBuf> {
+        self.partitions.iter().skip(1).map(|p| p.render.clone()).collect()
+    }
+}
+
+#[test]
+fn test_partition_info() {
+    let root = std::env::temp_dir().join(format!("amdgpu_top_partition_{}", std::process::id()));
+    let sysfs = root.join("device");
+    let nodes = root.join("nodes");
+    let node = |i: u32, s: &str| {
+        let path = nodes.join(i.to_string());
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("properties"), s).unwrap();
+    };
+
+    fs::create_dir_all(&sysfs).unwrap();
+    assert!(PartitionInfo::from_path(&sysfs, &nodes, "0000:03:00.0").is_none());
+
+    fs::write(sysfs.join("current_compute_partition"), "DPX\n").unwrap();
+    fs::write(sysfs.join("available_compute_partition"), "SPX, DPX, CPX\n").unwrap();
+    fs::write(sysfs.join("current_memory_partition"), "NPS1\n").unwrap();
+    // CPU
+    node(0, "cpu_cores_count 64\nsimd_count 0\ndrm_render_minor 0\nlocation_id 0\ndomain 0\n");
+    // location_id: 0x0300 (03:00.0)
+    node(1, "simd_count 608\nlocation_id 768\ndomain 0\ndrm_render_minor 128\nnum_xcc 4\n");
+    node(2, "simd_count 608\nlocation_id 768\ndomain 0\ndrm_render_minor 136\nnum_xcc 4\n");
+    // other GPU (04:00.0)
+    node(3, "simd_count 608\nlocation_id 1024\ndomain 0\ndrm_render_minor 129\nnum_xcc 8\n");
+
+    let info = PartitionInfo::from_path(&sysfs, &nodes, "0000:03:00.0").unwrap();
+
+    assert_eq!(info.compute_mode, ComputePartitionMode::DPX);
+    assert_eq!(info.available_compute_modes.len(), 3);
+    assert_eq!(info.memory_mode, Some(MemoryPartitionMode::NPS1));
+    assert_eq!(info.partitions.len(), 2);
+    assert_eq!(info.partitions[1].xcc, vec![4, 5, 6, 7]);
+    assert_eq!(info.secondary_render_paths(), vec![PathBuf::from("/dev/dri/renderD136")]);
+
+    let _ = fs::remove_dir_all(&root);
+}
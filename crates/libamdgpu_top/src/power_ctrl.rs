@@ -0,0 +1,324 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use libdrm_amdgpu_sys::AMDGPU::PowerCap;
+use crate::write_sysfs;
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#power-dpm-force-performance-level
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#pp-power-profile-mode
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#hwmon-interfaces
+
+const PERF_LEVEL_PATH: &str = "power_dpm_force_performance_level";
+const POWER_PROFILE_MODE_PATH: &str = "pp_power_profile_mode";
+
+/// Opening the file for writing does not change anything,
+/// so this can be used to check permissions before the actual write.
+pub fn is_writable_sysfs<P: AsRef<Path>>(path: P) -> bool {
+    fs::OpenOptions::new().write(true).open(path).is_ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfLevel {
+    Auto,
+    Low,
+    High,
+    Manual,
+    ProfileStandard,
+    ProfileMinSclk,
+    ProfileMinMclk,
+    ProfilePeak,
+    PerfDeterminism,
+}
+
+impl PerfLevel {
+    pub const ALL: [Self; 9] = [
+        Self::Auto,
+        Self::Low,
+        Self::High,
+        Self::Manual,
+        Self::ProfileStandard,
+        Self::ProfileMinSclk,
+        Self::ProfileMinMclk,
+        Self::ProfilePeak,
+        Self::PerfDeterminism,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Low => "low",
+            Self::High => "high",
+            Self::Manual => "manual",
+            Self::ProfileStandard => "profile_standard",
+            Self::ProfileMinSclk => "profile_min_sclk",
+            Self::ProfileMinMclk => "profile_min_mclk",
+            Self::ProfilePeak => "profile_peak",
+            Self::PerfDeterminism => "perf_determinism",
+        }
+    }
+
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        fs::read_to_string(sysfs_path.into().join(PERF_LEVEL_PATH)).ok()?.trim().parse().ok()
+    }
+
+    /// requires root
+    pub fn set<P: Into<PathBuf>>(&self, sysfs_path: P) -> io::Result<()> {
+        write_sysfs(&sysfs_path.into().join(PERF_LEVEL_PATH), self.as_str())
+    }
+
+    pub fn is_writable<P: Into<PathBuf>>(sysfs_path: P) -> bool {
+        is_writable_sysfs(sysfs_path.into().join(PERF_LEVEL_PATH))
+    }
+
+    /// `perf_determinism` is only supported on some server GPUs, skip it
+    pub fn next(&self) -> Self {
+        let levels = &Self::ALL[..Self::ALL.len() - 1];
+        let pos = levels.iter().position(|l| l == self).map_or(0, |pos| pos + 1);
+
+        levels[pos % levels.len()]
+    }
+}
+
+impl FromStr for PerfLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|l| l.as_str() == s).ok_or(())
+    }
+}
+
+impl fmt::Display for PerfLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerProfileMode {
+    pub index: usize,
+    pub name: String,
+    pub is_active: bool,
+}
+
+/// `pp_power_profile_mode`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerProfileTable {
+    pub modes: Vec<PowerProfileMode>,
+    /// The format of the heuristic parameters (custom profile) differs for each SMU version,
+    /// so keep the table as is.
+    pub table: String,
+}
+
+impl PowerProfileTable {
+    pub fn parse(s: &str) -> Option<Self> {
+        let first_line = s.lines().next()?;
+
+        // SMU v13.0.0/v13.0.7/v14.0.x print the profile names in the header line
+        // ref: drivers/gpu/drm/amd/pm/swsmu/smu13/smu_v13_0_0_ppt.c
+        let modes = if first_line.contains("BOOTUP_DEFAULT") {
+            Self::parse_header_line(first_line)
+        } else {
+            s.lines().filter_map(Self::parse_mode_line).collect()
+        };
+
+        if modes.is_empty() {
+            return None;
+        }
+
+        Some(Self { modes, table: s.trim_end().to_string() })
+    }
+
+    fn parse_header_line(line: &str) -> Vec<PowerProfileMode> {
+        let mut modes: Vec<PowerProfileMode> = Vec::new();
+
+        for name in line.split_whitespace() {
+            if name == "*" {
+                if let Some(last) = modes.last_mut() {
+                    last.is_active = true;
+                }
+                continue;
+            }
+
+            modes.push(PowerProfileMode {
+                index: modes.len(),
+                name: name.trim_end_matches('*').to_string(),
+                is_active: name.ends_with('*'),
+            });
+        }
+
+        modes
+    }
+
+    // " 1 3D_FULL_SCREEN*:", "  1 3D_FULL_SCREEN *:   0  100 ...", " 2 POWER_SAVING*"
+    fn parse_mode_line(line: &str) -> Option<PowerProfileMode> {
+        let (index, rest) = line.trim_start().split_once(char::is_whitespace)?;
+        let index = index.parse().ok()?;
+        let name = rest.split(':').next()?;
+        let is_active = name.contains('*');
+        let name = name.trim().trim_end_matches('*').trim_end().to_string();
+
+        if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        Some(PowerProfileMode { index, name, is_active })
+    }
+
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let s = fs::read_to_string(sysfs_path.into().join(POWER_PROFILE_MODE_PATH)).ok()?;
+
+        Self::parse(&s)
+    }
+
+    pub fn current(&self) -> Option<&PowerProfileMode> {
+        self.modes.iter().find(|m| m.is_active)
+    }
+
+    /// The CUSTOM profile requires the heuristic parameters, skip it
+    pub fn next_index(&self) -> Option<usize> {
+        let cur = self.modes.iter().position(|m| m.is_active).unwrap_or(0);
+
+        (1..=self.modes.len())
+            .map(|i| &self.modes[(cur + i) % self.modes.len()])
+            .find(|m| m.name != "CUSTOM")
+            .map(|m| m.index)
+    }
+
+    /// Write the profile index, requires root.
+    /// Some SMU versions need `manual` for `power_dpm_force_performance_level`.
+    pub fn set<P: Into<PathBuf>>(sysfs_path: P, index: usize) -> io::Result<()> {
+        write_sysfs(&sysfs_path.into().join(POWER_PROFILE_MODE_PATH), &index.to_string())
+    }
+
+    pub fn is_writable<P: Into<PathBuf>>(sysfs_path: P) -> bool {
+        is_writable_sysfs(sysfs_path.into().join(POWER_PROFILE_MODE_PATH))
+    }
+}
+
+/// Change of `power1_cap` in hwmon, values are in watts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerCapChange {
+    pub requested: u32,
+    pub from: u32,
+    pub to: u32,
+}
+
+impl PowerCapChange {
+    const POWER_CAP_PATH: &str = "power1_cap";
+
+    /// The requested value is clamped to the min/max reported by the driver
+    pub fn new(cap: &PowerCap, requested: u32) -> Self {
+        Self {
+            requested,
+            from: cap.current,
+            to: requested.max(cap.min).min(cap.max),
+        }
+    }
+
+    pub fn reset_to_default(cap: &PowerCap) -> Self {
+        Self::new(cap, cap.default)
+    }
+
+    pub fn is_clamped(&self) -> bool {
+        self.requested != self.to
+    }
+
+    /// requires root
+    pub fn apply<P: Into<PathBuf>>(&self, hwmon_path: P) -> io::Result<()> {
+        let uw = self.to as u64 * 1_000_000;
+
+        write_sysfs(&hwmon_path.into().join(Self::POWER_CAP_PATH), &uw.to_string())
+    }
+
+    pub fn is_writable<P: Into<PathBuf>>(hwmon_path: P) -> bool {
+        is_writable_sysfs(hwmon_path.into().join(Self::POWER_CAP_PATH))
+    }
+}
+
+impl fmt::Display for PowerCapChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "power_cap: {} W -> {} W", self.from, self.to)?;
+
+        if self.is_clamped() {
+            write!(f, " (clamped from {} W)", self.requested)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The power settings at startup, to restore them on exit
+#[derive(Debug, Clone)]
+pub struct PowerSettingsBackup {
+    pub sysfs_path: PathBuf,
+    pub perf_level: Option<PerfLevel>,
+    pub power_profile_index: Option<usize>,
+}
+
+impl PowerSettingsBackup {
+    pub fn from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Self {
+        let sysfs_path = sysfs_path.into();
+        let perf_level = PerfLevel::from_sysfs(&sysfs_path);
+        let power_profile_index = PowerProfileTable::from_sysfs(&sysfs_path)
+            .and_then(|t| t.current().map(|m| m.index));
+
+        Self { sysfs_path, perf_level, power_profile_index }
+    }
+
+    pub fn restore(&self) -> io::Result<()> {
+        if let Some(perf_level) = self.perf_level
+            && PerfLevel::from_sysfs(&self.sysfs_path) != Some(perf_level)
+        {
+            perf_level.set(&self.sysfs_path)?;
+        }
+
+        if let Some(index) = self.power_profile_index {
+            let current = PowerProfileTable::from_sysfs(&self.sysfs_path)
+                .and_then(|t| t.current().map(|m| m.index));
+
+            if current != Some(index) {
+                PowerProfileTable::set(&self.sysfs_path, index)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_power_profile_table_parse() {
+    let s = std::fs::read_to_string("src/pp_power_profile_mode_sample.txt").unwrap();
+    let table = PowerProfileTable::parse(&s).unwrap();
+
+    assert_eq!(table.modes.len(), 7);
+    assert_eq!(table.current().map(|m| m.name.as_str()), Some("3D_FULL_SCREEN"));
+    assert_eq!(table.modes[6].name, "CUSTOM");
+    assert_eq!(table.next_index(), Some(2));
+
+    let header = "                              BOOTUP_DEFAULT  3D_FULL_SCREEN  POWER_SAVING    VIDEO         * VR            COMPUTE         CUSTOM          WINDOW_3D      \n";
+    let table = PowerProfileTable::parse(header).unwrap();
+
+    assert_eq!(table.modes.len(), 8);
+    assert_eq!(table.current().map(|m| m.index), Some(3));
+
+    // Renoir, the profiles without the workload type are skipped
+    let renoir = "PROFILE_INDEX(NAME)\n 1 3D_FULL_SCREEN \n 3          VIDEO \n 4             VR \n 5        COMPUTE*\n 6         CUSTOM \n";
+    let table = PowerProfileTable::parse(renoir).unwrap();
+
+    assert_eq!(table.modes.iter().map(|m| m.index).collect::<Vec<_>>(), vec![1, 3, 4, 5, 6]);
+    assert_eq!(table.current().map(|m| m.name.as_str()), Some("COMPUTE"));
+    assert_eq!(table.next_index(), Some(1));
+
+    let table = PowerProfileTable::parse(&renoir.replace("COMPUTE*", "COMPUTE ").replace("VIDEO ", "VIDEO*")).unwrap();
+
+    assert_eq!(table.next_index(), Some(4));
+}
+
+#[test]
+fn test_perf_level_next() {
+    assert_eq!(PerfLevel::Auto.next(), PerfLevel::Low);
+    assert_eq!(PerfLevel::ProfilePeak.next(), PerfLevel::Auto);
+    assert_eq!(PerfLevel::PerfDeterminism.next(), PerfLevel::Auto);
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::AMDGPU::RasErrorCount;
+
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_ras.c
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_ras_eeprom.c
+
+const RAS_DIR: &str = "ras";
+const ERR_COUNT_SUFFIX: &str = "_err_count";
+
+// "feature mask: 0x3fbf", "table version: 0x20000", "schema: 0xf"
+fn parse_hex_line(s: &str) -> Option<u64> {
+    let (_name, val) = s.trim().split_once(':')?;
+    let val = val.trim();
+
+    u64::from_str_radix(val.strip_prefix("0x").unwrap_or(val), 16).ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RasBlockErrorCount {
+    pub block: String,
+    /// uncorrectable
+    pub ue: u64,
+    /// correctable
+    pub ce: u64,
+    /// deferred, since Linux 6.10
+    pub de: Option<u64>,
+    base_ue: u64,
+    base_ce: u64,
+}
+
+impl RasBlockErrorCount {
+    // "ue: 0\nce: 1\n", "ue: 0\nce: 1\nde: 0\n"
+    fn parse(block: &str, s: &str) -> Option<Self> {
+        let [mut ue, mut ce, mut de] = [None; 3];
+
+        for line in s.lines() {
+            let Some((name, val)) = line.split_once(':') else { continue };
+            let val: u64 = val.trim().parse().ok()?;
+
+            match name.trim() {
+                "ue" => ue = Some(val),
+                "ce" => ce = Some(val),
+                "de" => de = Some(val),
+                _ => {},
+            }
+        }
+
+        let [ue, ce] = [ue?, ce?];
+
+        Some(Self { block: block.to_string(), ue, ce, de, base_ue: ue, base_ce: ce })
+    }
+
+    /// increase since the session started
+    pub fn ue_delta(&self) -> u64 {
+        self.ue.saturating_sub(self.base_ue)
+    }
+
+    /// increase since the session started
+    pub fn ce_delta(&self) -> u64 {
+        self.ce.saturating_sub(self.base_ce)
+    }
+
+    fn update(&mut self, s: &str) {
+        let Some(new) = Self::parse(&self.block, s) else { return };
+
+        self.ue = new.ue;
+        self.ce = new.ce;
+        self.de = new.de;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadPageStatus {
+    Reserved,
+    Pending,
+    Failed,
+}
+
+impl BadPageStatus {
+    fn from_flag(s: &str) -> Option<Self> {
+        match s {
+            "R" => Some(Self::Reserved),
+            "P" => Some(Self::Pending),
+            "F" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BadPageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Retired page of VRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadPage {
+    /// page frame number
+    pub pfn: u64,
+    pub size: u64,
+    pub status: BadPageStatus,
+}
+
+impl BadPage {
+    // "0x00012345 : 0x00001000 : R"
+    fn parse(line: &str) -> Option<Self> {
+        let mut split = line.split(':').map(|s| s.trim());
+        let pfn = u64::from_str_radix(split.next()?.strip_prefix("0x")?, 16).ok()?;
+        let size = u64::from_str_radix(split.next()?.strip_prefix("0x")?, 16).ok()?;
+        let status = BadPageStatus::from_flag(split.next()?)?;
+
+        Some(Self { pfn, size, status })
+    }
+
+    pub fn address(&self) -> u64 {
+        self.pfn << 12
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RasEepromInfo {
+    pub version: Option<u64>,
+    pub schema: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RasInfo {
+    sysfs_path: PathBuf,
+    pub features: Option<u64>,
+    pub blocks: Vec<RasBlockErrorCount>,
+    pub bad_pages: Vec<BadPage>,
+    pub eeprom: RasEepromInfo,
+}
+
+impl RasInfo {
+    pub fn new<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let sysfs_path = sysfs_path.into();
+        let ras_dir = sysfs_path.join(RAS_DIR);
+        let features = fs::read_to_string(ras_dir.join("features")).ok()
+            .and_then(|s| parse_hex_line(&s));
+
+        let blocks = Self::read_blocks(&ras_dir)?;
+
+        if features.is_none() && blocks.is_empty() {
+            return None;
+        }
+
+        let eeprom = RasEepromInfo {
+            version: fs::read_to_string(ras_dir.join("version")).ok().and_then(|s| parse_hex_line(&s)),
+            schema: fs::read_to_string(ras_dir.join("schema")).ok().and_then(|s| parse_hex_line(&s)),
+        };
+        let bad_pages = Self::read_bad_pages(&ras_dir);
+
+        Some(Self { sysfs_path, features, blocks, bad_pages, eeprom })
+    }
+
+    fn read_blocks(ras_dir: &Path) -> Option<Vec<RasBlockErrorCount>> {
+        let mut blocks: Vec<_> = fs::read_dir(ras_dir).ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                let block = name.strip_suffix(ERR_COUNT_SUFFIX)?;
+                let s = fs::read_to_string(entry.path()).ok()?;
+
+                RasBlockErrorCount::parse(block, &s)
+            })
+            .collect();
+
+        blocks.sort_by(|a, b| a.block.cmp(&b.block));
+
+        Some(blocks)
+    }
+
+    fn read_bad_pages(ras_dir: &Path) -> Vec<BadPage> {
+        let Ok(s) = fs::read_to_string(ras_dir.join("gpu_vram_bad_pages")) else { return Vec::new() };
+
+        s.lines().filter_map(BadPage::parse).collect()
+    }
+
+    pub fn block(&self, name: &str) -> Option<&RasBlockErrorCount> {
+        self.blocks.iter().find(|b| b.block == name)
+    }
+
+    /// ECC error count of VRAM, `ras/umc_err_count`
+    pub fn umc_error_count(&self) -> Option<RasErrorCount> {
+        self.block("umc").map(|b| RasErrorCount { uncorrected: b.ue, corrected: b.ce })
+    }
+
+    pub fn total_ue(&self) -> u64 {
+        self.blocks.iter().map(|b| b.ue).sum()
+    }
+
+    pub fn total_ce(&self) -> u64 {
+        self.blocks.iter().map(|b| b.ce).sum()
+    }
+
+    /// The blocks are enumerated again if reading any of them fails
+    /// (e.g. a RAS block is disabled or enabled after a GPU reset),
+    /// the error counts of the session start are kept for the remaining blocks.
+    pub fn update(&mut self) {
+        let ras_dir = self.sysfs_path.join(RAS_DIR);
+        let mut rescan = false;
+
+        for b in self.blocks.iter_mut() {
+            let path = ras_dir.join(format!("{}{ERR_COUNT_SUFFIX}", b.block));
+
+            if let Ok(s) = fs::read_to_string(path) {
+                b.update(&s);
+            } else {
+                rescan = true;
+            }
+        }
+
+        if rescan {
+            let mut blocks = Self::read_blocks(&ras_dir).unwrap_or_default();
+
+            for b in blocks.iter_mut() {
+                if let Some(old) = self.block(&b.block) {
+                    b.base_ue = old.base_ue;
+                    b.base_ce = old.base_ce;
+                }
+            }
+
+            self.blocks = blocks;
+        }
+
+        self.bad_pages = Self::read_bad_pages(&ras_dir);
+    }
+}
+
+#[test]
+fn test_ras_info() {
+    let mut ras = RasInfo::new("src/ras_sample/").unwrap();
+
+    assert_eq!(ras.features, Some(0x3fbf));
+    assert_eq!(ras.blocks.iter().map(|b| b.block.as_str()).collect::<Vec<_>>(), ["sdma", "umc"]);
+    assert_eq!(ras.block("sdma").unwrap().de, Some(3));
+    assert_eq!(ras.total_ue(), 2);
+    assert_eq!(ras.total_ce(), 16);
+    assert_eq!(ras.umc_error_count().map(|e| (e.uncorrected, e.corrected)), Some((2, 15)));
+    assert_eq!(ras.bad_pages[1], BadPage { pfn: 0x54321, size: 0x1000, status: BadPageStatus::Pending });
+    assert_eq!(ras.eeprom, RasEepromInfo { version: Some(0x20000), schema: Some(0xf) });
+
+    ras.blocks[1].update("ue: 3\nce: 20\n");
+    assert_eq!(ras.blocks[1].ue_delta(), 1);
+    assert_eq!(ras.blocks[1].ce_delta(), 5);
+}
@@ -0,0 +1,386 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::Sampling;
+
+// Each source of the statistics is updated with its own interval.
+// The frontends run a single loop at the shortest interval (tick),
+// and `AppAmdgpuTop::update` updates only the sources that are due.
+// An interval that is not a multiple of the tick is effectively rounded up to the next tick.
+//
+// Reading GRBM wakes APUs from GFXOFF, so the perf counters are the most expensive source,
+// while fdinfo and gpu_metrics are cheap.
+
+pub const MIN_INTERVAL: Duration = Duration::from_millis(10);
+pub const MAX_PC_COUNT: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleSource {
+    /// GRBM, GRBM2 and other status registers
+    PerfCounter,
+    /// hwmon, DRM sensor info, VRAM usage
+    Sensors,
+    /// gpu_metrics, GPU activity, throttle log
+    GpuMetrics,
+    Fdinfo,
+    /// `pcie_bw` is read in a separate thread
+    PcieBw,
+    Ras,
+    /// CPU core frequency of APU
+    CpuFreq,
+}
+
+impl SampleSource {
+    pub const ALL: [Self; 7] = [
+        Self::PerfCounter,
+        Self::Sensors,
+        Self::GpuMetrics,
+        Self::Fdinfo,
+        Self::PcieBw,
+        Self::Ras,
+        Self::CpuFreq,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::PerfCounter => "pc",
+            Self::Sensors => "sensors",
+            Self::GpuMetrics => "metrics",
+            Self::Fdinfo => "fdinfo",
+            Self::PcieBw => "pcie",
+            Self::Ras => "ras",
+            Self::CpuFreq => "cpu_freq",
+        }
+    }
+
+    const fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for SampleSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SampleSource {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|src| src.as_str() == s).ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceConfig {
+    pub interval: Duration,
+    /// The number of samples per interval.
+    /// Only the perf counters are sampled multiple times,
+    /// other sources are cumulative counters or already averaged by the driver/firmware.
+    pub count: usize,
+}
+
+impl SourceConfig {
+    pub const fn new(interval: Duration) -> Self {
+        Self { interval, count: 1 }
+    }
+}
+
+/// Policies to stop touching the device while it is idle, see `stat::PowerStateMachine`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingPolicy {
+    /// `PowerState::NoPerfCounter`, disabled by `--pc-while-idle`
+    NoPcWhileIdle,
+    /// `PowerState::D3hot`, disabled by `--no-d3hot`
+    D3hotWhileIdle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamplingConfig {
+    sources: [SourceConfig; SampleSource::ALL.len()],
+    pub policies: Vec<SamplingPolicy>,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamplingConfigError(pub String);
+
+impl fmt::Display for SamplingConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let d = if let Some(ms) = s.strip_suffix("ms") {
+        Duration::from_millis(ms.parse().ok()?)
+    } else if let Some(sec) = s.strip_suffix('s') {
+        Duration::from_secs(sec.parse().ok()?)
+    } else {
+        Duration::from_millis(s.parse().ok()?)
+    };
+
+    Some(d)
+}
+
+impl SamplingConfig {
+    /// All sources are updated every `interval`, and the perf counters are sampled 100 times.
+    /// `interval` is clamped to `MIN_INTERVAL`, the frontends should reject a shorter interval.
+    pub fn new(interval: Duration) -> Self {
+        let interval = interval.max(MIN_INTERVAL);
+        let mut sources = [SourceConfig::new(interval); SampleSource::ALL.len()];
+
+        sources[SampleSource::PerfCounter.index()].count = 100;
+
+        Self {
+            sources,
+            policies: vec![SamplingPolicy::NoPcWhileIdle, SamplingPolicy::D3hotWhileIdle],
+        }
+    }
+
+    /// for high frequency mode of TUI (100 samples in 100ms)
+    pub fn high() -> Self {
+        Self::new(Duration::from_millis(100))
+    }
+
+    pub fn get(&self, src: SampleSource) -> SourceConfig {
+        self.sources[src.index()]
+    }
+
+    /// The interval is clamped to `MIN_INTERVAL`, and the perf counter samples to `1..=MAX_PC_COUNT`.
+    pub fn set(&mut self, src: SampleSource, config: SourceConfig) {
+        let interval = config.interval.max(MIN_INTERVAL);
+        let count = if src == SampleSource::PerfCounter {
+            config.count.clamp(1, MAX_PC_COUNT)
+        } else {
+            1
+        };
+
+        self.sources[src.index()] = SourceConfig { interval, count };
+    }
+
+    pub fn has_policy(&self, policy: SamplingPolicy) -> bool {
+        self.policies.contains(&policy)
+    }
+
+    /// Apply `<SOURCE>=<INTERVAL>[:<COUNT>],..` over the current config.
+    /// e.g. `pc=500ms:50,sensors=2s`
+    pub fn apply(&mut self, s: &str) -> Result<(), SamplingConfigError> {
+        let err = |msg: String| SamplingConfigError(msg);
+
+        for item in s.split(',').map(|item| item.trim()).filter(|item| !item.is_empty()) {
+            let Some((src, val)) = item.split_once('=') else {
+                return Err(err(format!("expected `<SOURCE>=<INTERVAL>[:<COUNT>]`: {item:?}")));
+            };
+            let Ok(src) = src.trim().parse::<SampleSource>() else {
+                let list: Vec<&str> = SampleSource::ALL.iter().map(|src| src.as_str()).collect();
+                return Err(err(format!("unknown source {src:?} (expected one of {})", list.join(", "))));
+            };
+            let (interval, count) = match val.trim().split_once(':') {
+                Some((interval, count)) => (interval, Some(count)),
+                None => (val.trim(), None),
+            };
+            let Some(interval) = parse_duration(interval) else {
+                return Err(err(format!("invalid interval for {src}: {interval:?}")));
+            };
+
+            if interval < MIN_INTERVAL {
+                return Err(err(format!("interval for {src} must be at least {MIN_INTERVAL:?}: {interval:?}")));
+            }
+
+            let count = match count {
+                Some(_) if src != SampleSource::PerfCounter => {
+                    return Err(err(format!("sample count is only supported for {}", SampleSource::PerfCounter)));
+                },
+                Some(count) => {
+                    let Ok(count) = count.parse::<usize>() else {
+                        return Err(err(format!("invalid sample count for {src}: {count:?}")));
+                    };
+
+                    if !(1..=MAX_PC_COUNT).contains(&count) {
+                        return Err(err(format!("sample count for {src} must be 1-{MAX_PC_COUNT}: {count}")));
+                    }
+
+                    count
+                },
+                None => self.get(src).count,
+            };
+
+            self.set(src, SourceConfig { interval, count });
+        }
+
+        Ok(())
+    }
+
+    /// Apply a config file, `<SOURCE>=<INTERVAL>[:<COUNT>]` per line, `#` starts a comment.
+    /// ```text
+    /// # amdgpu_top sampling config
+    /// pc = 2s:200
+    /// fdinfo = 500ms
+    /// ```
+    pub fn apply_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SamplingConfigError> {
+        let s = fs::read_to_string(path).map_err(|e| SamplingConfigError(e.to_string()))?;
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _comment)| line);
+
+            self.apply(line).map_err(|e| SamplingConfigError(format!("line {}: {e}", i+1)))?;
+        }
+
+        Ok(())
+    }
+
+    /// The shortest interval, the period of the main loop of the frontends.
+    /// `pcie_bw` is read in a separate thread, so it is not included.
+    pub fn tick(&self) -> Duration {
+        SampleSource::ALL
+            .iter()
+            .filter(|src| **src != SampleSource::PcieBw)
+            .map(|src| self.get(*src).interval)
+            .min()
+            .unwrap()
+    }
+
+    /// The number of the perf counter samples and the delay between them in a tick.
+    pub fn pc_sampling(&self) -> Sampling {
+        let tick = self.tick();
+        let pc = self.get(SampleSource::PerfCounter);
+        let count = (pc.count as u128 * tick.as_nanos() / pc.interval.as_nanos()).max(1) as usize;
+
+        Sampling {
+            count,
+            delay: tick / count as u32,
+        }
+    }
+}
+
+impl FromStr for SamplingConfig {
+    type Err = SamplingConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Self::default();
+        config.apply(s)?;
+
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    config: SamplingConfig,
+    elapsed: [Duration; SampleSource::ALL.len()],
+}
+
+impl Scheduler {
+    pub fn new(config: SamplingConfig) -> Self {
+        let mut elapsed = [Duration::ZERO; SampleSource::ALL.len()];
+
+        // Update all sources on the first call,
+        // except for fdinfo that needs two samples to calculate usage.
+        for src in SampleSource::ALL {
+            if src != SampleSource::Fdinfo {
+                elapsed[src.index()] = config.get(src).interval;
+            }
+        }
+
+        Self { config, elapsed }
+    }
+
+    pub fn config(&self) -> &SamplingConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: SamplingConfig) {
+        self.config = config;
+    }
+
+    pub fn advance(&mut self, interval: Duration) {
+        for e in self.elapsed.iter_mut() {
+            *e += interval;
+        }
+    }
+
+    /// Elapsed time since the last update of the source
+    pub fn elapsed(&self, src: SampleSource) -> Duration {
+        self.elapsed[src.index()]
+    }
+
+    pub fn is_due(&self, src: SampleSource) -> bool {
+        let interval = self.config.get(src).interval;
+        let elapsed = self.elapsed(src);
+
+        // tolerance for the rounding of `Sampling::to_duration`
+        elapsed != Duration::ZERO && interval.saturating_sub(interval / 10) <= elapsed
+    }
+
+    pub fn reset(&mut self, src: SampleSource) {
+        self.elapsed[src.index()] = Duration::ZERO;
+    }
+}
+
+#[test]
+fn test_sampling_config() {
+    let config: SamplingConfig = "pc=500ms:50, sensors=2s,fdinfo=1500".parse().unwrap();
+
+    assert_eq!(config.get(SampleSource::PerfCounter), SourceConfig { interval: Duration::from_millis(500), count: 50 });
+    assert_eq!(config.get(SampleSource::Sensors).interval, Duration::from_secs(2));
+    assert_eq!(config.get(SampleSource::Fdinfo).interval, Duration::from_millis(1500));
+    assert_eq!(config.get(SampleSource::Ras), SourceConfig::new(Duration::from_secs(1)));
+    assert_eq!(config.tick(), Duration::from_millis(500));
+
+    let sample = config.pc_sampling();
+    assert_eq!((sample.count, sample.delay), (50, Duration::from_millis(10)));
+
+    // 100 samples per 2s, 50 samples in a tick
+    let config: SamplingConfig = "pc=2s".parse().unwrap();
+    assert_eq!(config.pc_sampling().count, 50);
+
+    assert!("gpu=1s".parse::<SamplingConfig>().is_err());
+    assert!("sensors=1s:10".parse::<SamplingConfig>().is_err());
+    assert!("pc=1x".parse::<SamplingConfig>().is_err());
+    assert!("fdinfo=1ms".parse::<SamplingConfig>().is_err());
+    assert!("pc=1s:0".parse::<SamplingConfig>().is_err());
+}
+
+#[test]
+fn test_scheduler() {
+    let config: SamplingConfig = "pc=2s,ras=1500ms".parse().unwrap();
+    let mut sched = Scheduler::new(config);
+
+    assert!(sched.is_due(SampleSource::PerfCounter));
+    assert!(!sched.is_due(SampleSource::Fdinfo));
+
+    for src in SampleSource::ALL {
+        sched.reset(src);
+    }
+
+    // `Sampling::to_duration` may be a little shorter than the tick
+    sched.advance(Duration::from_millis(999));
+    assert!(sched.is_due(SampleSource::Sensors));
+    assert!(!sched.is_due(SampleSource::PerfCounter));
+    assert!(!sched.is_due(SampleSource::Ras));
+
+    sched.advance(Duration::from_millis(999));
+    assert!(sched.is_due(SampleSource::PerfCounter));
+    assert!(sched.is_due(SampleSource::Ras));
+    assert_eq!(sched.elapsed(SampleSource::Fdinfo), Duration::from_millis(1998));
+}
+
+#[test]
+fn test_sampling_config_file() {
+    let mut config = SamplingConfig::default();
+    config.apply_file("src/sampling_config_sample.txt").unwrap();
+
+    assert_eq!(config.get(SampleSource::PerfCounter), SourceConfig { interval: Duration::from_secs(2), count: 200 });
+    assert_eq!(config.get(SampleSource::Sensors).interval, Duration::from_secs(2));
+    assert_eq!(config.get(SampleSource::Fdinfo).interval, Duration::from_millis(500));
+    assert_eq!(config.get(SampleSource::Ras).interval, Duration::from_secs(10));
+}
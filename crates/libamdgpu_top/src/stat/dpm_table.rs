@@ -0,0 +1,214 @@
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#pp-dpm
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum DpmDomain {
+    SCLK,
+    MCLK,
+    FCLK,
+    SOCCLK,
+    DCEFCLK,
+    VCLK,
+    DCLK,
+    PCIE,
+}
+
+impl DpmDomain {
+    pub const ALL: [Self; 8] = [
+        Self::SCLK,
+        Self::MCLK,
+        Self::FCLK,
+        Self::SOCCLK,
+        Self::DCEFCLK,
+        Self::VCLK,
+        Self::DCLK,
+        Self::PCIE,
+    ];
+
+    pub const fn file_name(&self) -> &'static str {
+        match self {
+            Self::SCLK => "pp_dpm_sclk",
+            Self::MCLK => "pp_dpm_mclk",
+            Self::FCLK => "pp_dpm_fclk",
+            Self::SOCCLK => "pp_dpm_socclk",
+            Self::DCEFCLK => "pp_dpm_dcefclk",
+            Self::VCLK => "pp_dpm_vclk",
+            Self::DCLK => "pp_dpm_dclk",
+            Self::PCIE => "pp_dpm_pcie",
+        }
+    }
+}
+
+impl fmt::Display for DpmDomain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DpmLevel {
+    /// "0", "1", .., or "S" (deep sleep)
+    pub label: String,
+    /// "1000Mhz", "8.0GT/s, x16 619Mhz"
+    pub desc: String,
+    pub mhz: Option<u32>,
+    pub is_active: bool,
+}
+
+impl DpmLevel {
+    // "1: 1000Mhz *", "S: 19Mhz *", "1: 8.0GT/s, x16 619Mhz *"
+    fn parse(line: &str) -> Option<Self> {
+        let (label, desc) = line.split_once(':')?;
+        let desc = desc.trim();
+        let is_active = desc.ends_with('*');
+        let desc = desc.trim_end_matches('*').trim_end().to_string();
+        let mhz = desc
+            .split_whitespace()
+            .find_map(|s| s.strip_suffix("Mhz").or_else(|| s.strip_suffix("MHz")))
+            .and_then(|s| s.parse().ok());
+
+        Some(Self { label: label.trim().to_string(), desc, mhz, is_active })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DpmTable {
+    pub domain: DpmDomain,
+    pub levels: Vec<DpmLevel>,
+    start: Instant,
+    last_update: Duration,
+    last_active: Option<usize>,
+    /// time the level was active
+    pub residency: Vec<Duration>,
+    pub total: Duration,
+    pub samples: u64,
+}
+
+impl DpmTable {
+    pub fn parse(domain: DpmDomain, s: &str) -> Option<Self> {
+        let levels: Vec<DpmLevel> = s.lines().filter_map(DpmLevel::parse).collect();
+
+        if levels.is_empty() {
+            return None;
+        }
+
+        let residency = vec![Duration::ZERO; levels.len()];
+        let last_active = levels.iter().position(|l| l.is_active);
+
+        Some(Self {
+            domain,
+            levels,
+            start: Instant::now(),
+            last_update: Duration::ZERO,
+            last_active,
+            residency,
+            total: Duration::ZERO,
+            samples: 0,
+        })
+    }
+
+    pub fn from_sysfs(domain: DpmDomain, sysfs_path: &Path) -> Option<Self> {
+        let s = std::fs::read_to_string(sysfs_path.join(domain.file_name())).ok()?;
+
+        Self::parse(domain, &s)
+    }
+
+    pub fn get_all_from_sysfs(sysfs_path: &Path) -> Vec<Self> {
+        DpmDomain::ALL
+            .iter()
+            .filter_map(|domain| Self::from_sysfs(*domain, sysfs_path))
+            .collect()
+    }
+
+    pub fn active_level(&self) -> Option<usize> {
+        self.levels.iter().position(|l| l.is_active)
+    }
+
+    /// Re-read the table and accumulate the residency.
+    /// The residency is reset if the number of levels is changed (e.g. by OD).
+    pub fn update(&mut self, sysfs_path: &Path) {
+        let Ok(s) = std::fs::read_to_string(sysfs_path.join(self.domain.file_name())) else {
+            return;
+        };
+        let levels: Vec<DpmLevel> = s.lines().filter_map(DpmLevel::parse).collect();
+
+        self.update_with_elapsed(self.start.elapsed(), levels);
+    }
+
+    /// The interval since the last update is counted for the level active at the last update.
+    pub fn update_with_elapsed(&mut self, now: Duration, levels: Vec<DpmLevel>) {
+        let delta = now.saturating_sub(self.last_update);
+
+        if levels.len() != self.levels.len() {
+            self.residency = vec![Duration::ZERO; levels.len()];
+            self.total = Duration::ZERO;
+            self.samples = 0;
+        } else if let Some(i) = self.last_active {
+            self.residency[i] += delta;
+            self.total += delta;
+        }
+
+        self.levels = levels;
+        self.last_update = now;
+        self.last_active = self.active_level();
+
+        if self.last_active.is_some() {
+            self.samples += 1;
+        }
+    }
+
+    /// The level is unknown while the device is idle (suspended), the interval is not counted.
+    pub fn update_for_idle(&mut self) {
+        let now = self.start.elapsed();
+
+        if let Some(i) = self.last_active.take() {
+            let delta = now.saturating_sub(self.last_update);
+
+            self.residency[i] += delta;
+            self.total += delta;
+        }
+
+        self.last_update = now;
+    }
+
+    pub fn residency_percent(&self, index: usize) -> f64 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+
+        self.residency.get(index).map_or(0.0, |r| r.as_secs_f64() * 100.0 / self.total.as_secs_f64())
+    }
+}
+
+#[test]
+fn test_dpm_table_parse() {
+    let sclk = std::fs::read_to_string("src/stat/pp_dpm_sclk_sample.txt").unwrap();
+    let pcie = std::fs::read_to_string("src/stat/pp_dpm_pcie_sample.txt").unwrap();
+    let mut sclk = DpmTable::parse(DpmDomain::SCLK, &sclk).unwrap();
+    let pcie = DpmTable::parse(DpmDomain::PCIE, &pcie).unwrap();
+
+    assert_eq!(sclk.levels.len(), 3);
+    assert_eq!(sclk.levels[0].label, "S");
+    assert_eq!(sclk.levels[2].mhz, Some(2575));
+    assert_eq!(sclk.active_level(), Some(1));
+
+    let secs = Duration::from_secs;
+    let mut levels = sclk.levels.clone();
+
+    levels[1].is_active = false;
+    levels[2].is_active = true;
+    sclk.update_with_elapsed(secs(3), levels.clone());
+    sclk.update_with_elapsed(secs(4), levels);
+    assert_eq!(sclk.total, secs(4));
+    assert_eq!(sclk.residency_percent(1), 75.0);
+    assert_eq!(sclk.residency_percent(2), 25.0);
+    assert_eq!(sclk.active_level(), Some(2));
+
+    assert_eq!(pcie.levels.len(), 2);
+    assert_eq!(pcie.levels[1].desc, "16.0GT/s, x16 619Mhz");
+    assert_eq!(pcie.active_level(), Some(1));
+}
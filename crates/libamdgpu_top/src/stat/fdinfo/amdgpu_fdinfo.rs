@@ -3,7 +3,7 @@ use std::io::Read;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
-use super::ProcInfo;
+use super::{KfdProcUsage, ProcInfo};
 use crate::stat;
 
 // ref: drivers/gpu/drm/amd/amdgpu/amdgpu_fdinfo.c
@@ -357,6 +357,10 @@ pub struct ProcUsage {
     pub ids_count: usize,
     pub usage: FdInfoUsage,
     pub is_kfd_process: bool,
+    /// from `/sys/class/kfd/kfd/proc/<pid>/`
+    pub kfd: Option<KfdProcUsage>,
+    /// usage per DRM node, only if the process opens multiple DRM nodes (compute partitions)
+    pub devices: Vec<(PathBuf, FdInfoUsage)>,
 }
 
 #[derive(Clone, Default)]
@@ -368,6 +372,10 @@ pub struct FdInfoStat {
     pub has_vcn: bool,
     pub has_vcn_unified: bool,
     pub has_vpe: bool,
+    /// `gpu_id`s of the KFD topology nodes of the device
+    pub kfd_gpu_ids: Vec<u64>,
+    pub pre_kfd_sdma_map: HashMap<i32, u64>,
+    pub pre_device_usage_map: HashMap<(i32, PathBuf), FdInfoUsage>,
 }
 
 impl FdInfoStat {
@@ -394,12 +402,19 @@ impl FdInfoStat {
         let mut buf = String::with_capacity(2048);
         let mut ids_count = 0usize;
         let mut path = PathBuf::with_capacity(24);
+        let mut device_stat: Vec<(PathBuf, FdInfoUsage)> = if proc_info.devices.len() > 1 {
+            proc_info.devices.iter().map(|(d, _)| (d.clone(), FdInfoUsage::default())).collect()
+        } else {
+            Vec::new()
+        };
 
         path.push("/proc");
         path.push(pid.to_string());
 
         if !path.exists() {
             self.pre_proc_usage_map.remove(&pid);
+            self.pre_kfd_sdma_map.remove(&pid);
+            self.pre_device_usage_map.retain(|(p, _), _| *p != pid);
             return;
         }
 
@@ -423,23 +438,43 @@ impl FdInfoStat {
                 continue;
             }
 
+            let mut fd_stat = FdInfoUsage::default();
+
             for l in lines {
                 let Some(s) = l.get(0..10) else { continue };
 
                 match s {
-                    "drm-memory" => stat.mem_usage_parse(l),
-                    "drm-engine" => stat.engine_parse(l),
-                    "amd-evicte" => stat.evicted_vram_parse(l),
-                    "amd-reques" => stat.requested_vram_parse(l),
+                    "drm-memory" => fd_stat.mem_usage_parse(l),
+                    "drm-engine" => fd_stat.engine_parse(l),
+                    "amd-evicte" => fd_stat.evicted_vram_parse(l),
+                    "amd-reques" => fd_stat.requested_vram_parse(l),
                     "drm-total-" |
                     "drm-shared" |
                     "drm-reside" |
-                    "drm-purgea" => stat.drm_memory_stat_parse(l),
+                    "drm-purgea" => fd_stat.drm_memory_stat_parse(l),
                     _ => {},
                 }
             }
+
+            stat = stat + fd_stat;
+
+            if let Some((_, s)) = proc_info.devices
+                .iter()
+                .position(|(_, fds)| fds.contains(fd))
+                .and_then(|i| device_stat.get_mut(i))
+            {
+                *s = *s + fd_stat;
+            }
         }
 
+        // The first sample of each node has no engine usage, same as the process
+        let devices = device_stat.into_iter().map(|(device, cur)| {
+            let pre = self.pre_device_usage_map.insert((pid, device.clone()), cur).unwrap_or(cur);
+            let usage = cur.calc_usage(&pre, 0.0, 0.0, &self.interval, self.has_vcn, self.has_vcn_unified);
+
+            (device, usage)
+        }).collect();
+
         let name = proc_info.name.clone();
         let cur_cpu_time = {
             path.pop(); // fdinfo
@@ -527,15 +562,31 @@ impl FdInfoStat {
             }
         };
 
+        let kfd = self.get_kfd_proc_usage(proc_info);
+
         self.proc_usage.push(ProcUsage {
             pid,
             name,
             ids_count,
             usage,
             is_kfd_process: proc_info.is_kfd_proc,
+            kfd,
+            devices,
         });
     }
 
+    fn get_kfd_proc_usage(&mut self, proc_info: &ProcInfo) -> Option<KfdProcUsage> {
+        if !proc_info.is_kfd_proc || self.kfd_gpu_ids.is_empty() { return None }
+
+        let mut kfd = KfdProcUsage::read(proc_info.pid, &self.kfd_gpu_ids)?;
+
+        if let Some(pre_sdma_time) = self.pre_kfd_sdma_map.insert(proc_info.pid, kfd.sdma_time) {
+            kfd.calc_sdma_usage(pre_sdma_time, &self.interval);
+        }
+
+        Some(kfd)
+    }
+
     pub fn update_proc_usage(&mut self, proc_index: &[ProcInfo]) {
         self.proc_usage.clear();
         self.drm_client_ids.clear();
@@ -554,6 +605,8 @@ impl FdInfoStat {
 
                 b
             });
+            self.pre_kfd_sdma_map.retain(|pid, _| self.pre_proc_usage_map.contains_key(pid));
+            self.pre_device_usage_map.retain(|(pid, _), _| self.pre_proc_usage_map.contains_key(pid));
         }
 
         for pu in proc_index {
@@ -591,4 +644,11 @@ impl FdInfoStat {
             .iter()
             .any(|pu| pu.is_kfd_process)
     }
+
+    pub fn has_kfd_stat(&self) -> bool {
+        self
+            .proc_usage
+            .iter()
+            .any(|pu| pu.kfd.is_some())
+    }
 }
@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+// ref: drivers/gpu/drm/amd/amdkfd/kfd_process.c
+//
+// /sys/class/kfd/kfd/proc/<pid>/
+//   vram_<gpu_id>: VRAM usage (bytes)
+//   sdma_<gpu_id>: SDMA activity (usec)
+//   stats_<gpu_id>/evicted_ms, stats_<gpu_id>/cu_occupancy
+//   queues/<queue_id>/gpuid, queues/<queue_id>/type
+//
+// The AMDKFD driver does not show them in fdinfo.
+// `gpu_id` is from the KFD topology node, a compute partition has its own `gpu_id`.
+const KFD_PROC: &str = "/sys/class/kfd/kfd/proc";
+
+// enum kfd_queue_type
+const KFD_QUEUE_TYPE_COMPUTE: u64 = 0;
+const KFD_QUEUE_TYPE_SDMA: u64 = 1;
+const KFD_QUEUE_TYPE_SDMA_XGMI: u64 = 4;
+const KFD_QUEUE_TYPE_SDMA_BY_ENG_ID: u64 = 5;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd)]
+pub struct KfdProcUsage {
+    pub vram_usage: u64, // KiB
+    pub sdma_time: u64, // usec, total
+    pub sdma: i64, // %
+    pub cu_occupancy: u64, // number of CUs
+    pub evicted_ms: u64, // ms, total
+    pub compute_queues: u32,
+    pub sdma_queues: u32,
+}
+
+impl KfdProcUsage {
+    /// `None` if the process has no KFD process entry for the GPU
+    pub fn read(pid: i32, gpu_ids: &[u64]) -> Option<Self> {
+        Self::from_path(Path::new(KFD_PROC).join(pid.to_string()), gpu_ids)
+    }
+
+    fn from_path<P: AsRef<Path>>(path: P, gpu_ids: &[u64]) -> Option<Self> {
+        let path = path.as_ref();
+        let read = |name: String| -> Option<u64> {
+            fs::read_to_string(path.join(name)).ok()?.trim().parse().ok()
+        };
+        let mut usage = Self::default();
+        let mut found = false;
+
+        for id in gpu_ids {
+            let Some(vram) = read(format!("vram_{id}")) else { continue };
+            found = true;
+
+            usage.vram_usage += vram >> 10;
+            usage.sdma_time += read(format!("sdma_{id}")).unwrap_or(0);
+            usage.cu_occupancy += read(format!("stats_{id}/cu_occupancy")).unwrap_or(0);
+            usage.evicted_ms += read(format!("stats_{id}/evicted_ms")).unwrap_or(0);
+        }
+
+        if !found { return None }
+
+        if let Ok(queues) = fs::read_dir(path.join("queues")) {
+            for queue in queues.flatten() {
+                let queue = queue.path();
+                let read = |name: &str| -> Option<u64> {
+                    fs::read_to_string(queue.join(name)).ok()?.trim().parse().ok()
+                };
+
+                if !read("gpuid").is_some_and(|id| gpu_ids.contains(&id)) { continue }
+
+                match read("type") {
+                    Some(KFD_QUEUE_TYPE_COMPUTE) => usage.compute_queues += 1,
+                    Some(
+                        KFD_QUEUE_TYPE_SDMA |
+                        KFD_QUEUE_TYPE_SDMA_XGMI |
+                        KFD_QUEUE_TYPE_SDMA_BY_ENG_ID
+                    ) => usage.sdma_queues += 1,
+                    _ => {},
+                }
+            }
+        }
+
+        Some(usage)
+    }
+
+    pub fn calc_sdma_usage(&mut self, pre_sdma_time: u64, interval: &Duration) {
+        let interval = interval.as_micros() as u64;
+
+        self.sdma = (self.sdma_time.saturating_sub(pre_sdma_time) * 100)
+            .checked_div(interval)
+            .unwrap_or(0) as i64;
+    }
+
+    pub fn total_queues(&self) -> u32 {
+        self.compute_queues + self.sdma_queues
+    }
+}
+
+#[test]
+fn test_kfd_proc_usage() {
+    let root = std::env::temp_dir().join(format!("amdgpu_top_kfd_proc_{}", std::process::id()));
+    let queue = |id: u32, gpuid: u64, queue_type: u64| {
+        let path = root.join(format!("queues/{id}"));
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("gpuid"), format!("{gpuid}\n")).unwrap();
+        fs::write(path.join("type"), format!("{queue_type}\n")).unwrap();
+    };
+
+    fs::create_dir_all(root.join("stats_4386")).unwrap();
+    fs::write(root.join("vram_4386"), "268435456\n").unwrap();
+    fs::write(root.join("sdma_4386"), "1500000\n").unwrap();
+    fs::write(root.join("stats_4386/cu_occupancy"), "24\n").unwrap();
+    fs::write(root.join("stats_4386/evicted_ms"), "12\n").unwrap();
+    fs::write(root.join("vram_9999"), "4096\n").unwrap();
+    queue(0, 4386, KFD_QUEUE_TYPE_COMPUTE);
+    queue(1, 4386, KFD_QUEUE_TYPE_COMPUTE);
+    queue(2, 4386, KFD_QUEUE_TYPE_SDMA);
+    queue(3, 9999, KFD_QUEUE_TYPE_COMPUTE);
+
+    assert!(KfdProcUsage::from_path(&root, &[1234]).is_none());
+
+    let mut usage = KfdProcUsage::from_path(&root, &[4386]).unwrap();
+
+    assert_eq!(usage.vram_usage, 256 << 10);
+    assert_eq!(usage.cu_occupancy, 24);
+    assert_eq!(usage.evicted_ms, 12);
+    assert_eq!(usage.compute_queues, 2);
+    assert_eq!(usage.sdma_queues, 1);
+
+    usage.calc_sdma_usage(1_000_000, &Duration::from_secs(1));
+    assert_eq!(usage.sdma, 50);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -6,3 +6,6 @@ pub use amdgpu_fdinfo_sort::*;
 
 mod proc_info;
 pub use proc_info::*;
+
+mod kfd_proc;
+pub use kfd_proc::*;
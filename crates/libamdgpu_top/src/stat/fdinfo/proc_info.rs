@@ -9,12 +9,15 @@ pub struct ProcInfo {
     pub pid: i32,
     pub name: String,
     pub fds: Vec<i32>,
+    /// DRM nodes opened by the process and their fds, to find the compute partitions used by the process
+    pub devices: Vec<(PathBuf, Vec<i32>)>,
     pub is_kfd_proc: bool,
 }
 
-fn get_fds<T: AsRef<Path>>(fd_dir_path: &mut PathBuf, device_path: &[T]) -> (Vec<i32>, bool) {
-    let Ok(fd_list) = fs::read_dir(&fd_dir_path) else { return (Vec::new(), false) };
+fn get_fds<T: AsRef<Path>>(fd_dir_path: &mut PathBuf, device_path: &[T]) -> (Vec<i32>, Vec<(PathBuf, Vec<i32>)>, bool) {
+    let Ok(fd_list) = fs::read_dir(&fd_dir_path) else { return (Vec::new(), Vec::new(), false) };
     let mut fds: Vec<i32> = Vec::with_capacity(16);
+    let mut devices: Vec<(PathBuf, Vec<i32>)> = Vec::new();
     let mut is_kfd_proc = false;
 
     for dir_entry in fd_list {
@@ -28,9 +31,15 @@ fn get_fds<T: AsRef<Path>>(fd_dir_path: &mut PathBuf, device_path: &[T]) -> (Vec
         };
 
         // e.g. "/dev/dri/renderD128" or "/dev/dri/card0"
-        if device_path.iter().any(|path| link.starts_with(path)) {
+        if let Some(path) = device_path.iter().find(|path| link.starts_with(path)) {
             let Some(fd) = fd.to_str().and_then(|s| s.parse::<i32>().ok()) else { continue };
             fds.push(fd);
+
+            if let Some((_, device_fds)) = devices.iter_mut().find(|(d, _)| d == path.as_ref()) {
+                device_fds.push(fd);
+            } else {
+                devices.push((path.as_ref().to_path_buf(), vec![fd]));
+            }
         }
 
         if link.starts_with("/dev/kfd") {
@@ -38,7 +47,7 @@ fn get_fds<T: AsRef<Path>>(fd_dir_path: &mut PathBuf, device_path: &[T]) -> (Vec
         }
     }
 
-    (fds, is_kfd_proc)
+    (fds, devices, is_kfd_proc)
 }
 
 pub fn get_process_list() -> Vec<i32> {
@@ -101,7 +110,7 @@ pub fn update_index_by_all_proc<T: AsRef<Path>>(
         buf_path.push("/proc");
         buf_path.push(pid.to_string());
 
-        let (fds, is_kfd_proc) = get_fds(&mut buf_path.join("fd/"), device_path);
+        let (fds, devices, is_kfd_proc) = get_fds(&mut buf_path.join("fd/"), device_path);
 
         if fds.is_empty() { continue }
 
@@ -114,7 +123,7 @@ pub fn update_index_by_all_proc<T: AsRef<Path>>(
         buf_name.pop(); // trim '\n'
         let name = buf_name.clone();
 
-        vec_info.push(ProcInfo { pid, name, fds, is_kfd_proc });
+        vec_info.push(ProcInfo { pid, name, fds, devices, is_kfd_proc });
     }
 }
 
@@ -144,15 +153,9 @@ pub fn spawn_update_index_thread(
         let all_proc = get_process_list();
 
         for device_path in &device_paths {
-            let paths: &[&PathBuf] = if device_path.is_amdgpu() {
-                &[&device_path.render, &device_path.card]
-            } else {
-                &[&device_path.accel]
-            };
-
             update_index_by_all_proc(
                 &mut buf_index,
-                paths,
+                &device_path.drm_paths(),
                 &all_proc,
             );
 
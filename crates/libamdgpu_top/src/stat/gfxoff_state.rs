@@ -3,9 +3,10 @@
 // AMD APU/GPU exits GFXOFF state by reading the performance counter (GRBM, GRBM2),  
 // so useful only in SMI mode.
 
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 use std::path::PathBuf;
 use std::fs;
+use std::time::{Duration, Instant};
 use crate::PCI;
 
 const BASE: &str = "/sys/kernel/debug/dri";
@@ -110,6 +111,149 @@ impl From<u32> for GfxoffStatus {
     }
 }
 
+// Reading `amdgpu_gfxoff_residency` and `amdgpu_gfxoff_count` resumes the device (runtime PM).
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_debugfs.c
+
+/// GFXOFF residency and entry count
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GfxoffResidency {
+    /// average GFXOFF residency (%) during the last interval
+    pub residency: Option<f64>,
+    /// time-weighted average of the residency over the session
+    pub session_residency: Option<f64>,
+    /// total GFXOFF entry count since system power-up
+    pub count: Option<u64>,
+    /// GFXOFF entry count since the session started
+    pub session_count: Option<u64>,
+    /// Why the values are not available
+    pub note: Option<String>,
+}
+
+impl GfxoffResidency {
+    pub fn is_available(&self) -> bool {
+        self.residency.is_some() || self.count.is_some()
+    }
+}
+
+/// Start/stop the residency logging of SMU with `amdgpu_gfxoff_residency`.
+/// The logging is restarted on every update to read the residency of the last interval,
+/// and stopped when dropped.
+/// Reading debugfs wakes the GFX, `AppAmdgpuTop` updates it only while `PowerState::Active`,
+/// and does not create it for dump modes.
+#[derive(Debug)]
+pub struct GfxoffResidencyLogger {
+    debug_dri_path: Option<PathBuf>,
+    is_logging: bool,
+    start: Instant,
+    last_update: Duration,
+    start_count: Option<u64>,
+    /// sum of (residency * interval)
+    total_residency: f64,
+    total_time: Duration,
+    pub stat: GfxoffResidency,
+}
+
+impl GfxoffResidencyLogger {
+    const RESIDENCY: &str = "amdgpu_gfxoff_residency";
+    const COUNT: &str = "amdgpu_gfxoff_count";
+
+    pub fn new(pci_bus: PCI::BUS_INFO) -> Self {
+        let debug_dri_path = pci_bus.get_debug_dri_path().ok();
+        let mut logger = Self {
+            debug_dri_path,
+            is_logging: false,
+            start: Instant::now(),
+            last_update: Duration::ZERO,
+            start_count: None,
+            total_residency: 0.0,
+            total_time: Duration::ZERO,
+            stat: GfxoffResidency::default(),
+        };
+
+        let Some(path) = &logger.debug_dri_path else {
+            logger.stat.note = Some("debugfs (/sys/kernel/debug/dri) is not available, requires root".to_string());
+            return logger;
+        };
+
+        logger.is_logging = write_gfxoff(path.join(Self::RESIDENCY), 1).is_ok();
+        logger.start_count = read_gfxoff_count(path.join(Self::COUNT)).ok();
+        logger.update_with_elapsed(Duration::ZERO, None, logger.start_count);
+
+        logger
+    }
+
+    pub fn update(&mut self) {
+        let Some(path) = &self.debug_dri_path else { return };
+        let residency = if self.is_logging {
+            let path = path.join(Self::RESIDENCY);
+            let residency = write_gfxoff(&path, 0).and_then(|_| read_gfxoff(&path)).ok();
+
+            self.is_logging = write_gfxoff(&path, 1).is_ok();
+
+            residency
+        } else {
+            None
+        };
+        let count = read_gfxoff_count(path.join(Self::COUNT)).ok();
+
+        self.update_with_elapsed(self.start.elapsed(), residency, count);
+    }
+
+    /// `residency`: the raw value of `amdgpu_gfxoff_residency` (% * 100)
+    pub fn update_with_elapsed(&mut self, now: Duration, residency: Option<u32>, count: Option<u64>) {
+        let interval = now.saturating_sub(self.last_update);
+        let residency = residency.map(|r| r as f64 / 100.0);
+
+        self.last_update = now;
+
+        if let Some(r) = residency {
+            self.total_residency += r * interval.as_secs_f64();
+            self.total_time += interval;
+        }
+
+        if self.start_count.is_none() {
+            self.start_count = count;
+        }
+
+        self.stat.residency = residency;
+        self.stat.session_residency = (!self.total_time.is_zero())
+            .then(|| self.total_residency / self.total_time.as_secs_f64());
+        self.stat.count = count;
+        self.stat.session_count = count.zip(self.start_count).map(|(cur, start)| cur.saturating_sub(start));
+        self.stat.note = if self.debug_dri_path.is_none() {
+            self.stat.note.take()
+        } else if !self.is_logging && count.is_none() {
+            Some(format!("{} and {} are not supported", Self::RESIDENCY, Self::COUNT))
+        } else {
+            None
+        };
+    }
+}
+
+impl Drop for GfxoffResidencyLogger {
+    fn drop(&mut self) {
+        if let Some(path) = &self.debug_dri_path
+            && self.is_logging
+        {
+            let _ = write_gfxoff(path.join(Self::RESIDENCY), 0);
+        }
+    }
+}
+
+fn write_gfxoff<P: Into<PathBuf>>(path: P, val: u32) -> io::Result<()> {
+    let mut f = fs::OpenOptions::new().write(true).open(path.into())?;
+    f.write_all(&val.to_le_bytes())
+}
+
+fn read_gfxoff_count<P: Into<PathBuf>>(path: P) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+
+    let mut f = fs::File::open(path.into())?;
+    f.read_exact(&mut buf)?;
+
+    Ok(u64::from_le_bytes(buf))
+}
+
 fn read_gfxoff<P: Into<PathBuf>>(path: P) -> io::Result<u32> {
     let mut buf = [0xFFu8; 4];
     
@@ -118,3 +262,34 @@ fn read_gfxoff<P: Into<PathBuf>>(path: P) -> io::Result<u32> {
 
     Ok(u32::from_le_bytes(buf))
 }
+
+#[test]
+fn test_gfxoff_residency() {
+    let secs = Duration::from_secs;
+    let mut logger = GfxoffResidencyLogger {
+        debug_dri_path: Some(PathBuf::new()),
+        is_logging: true,
+        start: Instant::now(),
+        last_update: Duration::ZERO,
+        start_count: None,
+        total_residency: 0.0,
+        total_time: Duration::ZERO,
+        stat: GfxoffResidency::default(),
+    };
+
+    logger.update_with_elapsed(secs(0), None, Some(100));
+    logger.update_with_elapsed(secs(1), Some(9000), Some(110));
+    logger.update_with_elapsed(secs(4), Some(5000), Some(150));
+
+    assert_eq!(logger.stat.residency, Some(50.0));
+    assert_eq!(logger.stat.session_residency, Some(60.0));
+    assert_eq!(logger.stat.count, Some(150));
+    assert_eq!(logger.stat.session_count, Some(50));
+    assert!(logger.stat.note.is_none());
+
+    // not supported by the device
+    logger.is_logging = false;
+    logger.update_with_elapsed(secs(5), None, None);
+    assert!(!logger.stat.is_available());
+    assert!(logger.stat.note.is_some());
+}
@@ -0,0 +1,91 @@
+use libdrm_amdgpu_sys::AMDGPU::DeviceHandle;
+use super::{PCRegTable, PerfCounter};
+
+// GRBM_STATUS_SE0..SE3
+// Only 4 SEs are in the allowed register list of amdgpu.
+// ref: drivers/gpu/drm/amd/amdgpu/soc21.c (soc21_allowed_read_registers)
+pub const GRBM_SE_PREFIX: &str = "GRBM_SE";
+
+/// Per-SE breakdown of GRBM_STATUS
+#[derive(Clone, Debug)]
+pub struct GrbmSe {
+    pub se: Vec<PerfCounter>,
+}
+
+impl GrbmSe {
+    pub fn is_se_table(table: &PCRegTable) -> bool {
+        table.name.starts_with(GRBM_SE_PREFIX)
+    }
+
+    pub fn from_tables(tables: &[PCRegTable], max_se: usize, amdgpu_dev: &DeviceHandle) -> Option<Self> {
+        let se: Vec<PerfCounter> = tables
+            .iter()
+            .filter(|t| Self::is_se_table(t))
+            .take(max_se)
+            .map(PerfCounter::from_table)
+            .filter(|pc| pc.check_reg(amdgpu_dev))
+            .collect();
+
+        // nothing to compare
+        if se.len() < 2 {
+            return None;
+        }
+
+        Some(Self { se })
+    }
+
+    pub fn bit_names(&self) -> Vec<&str> {
+        self.se[0].pc_index.iter().map(|idx| idx.name.as_str()).collect()
+    }
+
+    /// usage of the bit for each SE
+    pub fn usage(&self, bit: usize) -> Vec<u8> {
+        self.se.iter().filter_map(|pc| pc.pc_index.get(bit).map(|idx| idx.usage)).collect()
+    }
+
+    /// difference between the busiest SE and the least busy SE
+    pub fn imbalance(&self, bit: usize) -> u8 {
+        let usage = self.usage(bit);
+        let max = usage.iter().max().copied().unwrap_or(0);
+        let min = usage.iter().min().copied().unwrap_or(0);
+
+        max - min
+    }
+
+    pub fn read_reg(&mut self, amdgpu_dev: &DeviceHandle) {
+        for pc in self.se.iter_mut() {
+            pc.read_reg(amdgpu_dev);
+        }
+    }
+
+    pub fn clear_pc(&mut self) {
+        for pc in self.se.iter_mut() {
+            pc.clear_pc();
+        }
+    }
+
+    pub fn update_pc_usage(&mut self) {
+        for pc in self.se.iter_mut() {
+            pc.update_pc_usage();
+        }
+    }
+}
+
+#[test]
+fn test_grbm_se_imbalance() {
+    let tables = PCRegTable::parse_tables(
+        "[GRBM_SE0]\noffset = 0x2005\nTA = 25\nSPI = 27\n[GRBM_SE1]\noffset = 0x2006\nTA = 25\nSPI = 27\n"
+    ).unwrap();
+    let mut grbm_se = GrbmSe { se: tables.iter().map(PerfCounter::from_table).collect() };
+
+    for (pc, usage) in grbm_se.se.iter_mut().zip([[80, 10], [20, 10]]) {
+        for (idx, u) in pc.pc_index.iter_mut().zip(usage) {
+            idx.usage = u;
+        }
+    }
+
+    assert_eq!(grbm_se.bit_names(), ["TA", "SPI"]);
+    assert_eq!(grbm_se.usage(0), [80, 20]);
+    assert_eq!(grbm_se.imbalance(0), 60);
+    assert_eq!(grbm_se.imbalance(1), 0);
+}
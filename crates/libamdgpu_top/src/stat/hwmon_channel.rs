@@ -0,0 +1,214 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+use super::parse_hwmon;
+
+// ref: https://www.kernel.org/doc/html/latest/hwmon/sysfs-interface.html
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HwmonChannelType {
+    Temp,
+    In,
+    Power,
+    Freq,
+    Curr,
+    Fan,
+}
+
+impl HwmonChannelType {
+    const ALL: [Self; 6] = [
+        Self::Temp,
+        Self::In,
+        Self::Power,
+        Self::Freq,
+        Self::Curr,
+        Self::Fan,
+    ];
+
+    pub const fn prefix(&self) -> &'static str {
+        match self {
+            Self::Temp => "temp",
+            Self::In => "in",
+            Self::Power => "power",
+            Self::Freq => "freq",
+            Self::Curr => "curr",
+            Self::Fan => "fan",
+        }
+    }
+
+    // display unit for `HwmonChannel::to_display_value`
+    pub const fn unit(&self) -> &'static str {
+        match self {
+            Self::Temp => "C",
+            Self::In => "mV",
+            Self::Power => "W",
+            Self::Freq => "MHz",
+            Self::Curr => "A",
+            Self::Fan => "RPM",
+        }
+    }
+
+    // native unit (m°C, mV, µW, Hz, mA, RPM) per display unit
+    const fn divisor(&self) -> f64 {
+        match self {
+            Self::Temp => 1_000.0,
+            Self::In => 1.0,
+            Self::Power => 1_000_000.0,
+            Self::Freq => 1_000_000.0,
+            Self::Curr => 1_000.0,
+            Self::Fan => 1.0,
+        }
+    }
+
+    const fn input_attrs(&self) -> &'static [&'static str] {
+        match self {
+            Self::Power => &["average", "input"],
+            _ => &["input"],
+        }
+    }
+
+    fn parse_file_name(name: &str) -> Option<(Self, u32)> {
+        let (channel, _attr) = name.split_once('_')?;
+
+        Self::ALL.into_iter().find_map(|type_| {
+            let index = channel.strip_prefix(type_.prefix())?.parse::<u32>().ok()?;
+
+            Some((type_, index))
+        })
+    }
+}
+
+impl fmt::Display for HwmonChannelType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HwmonChannel {
+    pub type_: HwmonChannelType,
+    pub index: u32,
+    pub label: Option<String>,
+    /// `average` or `input` for the power channels
+    pub input_attr: Option<&'static str>,
+    // values are in native units
+    pub input: Option<i64>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub crit: Option<i64>,
+    pub emergency: Option<i64>,
+    pub cap: Option<i64>,
+}
+
+impl HwmonChannel {
+    pub fn from_hwmon_path(hwmon_path: &Path, type_: HwmonChannelType, index: u32) -> Self {
+        let prefix = format!("{}{index}", type_.prefix());
+        let read = |attr: &str| -> Option<i64> {
+            parse_hwmon(hwmon_path.join(format!("{prefix}_{attr}")))
+        };
+        let label = std::fs::read_to_string(hwmon_path.join(format!("{prefix}_label")))
+            .ok()
+            .map(|s| s.trim_end().to_string());
+        let (input_attr, input) = type_.input_attrs().iter().find_map(|attr| Some((*attr, read(attr)?))).unzip();
+
+        Self {
+            type_,
+            index,
+            label,
+            input_attr,
+            input,
+            min: read("min"),
+            max: read("max"),
+            crit: read("crit"),
+            emergency: read("emergency"),
+            cap: read("cap"),
+        }
+    }
+
+    pub fn update(&mut self, hwmon_path: &Path) {
+        let prefix = format!("{}{}", self.type_.prefix(), self.index);
+
+        (self.input_attr, self.input) = self.type_.input_attrs().iter().find_map(|attr| {
+            Some((*attr, parse_hwmon(hwmon_path.join(format!("{prefix}_{attr}")))?))
+        }).unzip();
+    }
+
+    pub fn name(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => format!("{}{}", self.type_.prefix(), self.index),
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        self.type_.unit()
+    }
+
+    pub fn to_display_value(&self, val: i64) -> f64 {
+        val as f64 / self.type_.divisor()
+    }
+
+    pub fn limits(&self) -> [(&'static str, Option<i64>); 5] {
+        [
+            ("min", self.min),
+            ("max", self.max),
+            ("crit", self.crit),
+            ("emergency", self.emergency),
+            ("cap", self.cap),
+        ]
+    }
+
+    /// Channels whose input is not readable yet (e.g. `temp2_input` while the dGPU is suspended)
+    /// are kept, and the input is re-read by `update`.
+    pub fn get_all_from_hwmon_path(hwmon_path: &Path) -> Vec<Self> {
+        let Ok(dir) = std::fs::read_dir(hwmon_path) else { return Vec::new() };
+        let channels: BTreeSet<(HwmonChannelType, u32)> = dir
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+
+                HwmonChannelType::parse_file_name(name.to_str()?)
+            })
+            .collect();
+
+        channels
+            .into_iter()
+            .map(|(type_, index)| Self::from_hwmon_path(hwmon_path, type_, index))
+            .collect()
+    }
+}
+
+#[test]
+fn test_hwmon_channel() {
+    let channels = HwmonChannel::get_all_from_hwmon_path(Path::new("src/stat/hwmon_sample/"));
+    let names: Vec<String> = channels.iter().map(|ch| ch.name()).collect();
+
+    assert_eq!(names, ["edge", "junction", "vddgfx", "PPT", "sclk", "fan1"]);
+
+    let edge = &channels[0];
+    assert_eq!(edge.input, Some(45_000));
+    assert_eq!(edge.to_display_value(edge.input.unwrap()), 45.0);
+    assert_eq!(edge.crit, Some(100_000));
+    assert_eq!(edge.emergency, Some(105_000));
+
+    let ppt = &channels[3];
+    assert_eq!(ppt.input, Some(15_000_000));
+    assert_eq!(ppt.cap, Some(203_000_000));
+    assert_eq!(ppt.input_attr, Some("average"));
+    assert_eq!(ppt.unit(), "W");
+
+    // unreadable at startup, re-read by `update`
+    let root = std::env::temp_dir().join(format!("amdgpu_top_hwmon_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("temp2_label"), "junction\n").unwrap();
+
+    let mut channels = HwmonChannel::get_all_from_hwmon_path(&root);
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].input, None);
+
+    std::fs::write(root.join("temp2_input"), "52000\n").unwrap();
+    channels[0].update(&root);
+    assert_eq!(channels[0].input, Some(52_000));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+use libdrm_amdgpu_sys::AMDGPU::HwmonTempType;
+use super::{MilliAmpere, MilliCelsius, MilliVolt, parse_hwmon};
+
+fn read_label(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim_end().to_string())
+}
+
+#[derive(Clone, Debug)]
+pub struct HwmonTemperature {
+    pub type_: HwmonTempType,
+    pub current: MilliCelsius,
+    pub critical: Option<MilliCelsius>,
+    pub emergency: Option<MilliCelsius>,
+}
+
+impl HwmonTemperature {
+    pub fn from_hwmon_path<P: Into<PathBuf>>(path: P, type_: HwmonTempType) -> Option<Self> {
+        let path = path.into();
+        // ref: drivers/gpu/drm/amd/pm/amdgpu_pm.c
+        let channel = match type_ {
+            HwmonTempType::Edge => 1,
+            HwmonTempType::Junction => 2,
+            HwmonTempType::Memory => 3,
+        };
+
+        let current = parse_hwmon(path.join(format!("temp{channel}_input"))).map(MilliCelsius)?;
+        let critical = parse_hwmon(path.join(format!("temp{channel}_crit"))).map(MilliCelsius);
+        let emergency = parse_hwmon(path.join(format!("temp{channel}_emergency"))).map(MilliCelsius);
+
+        Some(Self { type_, current, critical, emergency })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HwmonVoltage {
+    pub channel: u32,
+    pub label: Option<String>, // "vddgfx", "vddnb"
+    pub value: MilliVolt,
+}
+
+impl HwmonVoltage {
+    pub fn from_hwmon_path<P: Into<PathBuf>>(path: P, channel: u32) -> Option<Self> {
+        let path = path.into();
+        let value = parse_hwmon(path.join(format!("in{channel}_input"))).map(MilliVolt)?;
+        let label = read_label(&path.join(format!("in{channel}_label")));
+
+        Some(Self { channel, label, value })
+    }
+
+    pub fn name(&self) -> String {
+        self.label.clone().unwrap_or_else(|| format!("in{}", self.channel))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HwmonCurrent {
+    pub channel: u32,
+    pub label: Option<String>,
+    pub value: MilliAmpere,
+}
+
+impl HwmonCurrent {
+    pub fn from_hwmon_path<P: Into<PathBuf>>(path: P, channel: u32) -> Option<Self> {
+        let path = path.into();
+        let value = parse_hwmon(path.join(format!("curr{channel}_input"))).map(MilliAmpere)?;
+        let label = read_label(&path.join(format!("curr{channel}_label")));
+
+        Some(Self { channel, label, value })
+    }
+
+    pub fn name(&self) -> String {
+        self.label.clone().unwrap_or_else(|| format!("curr{}", self.channel))
+    }
+
+    // `curr1_*` is the first channel
+    pub(crate) fn all_from_hwmon_path(path: &Path) -> Vec<Self> {
+        (1..).map_while(|channel| Self::from_hwmon_path(path, channel)).collect()
+    }
+}
@@ -1,8 +1,6 @@
 use std::fmt;
 use std::path::PathBuf;
-
-const POWER1_AVG: &str = "power1_average";
-const POWER1_INPUT: &str = "power1_input";
+use super::{MicroWatt, parse_hwmon};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub enum PowerType {
@@ -11,10 +9,10 @@ pub enum PowerType {
 }
 
 impl PowerType {
-    const fn as_filename(&self) -> &str {
+    const fn as_suffix(&self) -> &str {
         match self {
-            Self::Input => POWER1_INPUT,
-            Self::Average => POWER1_AVG,
+            Self::Input => "input",
+            Self::Average => "average",
         }
     }
 }
@@ -28,16 +26,40 @@ impl fmt::Display for PowerType {
 #[derive(Clone, Debug)]
 pub struct HwmonPower {
     pub type_: PowerType,
-    pub value: u32, // W
+    pub label: Option<String>,
+    pub value: MicroWatt,
 }
 
 impl HwmonPower {
     pub fn from_hwmon_path_with_type<P: Into<PathBuf>>(path: P, type_: PowerType) -> Option<Self> {
+        Self::from_hwmon_path_with_channel(path, 1, type_)
+    }
+
+    pub fn from_hwmon_path_with_channel<P: Into<PathBuf>>(
+        path: P,
+        channel: u32,
+        type_: PowerType,
+    ) -> Option<Self> {
         let path = path.into();
 
-        let s = std::fs::read_to_string(path.join(type_.as_filename())).ok()?;
-        let value = s.trim_end().parse::<u32>().ok()?.saturating_div(1_000_000);
+        let value = parse_hwmon::<u64, _>(
+            path.join(format!("power{channel}_{}", type_.as_suffix()))
+        ).map(MicroWatt)?;
+        let label = std::fs::read_to_string(path.join(format!("power{channel}_label")))
+            .ok()
+            .map(|s| s.trim_end().to_string());
+
+        Some(Self { type_, label, value })
+    }
+
+    // APUs (e.g. VanGogh) export slowPPT as `power1_*` and fastPPT as `power2_*`
+    pub fn from_hwmon_path_with_channel_any_type<P: Into<PathBuf>>(
+        path: P,
+        channel: u32,
+    ) -> Option<Self> {
+        let path = path.into();
 
-        Some(Self { type_, value })
+        Self::from_hwmon_path_with_channel(&path, channel, PowerType::Average)
+            .or_else(|| Self::from_hwmon_path_with_channel(&path, channel, PowerType::Input))
     }
 }
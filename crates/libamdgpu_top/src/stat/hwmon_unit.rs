@@ -0,0 +1,54 @@
+// hwmon exports values in native units, keep them as is and convert in each frontend.
+// ref: https://www.kernel.org/doc/html/latest/hwmon/sysfs-interface.html
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MicroWatt(pub u64);
+
+impl MicroWatt {
+    pub const fn from_watts(w: u32) -> Self {
+        Self(w as u64 * 1_000_000)
+    }
+
+    pub const fn as_watts(&self) -> u32 {
+        (self.0 / 1_000_000) as u32
+    }
+
+    pub const fn as_milliwatts(&self) -> u64 {
+        self.0 / 1_000
+    }
+
+    pub fn as_watts_f64(&self) -> f64 {
+        self.0 as f64 / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliCelsius(pub i64);
+
+impl MilliCelsius {
+    pub const fn as_celsius(&self) -> i64 {
+        self.0 / 1_000
+    }
+
+    pub fn as_celsius_f64(&self) -> f64 {
+        self.0 as f64 / 1_000.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliVolt(pub u32);
+
+impl MilliVolt {
+    pub fn as_volts_f64(&self) -> f64 {
+        self.0 as f64 / 1_000.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliAmpere(pub u32);
+
+impl MilliAmpere {
+    pub fn as_amperes_f64(&self) -> f64 {
+        self.0 as f64 / 1_000.0
+    }
+}
@@ -1,117 +1,63 @@
-// GRBM: Graphics Register Bus Manager
-// ref: https://github.com/ROCm/ROCm/blob/rocm-7.1.1/docs/conceptual/gpu-arch/mi300-mi200-performance-counters.rst#graphics-register-bus-manager-counters
-// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/amdgpu-glossary.html
-
-pub(crate) const GRBM_INDEX: &[(&str, usize)] = &[
-    ("Graphics Pipe", 31),
-    ("Texture Pipe", 14),
-    // ("Command Processor", 29),
-    // ("Global Data Share", 15),
-    ("Shader Export", 20),
-    ("Shader Processor Interpolator", 22),
-    ("Primitive Assembly", 25),
-    ("Depth Block", 26),
-    ("Color Block", 30),
-    ("Vertex Grouper / Tessellator", 17),
-    ("Input Assembly", 19),
-    ("Work Distributor", 21),
-    // ("Barycentric Interpolator", 23),
-];
-
-pub(crate) const GFX10_GRBM_INDEX: &[(&str, usize)] = &[
-    ("Graphics Pipe", 31),
-    ("Texture Pipe", 14),
-    // ("Command Processor", 29),
-    // ("Global Data Share", 15),
-    ("Shader Export", 20),
-    ("Shader Processor Interpolator", 22),
-    ("Primitive Assembly", 25),
-    ("Depth Block", 26),
-    ("Color Block", 30),
-    ("Geometry Engine", 21),
-    // ("Barycentric Interpolator", 23),
-];
-
-pub(crate) const GRBM2_INDEX: &[(&str, usize)] = &[
-    ("RunList Controller", 24),
-    ("Texture Cache", 25),
-    // ("Texture Cache Resident", 26),
-    ("Command Processor -  Fetcher", 28),
-    ("Command Processor -  Compute", 29),
-    ("Command Processor - Graphics", 30),
-];
-
-pub(crate) const GFX9_GRBM2_INDEX: &[(&str, usize)] = &[
-    ("RunList Controller", 24),
-    ("Texture Cache", 25),
-    // ("Texture Cache Resident", 26),
-    ("Unified Translation Cache Level-2", 15), // UTCL2
-    ("Efficiency Arbiter", 16), // EA
-    ("Render Backend Memory Interface", 17), // RMI
-    ("Command Processor -  Fetcher", 28), // CPF
-    ("Command Processor -  Compute", 29), // CPC
-    ("Command Processor - Graphics", 30), // CPG
-];
-
-pub(crate) const GFX10_GRBM2_INDEX: &[(&str, usize)] = &[
-    ("RunList Controller", 24),
-    ("Texture Cache per Pipe", 25),
-    ("Unified Translation Cache Level-2", 15), // UTCL2
-    ("Efficiency Arbiter", 16), // EA
-    ("Render Backend Memory Interface", 17), // RMI
-    ("SDMA", 21),
-    ("Command Processor -  Fetcher", 28), // CPF
-    ("Command Processor -  Compute", 29), // CPC
-    ("Command Processor - Graphics", 30), // CPG
-];
-
-pub(crate) const GFX10_3_GRBM2_INDEX: &[(&str, usize)] = &[
-    ("RunList Controller", 26),
-    ("Texture Cache per Pipe", 27),
-    ("Unified Translation Cache Level-2", 15), // UTCL2
-    ("Efficiency Arbiter", 16), // EA
-    ("Render Backend Memory Interface", 17), // RMI
-    ("SDMA", 21),
-    ("Command Processor -  Fetcher", 28), // CPF
-    ("Command Processor -  Compute", 29), // CPC
-    ("Command Processor - Graphics", 30), // CPG
-];
-
-pub(crate) const GFX12_GRBM2_INDEX: &[(&str, usize)] = &[
-    ("RunList Controller", 26),
-    ("Texture Cache per Pipe", 27),
-    ("Unified Translation Cache Level-2", 15), // UTCL2
-    ("Efficiency Arbiter", 16), // EA
-    ("SDMA", 21),
-    ("Command Processor -  Fetcher", 28), // CPF
-    ("Command Processor -  Compute", 29), // CPC
-    ("Command Processor - Graphics", 30), // CPG
-];
-
 mod cpu_freq;
 pub use cpu_freq::*;
 
 mod perf_counter;
 pub use perf_counter::*;
 
+mod perf_counter_table;
+pub use perf_counter_table::*;
+
+mod grbm_se;
+pub use grbm_se::*;
+
 mod fdinfo;
 pub use fdinfo::*;
 
 mod sensors;
 pub use sensors::*;
 
+mod hwmon_unit;
+pub use hwmon_unit::*;
+
 mod hwmon_power;
 pub(crate) use hwmon_power::*;
 
+mod hwmon_input;
+pub use hwmon_input::*;
+
+mod hwmon_channel;
+pub use hwmon_channel::*;
+
 mod pcie_bw;
 pub use pcie_bw::*;
 
+mod pcie;
+pub use pcie::*;
+
+mod xgmi;
+pub use xgmi::*;
+
+mod partition;
+pub use partition::*;
+
 mod gfxoff_state;
 pub use gfxoff_state::*;
 
 mod gpu_activity;
 pub use gpu_activity::*;
 
+mod dpm_table;
+pub use dpm_table::*;
+
+mod throttle_log;
+pub use throttle_log::*;
+
+mod power_state;
+pub use power_state::*;
+
+mod runtime_pm;
+pub use runtime_pm::*;
+
 pub mod gpu_metrics_util;
 
 pub(crate) fn parse_hwmon<T: std::str::FromStr, P: Into<std::path::PathBuf>>(path: P) -> Option<T> {
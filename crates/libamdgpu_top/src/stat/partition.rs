@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+use crate::PartitionInfo;
+use crate::AMDGPU::{GpuMetrics, MetricsInfo};
+use super::{FdInfoUsage, ProcInfo, ProcUsage};
+
+// Per-partition utilization of the compute partitions (MI300 series)
+//
+//  * XCC busy: `xcp_stats[xcp].gfx_busy_inst[xcc]` of gpu_metrics v1.4/v1.5
+//  * process usage: fdinfo of the processes opening the render node of the partition
+//
+// ref: drivers/gpu/drm/amd/include/kgd_pp_interface.h
+
+#[derive(Debug, Clone, Default)]
+pub struct PartitionUsage {
+    pub index: usize,
+    pub render: PathBuf,
+    /// %, per XCC in the partition
+    pub xcc_busy: Vec<u32>,
+    /// %, sum of the processes
+    pub gfx: i64,
+    /// %, sum of the processes
+    pub compute: i64,
+    /// KiB, sum of the processes
+    pub vram_usage: u64,
+    /// (pid, name)
+    pub processes: Vec<(i32, String)>,
+}
+
+impl PartitionUsage {
+    pub fn avg_xcc_busy(&self) -> Option<u32> {
+        if self.xcc_busy.is_empty() {
+            return None;
+        }
+
+        Some(self.xcc_busy.iter().sum::<u32>() / self.xcc_busy.len() as u32)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PartitionStat {
+    pub partitions: Vec<PartitionUsage>,
+    /// DRM card node, opened by the processes using the first partition
+    card: PathBuf,
+}
+
+impl PartitionStat {
+    pub fn new(info: &PartitionInfo, card: &Path) -> Self {
+        let partitions = info.partitions.iter().map(|p| PartitionUsage {
+            index: p.index,
+            render: p.render.clone(),
+            ..Default::default()
+        }).collect();
+
+        Self { partitions, card: card.to_path_buf() }
+    }
+
+    pub fn update_xcc_busy(&mut self, metrics: &GpuMetrics) {
+        let Some(xcp_stats) = metrics.get_xcp_stats() else { return };
+
+        for (partition, xcp) in self.partitions.iter_mut().zip(xcp_stats.iter()) {
+            partition.xcc_busy = xcp.gfx_busy_inst
+                .iter()
+                .copied()
+                .take_while(|v| *v != u32::MAX)
+                .collect();
+        }
+    }
+
+    pub fn update_usage(&mut self, proc_index: &[ProcInfo], proc_usage: &[ProcUsage]) {
+        for partition in self.partitions.iter_mut() {
+            let is_first = partition.index == 0;
+
+            partition.gfx = 0;
+            partition.compute = 0;
+            partition.vram_usage = 0;
+            partition.processes.clear();
+
+            let is_partition_device = |d: &PathBuf| *d == partition.render || (is_first && *d == self.card);
+            let procs = proc_index.iter().filter(|proc_info| {
+                proc_info.devices.iter().any(|(d, _)| is_partition_device(d))
+            });
+
+            for proc_info in procs {
+                partition.processes.push((proc_info.pid, proc_info.name.clone()));
+
+                let Some(pu) = proc_usage.iter().find(|pu| pu.pid == proc_info.pid) else { continue };
+
+                // usage per DRM node if the process uses multiple partitions
+                let usage = if pu.devices.is_empty() {
+                    pu.usage
+                } else {
+                    pu.devices
+                        .iter()
+                        .filter(|(d, _)| is_partition_device(d))
+                        .fold(FdInfoUsage::default(), |acc, (_, usage)| acc + *usage)
+                };
+
+                partition.gfx += usage.gfx;
+                partition.compute += usage.compute;
+                partition.vram_usage += usage.vram_usage;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+}
+
+#[test]
+fn test_partition_stat() {
+    let mut stat = PartitionStat {
+        partitions: (0..2).map(|i| PartitionUsage {
+            index: i,
+            render: PathBuf::from(format!("/dev/dri/renderD{}", 128 + i * 8)),
+            ..Default::default()
+        }).collect(),
+        card: PathBuf::from("/dev/dri/card0"),
+    };
+    let proc_info = |pid: i32, devices: &[&str]| ProcInfo {
+        pid,
+        name: format!("proc{pid}"),
+        devices: devices.iter().map(|d| (PathBuf::from(d), Vec::new())).collect(),
+        ..Default::default()
+    };
+    let proc_index = [
+        proc_info(1, &["/dev/dri/card0"]),
+        proc_info(2, &["/dev/dri/renderD136"]),
+        proc_info(3, &["/dev/dri/renderD128", "/dev/dri/renderD136"]),
+    ];
+    let usage = |compute: i64| FdInfoUsage { compute, ..Default::default() };
+    let proc_usage = [
+        ProcUsage { pid: 2, usage: usage(10), ..Default::default() },
+        ProcUsage {
+            pid: 3,
+            usage: usage(40),
+            devices: vec![
+                (PathBuf::from("/dev/dri/renderD128"), usage(30)),
+                (PathBuf::from("/dev/dri/renderD136"), usage(10)),
+            ],
+            ..Default::default()
+        },
+    ];
+
+    stat.update_usage(&proc_index, &proc_usage);
+
+    assert_eq!(stat.partitions[0].processes.len(), 2);
+    assert_eq!(stat.partitions[1].processes.len(), 2);
+    assert_eq!(stat.partitions[0].compute, 30);
+    assert_eq!(stat.partitions[1].compute, 20);
+    assert_eq!(stat.partitions[0].avg_xcc_busy(), None);
+}
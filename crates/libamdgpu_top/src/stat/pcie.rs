@@ -0,0 +1,184 @@
+use std::fmt;
+use crate::AMDGPU::{GpuMetrics, MetricsInfo};
+use crate::PCI;
+use super::{PcieBw, Sensors};
+
+// Unified PCIe statistics from the best available source per ASIC.
+//
+//  * bandwidth
+//    * `pcie_bw` (sysfs): dGPUs before Navi10, read in a separate thread (see `PcieBw`)
+//    * gpu_metrics v1.4+ (`pcie_bandwidth_inst`): Instinct MI300 series
+//    * not available on APUs and RDNA dGPUs, there is no source in the driver
+//  * link retraining/replay/NAK counters: gpu_metrics v1.4+
+//  * current link: gpu_metrics v1.x (`pcie_link_{width,speed}`), or `Sensors::current_link`
+//  * min/max link: `Sensors::{min,max}_dpm_link` (`pp_dpm_pcie`)
+//
+// ref: drivers/gpu/drm/amd/include/kgd_pp_interface.h (`gpu_metrics_v1_*`)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcieBwSource {
+    PcieBw,
+    GpuMetrics,
+}
+
+impl fmt::Display for PcieBwSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::PcieBw => "pcie_bw",
+            Self::GpuMetrics => "gpu_metrics",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// Delta of the accumulated counter of gpu_metrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcieCounter {
+    pub label: &'static str,
+    pub value: u64,
+    pub delta: u64,
+    pub total_delta: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PcieStat {
+    pub bw_source: Option<PcieBwSource>,
+    /// MiB/s, only from `pcie_bw`
+    pub sent: Option<u64>,
+    /// MiB/s, only from `pcie_bw`
+    pub received: Option<u64>,
+    /// MiB/s, sent + received for `pcie_bw`
+    pub bandwidth: Option<u64>,
+    pub counters: Vec<PcieCounter>,
+    pub current_link: Option<PCI::LINK>,
+    pub min_link: Option<PCI::LINK>,
+    pub max_link: Option<PCI::LINK>,
+}
+
+impl PcieStat {
+    pub fn new(has_pcie_bw: bool) -> Self {
+        Self {
+            bw_source: has_pcie_bw.then_some(PcieBwSource::PcieBw),
+            ..Default::default()
+        }
+    }
+
+    pub fn has_stat(&self) -> bool {
+        self.bw_source.is_some() || !self.counters.is_empty()
+    }
+
+    /// `pcie_bw`: `None` while the thread is updating it, the last value is kept.
+    /// `metrics`, `sensors`: `None` for the sources not available
+    pub fn update(
+        &mut self,
+        pcie_bw: Option<&PcieBw>,
+        metrics: Option<&GpuMetrics>,
+        sensors: Option<&Sensors>,
+    ) {
+        let metrics_bw = metrics
+            .and_then(|m| m.get_pcie_bandwidth_inst())
+            .filter(|v| *v != u64::MAX)
+            .map(|gb_s| gb_s.saturating_mul(1_000_000_000) >> 20);
+
+        if self.bw_source.is_none() && metrics_bw.is_some() {
+            self.bw_source = Some(PcieBwSource::GpuMetrics);
+        }
+
+        match self.bw_source {
+            Some(PcieBwSource::PcieBw) => if let Some(bw) = pcie_bw {
+                let [sent, received] = [bw.sent, bw.received]
+                    .map(|v| Self::pcie_bw_to_mib_s(v, bw.max_payload_size));
+
+                self.sent = sent;
+                self.received = received;
+                self.bandwidth = sent.zip(received).map(|(s, r)| s + r);
+            },
+            Some(PcieBwSource::GpuMetrics) => self.bandwidth = metrics_bw,
+            None => {},
+        }
+
+        if let Some(m) = metrics {
+            self.update_counters([
+                ("L0 to Recovery", m.get_pcie_l0_to_recov_count_acc()),
+                ("Replay", m.get_pcie_replay_count_acc()),
+                ("Replay Rollover", m.get_pcie_replay_rover_count_acc()),
+                ("NAK Sent", m.get_pcie_nak_sent_count_acc().map(u64::from)),
+                ("NAK Received", m.get_pcie_nak_rcvd_count_acc().map(u64::from)),
+            ]);
+        }
+
+        let metrics_link = metrics.and_then(|m| {
+            let width = m.get_pcie_link_width().filter(|v| *v != u16::MAX && *v != 0)?;
+            let r#gen = Self::speed_to_gen(m.get_pcie_link_speed()?)?;
+
+            Some(PCI::LINK { r#gen, width: width as u8 })
+        });
+
+        self.current_link = metrics_link.or(sensors.and_then(|s| s.current_link));
+        self.min_link = sensors.and_then(|s| s.min_dpm_link);
+        self.max_link = sensors.and_then(|s| s.max_dpm_link.or(s.max_gpu_link));
+    }
+
+    /// for when the device is suspended
+    pub fn update_for_idle(&mut self, sensors: Option<&Sensors>) {
+        self.sent = None;
+        self.received = None;
+        self.bandwidth = None;
+        self.update(None, None, sensors);
+    }
+
+    /// `pcie_bw` reports the number of packets in 1s
+    fn pcie_bw_to_mib_s(count: Option<u64>, max_payload_size: Option<i32>) -> Option<u64> {
+        let mps = max_payload_size.and_then(|mps| u64::try_from(mps).ok())?;
+
+        Some((count? * mps) >> 20)
+    }
+
+    /// `pcie_link_speed` of gpu_metrics is in 0.1 GT/s
+    fn speed_to_gen(speed: u16) -> Option<u8> {
+        let r#gen = match speed {
+            25 => 1,
+            50 => 2,
+            80 => 3,
+            160 => 4,
+            320 => 5,
+            640 => 6,
+            _ => return None,
+        };
+
+        Some(r#gen)
+    }
+
+    fn update_counters<I: IntoIterator<Item = (&'static str, Option<u64>)>>(&mut self, counters: I) {
+        for (label, value) in counters {
+            let Some(value) = value.filter(|v| *v != u64::MAX) else { continue };
+
+            if let Some(c) = self.counters.iter_mut().find(|c| c.label == label) {
+                c.delta = value.saturating_sub(c.value);
+                c.total_delta += c.delta;
+                c.value = value;
+            } else {
+                self.counters.push(PcieCounter { label, value, delta: 0, total_delta: 0 });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pcie_stat() {
+    let mut pcie = PcieStat::new(false);
+
+    pcie.update_counters([("Replay", Some(10)), ("NAK Sent", None)]);
+    pcie.update_counters([("Replay", Some(15)), ("NAK Sent", Some(u64::MAX))]);
+    pcie.update_counters([("Replay", Some(18)), ("NAK Sent", None)]);
+
+    assert_eq!(pcie.counters.len(), 1);
+    assert_eq!((pcie.counters[0].delta, pcie.counters[0].total_delta), (3, 8));
+    assert!(pcie.has_stat());
+
+    assert_eq!(PcieStat::pcie_bw_to_mib_s(Some(4096), Some(256)), Some(1));
+    assert_eq!(PcieStat::pcie_bw_to_mib_s(Some(4096), None), None);
+    assert_eq!(PcieStat::speed_to_gen(160), Some(4));
+    assert_eq!(PcieStat::speed_to_gen(0xFFFF), None);
+}
@@ -46,7 +46,7 @@ impl PcieBw {
         self.max_payload_size = split.next().and_then(|v| v.parse().ok());
     }
 
-    pub fn spawn_update_thread(&self) -> Arc<Mutex<Self>> {
+    pub fn spawn_update_thread(&self, interval: Duration) -> Arc<Mutex<Self>> {
         let arc = Arc::new(Mutex::new(self.clone()));
         let arc_pcie_bw = arc.clone();
         let mut buf_pcie_bw = self.clone();
@@ -65,7 +65,9 @@ impl PcieBw {
                 *pcie_bw = buf_pcie_bw.clone();
             }
 
-            std::thread::sleep(Duration::from_millis(500)); // wait for user input
+            // reading `pcie_bw` already takes 1s
+            let wait = interval.saturating_sub(Duration::from_secs(1)).max(Duration::from_millis(500));
+            std::thread::sleep(wait); // wait for user input
         });
 
         arc_pcie_bw
@@ -4,7 +4,7 @@ use libdrm_amdgpu_sys::AMDGPU::{
     GRBM_OFFSET,
     GRBM2_OFFSET,
 };
-use crate::stat;
+use super::PCRegTable;
 
 #[derive(Clone, Debug)]
 pub struct PCIndex {
@@ -15,37 +15,22 @@ pub struct PCIndex {
 
 #[derive(Clone, Debug)]
 pub struct PerfCounter {
-    pub pc_type: PCType,
+    pub name: String,
+    offset: u32,
     bits: PCAcc,
     pub pc_index: Vec<PCIndex>,
 }
 
 impl PerfCounter {
     pub fn new_with_chip_class(pc_type: PCType, chip_class: CHIP_CLASS) -> Self {
-        let index = match pc_type {
-            PCType::GRBM => {
-                if CHIP_CLASS::GFX10 <= chip_class {
-                    stat::GFX10_GRBM_INDEX
-                } else {
-                    stat::GRBM_INDEX
-                }
-            },
-            PCType::GRBM2 => {
-                if CHIP_CLASS::GFX12 <= chip_class {
-                    stat::GFX12_GRBM2_INDEX
-                } else if CHIP_CLASS::GFX10_3 <= chip_class {
-                    stat::GFX10_3_GRBM2_INDEX
-                } else if CHIP_CLASS::GFX10 <= chip_class {
-                    stat::GFX10_GRBM2_INDEX
-                } else if CHIP_CLASS::GFX9 <= chip_class {
-                    stat::GFX9_GRBM2_INDEX
-                } else {
-                    stat::GRBM2_INDEX
-                }
-            },
-        };
+        let tables = PCRegTable::builtin(chip_class);
+        let table = tables.iter().find(|t| t.name == pc_type.to_string()).unwrap();
+
+        Self::from_table(table)
+    }
 
-        let pc_index = index
+    pub fn from_table(table: &PCRegTable) -> Self {
+        let pc_index = table.bits
             .iter()
             .map(|(name, idx)| {
                 let index = *idx;
@@ -61,14 +46,29 @@ impl PerfCounter {
             .collect();
 
         Self {
-            pc_type,
+            name: table.name.clone(),
+            offset: table.offset,
             bits: PCAcc::default(),
             pc_index,
         }
     }
 
+    /// `GRBM` or `GRBM2`, `None` for the other registers
+    pub fn pc_type(&self) -> Option<PCType> {
+        match self.name.as_str() {
+            "GRBM" => Some(PCType::GRBM),
+            "GRBM2" => Some(PCType::GRBM2),
+            _ => None,
+        }
+    }
+
+    /// Registers not in the allowed list of amdgpu return an error.
+    pub fn check_reg(&self, amdgpu_dev: &DeviceHandle) -> bool {
+        amdgpu_dev.read_mm_registers(self.offset).is_ok()
+    }
+
     pub fn read_reg(&mut self, amdgpu_dev: &DeviceHandle) {
-        if let Ok(out) = amdgpu_dev.read_mm_registers(self.pc_type.offset()) {
+        if let Ok(out) = amdgpu_dev.read_mm_registers(self.offset) {
             self.bits.acc(out);
         }
     }
@@ -79,7 +79,7 @@ impl PerfCounter {
 
     pub fn update_pc_usage(&mut self) {
         for PCIndex { name: _name, index, usage } in self.pc_index.iter_mut() {
-            *usage = self.bits.get(*index);
+            *usage = self.bits.usage(*index);
         }
     }
 }
@@ -122,38 +122,30 @@ impl PCType {
 }
 
 #[derive(Clone, Default, Debug)]
-struct PCAcc([u8; 32]);
+struct PCAcc {
+    bits: [u32; 32],
+    samples: u32,
+}
 
 impl PCAcc {
     pub fn clear(&mut self) {
-        *self = Self([0u8; 32])
+        *self = Self::default()
     }
 
     pub fn acc(&mut self, reg: u32) {
-        *self += Self::from(reg)
-    }
-
-    fn get(&self, index: usize) -> u8 {
-        self.0[index]
-    }
-}
-
-impl From<u32> for PCAcc {
-    fn from(val: u32) -> Self {
-        let mut out = [0u8; 32];
-
-        for (i, o) in out.iter_mut().enumerate() {
-            *o = ((val >> i) & 0b1) as u8;
+        for (i, dst) in self.bits.iter_mut().enumerate() {
+            *dst += (reg >> i) & 0b1;
         }
 
-        Self(out)
+        self.samples += 1;
     }
-}
 
-impl std::ops::AddAssign for PCAcc {
-    fn add_assign(&mut self, other: Self) {
-        for (dst, src) in self.0.iter_mut().zip(other.0.iter()) {
-            *dst += src;
+    // %
+    fn usage(&self, index: usize) -> u8 {
+        if self.samples == 0 {
+            return 0;
         }
+
+        (self.bits[index] * 100 / self.samples) as u8
     }
 }
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use libdrm_amdgpu_sys::AMDGPU::CHIP_CLASS;
+
+// GRBM: Graphics Register Bus Manager
+// ref: https://github.com/ROCm/ROCm/blob/rocm-7.1.1/docs/conceptual/gpu-arch/mi300-mi200-performance-counters.rst#graphics-register-bus-manager-counters
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/amdgpu-glossary.html
+
+// Description of the status registers and their bits, in a subset of TOML.
+//
+// # comment
+// [GRBM]             # name of the register (panel)
+// offset = 0x2004    # dword offset for `amdgpu_read_mm_registers`
+// "Graphics Pipe" = 31
+
+const GFX6_TABLE: &str = include_str!("perf_counter_table/gfx6.toml");
+const GFX9_TABLE: &str = include_str!("perf_counter_table/gfx9.toml");
+const GFX10_TABLE: &str = include_str!("perf_counter_table/gfx10.toml");
+const GFX10_3_TABLE: &str = include_str!("perf_counter_table/gfx10_3.toml");
+const GFX12_TABLE: &str = include_str!("perf_counter_table/gfx12.toml");
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PCRegTable {
+    pub name: String,
+    pub offset: u32,
+    pub bits: Vec<(String, usize)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PCTableError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for PCTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_quote = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quote ^= true,
+            '#' if !in_quote => return &line[..i],
+            _ => {},
+        }
+    }
+
+    line
+}
+
+fn parse_int(s: &str) -> Option<u32> {
+    let s = s.replace('_', "");
+
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        s.parse().ok()
+    }
+}
+
+impl PCRegTable {
+    pub fn parse_tables(s: &str) -> Result<Vec<Self>, PCTableError> {
+        let mut tables: Vec<Self> = Vec::new();
+        let mut offset_set = false;
+
+        for (i, line) in s.lines().enumerate() {
+            let line_no = i + 1;
+            let err = |msg: &str| PCTableError { line: line_no, msg: msg.to_string() };
+            let line = strip_comment(line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                if tables.last().is_some_and(|_| !offset_set) {
+                    return Err(err("previous table has no offset"));
+                }
+
+                tables.push(Self { name: name.trim().to_string(), offset: 0, bits: Vec::new() });
+                offset_set = false;
+                continue;
+            }
+
+            let Some((key, val)) = line.split_once('=') else {
+                return Err(err("expected `key = value`"));
+            };
+            let Some(table) = tables.last_mut() else {
+                return Err(err("key outside of a table"));
+            };
+            let key = key.trim();
+            let Some(val) = parse_int(val.trim()) else {
+                return Err(err("invalid integer value"));
+            };
+
+            if key == "offset" {
+                table.offset = val;
+                offset_set = true;
+                continue;
+            }
+
+            let key = key.strip_prefix('"').and_then(|k| k.strip_suffix('"')).unwrap_or(key);
+
+            if val >= 32 {
+                return Err(err("bit index must be less than 32"));
+            }
+
+            table.bits.push((key.to_string(), val as usize));
+        }
+
+        if tables.last().is_some_and(|_| !offset_set) {
+            return Err(PCTableError { line: s.lines().count(), msg: "last table has no offset".to_string() });
+        }
+
+        Ok(tables)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, PCTableError> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| PCTableError { line: 0, msg: e.to_string() })?;
+
+        Self::parse_tables(&s)
+    }
+
+    pub fn builtin(chip_class: CHIP_CLASS) -> Vec<Self> {
+        let s = if CHIP_CLASS::GFX12 <= chip_class {
+            GFX12_TABLE
+        } else if CHIP_CLASS::GFX10_3 <= chip_class {
+            GFX10_3_TABLE
+        } else if CHIP_CLASS::GFX10 <= chip_class {
+            GFX10_TABLE
+        } else if CHIP_CLASS::GFX9 <= chip_class {
+            GFX9_TABLE
+        } else {
+            GFX6_TABLE
+        };
+
+        Self::parse_tables(s).unwrap()
+    }
+
+    /// The tables with the same name are replaced, others are appended.
+    pub fn merge(tables: &mut Vec<Self>, other: Vec<Self>) {
+        for t in other {
+            if let Some(dst) = tables.iter_mut().find(|dst| dst.name == t.name) {
+                *dst = t;
+            } else {
+                tables.push(t);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_builtin_tables() {
+    for s in [GFX6_TABLE, GFX9_TABLE, GFX10_TABLE, GFX10_3_TABLE, GFX12_TABLE] {
+        let tables = PCRegTable::parse_tables(s).unwrap();
+
+        for name in ["GRBM", "GRBM2", "GRBM_SE0", "CP_STAT"] {
+            assert!(tables.iter().any(|t| t.name == name));
+        }
+    }
+}
+
+#[test]
+fn test_parse_tables() {
+    let s = "[TEST] # comment\noffset = 0x21A0\n\"Busy # 0\" = 31 # CP_BUSY\nQuery = 18\n";
+    let tables = PCRegTable::parse_tables(s).unwrap();
+
+    assert_eq!(tables[0].offset, 0x21A0);
+    assert_eq!(tables[0].bits, [("Busy # 0".to_string(), 31), ("Query".to_string(), 18)]);
+    assert_eq!(PCRegTable::parse_tables("[TEST]\noffset = 1\nA = 32\n").unwrap_err().line, 3);
+    assert!(PCRegTable::parse_tables("[TEST]\nA = 1\n").is_err());
+}
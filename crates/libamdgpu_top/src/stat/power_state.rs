@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+use super::{FdInfoStat, GpuActivity};
+use crate::{SampleSource, SamplingPolicy};
+
+// Reading registers and sensors through the device handle keeps the device active,
+// so amdgpu_top stops touching the device while no process is using it.
+//
+//            idle                                  dGPU && all activities are 0%
+//  Active --------> NoPerfCounter ---------------------------------------------> D3hot
+//    ^   <--------                                                                |
+//    |     busy                                                                   |
+//    +---------------------- busy && the device is resumed (runtime PM) ----------+
+//
+//  "idle": fdinfo shows no engine usage and there is no KFD process.
+//  The AMDKFD driver does not track queues and does not show them in fdinfo.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerState {
+    /// All sources are updated.
+    #[default]
+    Active,
+    /// The perf counters are not read, to allow APU to enter GFXOFF state.
+    /// RDNA 4 GPUs also report 2% GFX usage with PC sampling only.
+    NoPerfCounter,
+    /// Only fdinfo is updated, to allow dGPU to enter D3hot state (runtime suspend).
+    D3hot,
+}
+
+impl PowerState {
+    pub fn allows(&self, src: SampleSource) -> bool {
+        match self {
+            Self::Active => true,
+            Self::NoPerfCounter => src != SampleSource::PerfCounter,
+            Self::D3hot => src == SampleSource::Fdinfo,
+        }
+    }
+
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Active => "monitoring all sources",
+            Self::NoPerfCounter => "perf counters paused while idle",
+            Self::D3hot => "monitoring paused to allow D3hot",
+        }
+    }
+}
+
+impl fmt::Display for PowerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PowerStateInput {
+    /// no usage of GFX, Compute, Media, VPE and DMA in fdinfo
+    pub is_fdinfo_idle: bool,
+    pub has_kfd_process: bool,
+    pub is_all_idling: bool,
+    pub is_apu: bool,
+}
+
+impl PowerStateInput {
+    pub fn new(fdinfo: &FdInfoStat, activity: &GpuActivity, is_apu: bool) -> Self {
+        let (usage, _, _, _) = fdinfo.fold_fdinfo_usage();
+
+        Self {
+            is_fdinfo_idle: usage.gfx == 0
+                && usage.compute == 0
+                && usage.media == 0
+                && usage.vpe == 0
+                && usage.dma == 0,
+            has_kfd_process: fdinfo.has_kfd_process(),
+            is_all_idling: activity.is_all_idling(),
+            is_apu,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.is_fdinfo_idle && !self.has_kfd_process
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerStateTransition {
+    pub from: PowerState,
+    pub to: PowerState,
+    /// elapsed time since the session started
+    pub time: Duration,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for PowerStateTransition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.1}s: {} -> {} ({})",
+            self.time.as_secs_f64(),
+            self.from,
+            self.to,
+            self.reason,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PowerStateMachine {
+    start: Instant,
+    last_update: Duration,
+    pub state: PowerState,
+    pub transitions: VecDeque<PowerStateTransition>,
+}
+
+impl Default for PowerStateMachine {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            last_update: Duration::ZERO,
+            state: PowerState::default(),
+            transitions: VecDeque::with_capacity(Self::MAX_TRANSITIONS),
+        }
+    }
+}
+
+impl PowerStateMachine {
+    pub const MAX_TRANSITIONS: usize = 64;
+
+    pub fn elapsed(&self) -> Duration {
+        self.last_update
+    }
+
+    /// Duration of the current state
+    pub fn duration(&self) -> Duration {
+        let since = self.transitions.back().map_or(Duration::ZERO, |t| t.time);
+
+        self.last_update.saturating_sub(since)
+    }
+
+    /// `is_device_active` is only called to leave D3hot state.
+    pub fn update<F: FnOnce() -> bool>(
+        &mut self,
+        policies: &[SamplingPolicy],
+        input: &PowerStateInput,
+        is_device_active: F,
+    ) -> Option<&PowerStateTransition> {
+        self.update_with_elapsed(self.start.elapsed(), policies, input, is_device_active)
+    }
+
+    pub fn update_with_elapsed<F: FnOnce() -> bool>(
+        &mut self,
+        now: Duration,
+        policies: &[SamplingPolicy],
+        input: &PowerStateInput,
+        is_device_active: F,
+    ) -> Option<&PowerStateTransition> {
+        self.last_update = now;
+
+        let (to, reason) = Self::next_state(self.state, policies, input, is_device_active)?;
+
+        if self.transitions.len() >= Self::MAX_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+
+        self.transitions.push_back(PowerStateTransition { from: self.state, to, time: now, reason });
+        self.state = to;
+
+        self.transitions.back()
+    }
+
+    fn next_state<F: FnOnce() -> bool>(
+        cur: PowerState,
+        policies: &[SamplingPolicy],
+        input: &PowerStateInput,
+        is_device_active: F,
+    ) -> Option<(PowerState, &'static str)> {
+        let is_idle = input.is_idle();
+        let no_pc = policies.contains(&SamplingPolicy::NoPcWhileIdle);
+        let d3hot = policies.contains(&SamplingPolicy::D3hotWhileIdle);
+        let busy_reason = if input.has_kfd_process {
+            "KFD process is running"
+        } else {
+            "process started using the GPU"
+        };
+
+        let next = match cur {
+            PowerState::D3hot if !d3hot => (PowerState::Active, "D3hot policy is disabled"),
+            // The device may still be suspended, wait for it to be resumed by the process.
+            PowerState::D3hot if !is_idle && is_device_active() => (PowerState::Active, busy_reason),
+            PowerState::D3hot => return None,
+            _ if d3hot && is_idle && input.is_all_idling && !input.is_apu => {
+                (PowerState::D3hot, "dGPU is idle")
+            },
+            PowerState::Active if no_pc && is_idle => {
+                (PowerState::NoPerfCounter, "no process is using the GPU")
+            },
+            PowerState::NoPerfCounter if !no_pc => (PowerState::Active, "NoPerfCounter policy is disabled"),
+            PowerState::NoPerfCounter if !is_idle => (PowerState::Active, busy_reason),
+            _ => return None,
+        };
+
+        Some(next).filter(|(to, _)| *to != cur)
+    }
+}
+
+#[test]
+fn test_power_state_machine() {
+    use super::{FdInfoUsage, ProcUsage};
+
+    let secs = Duration::from_secs;
+    let policies = crate::SamplingConfig::default().policies;
+    let proc = |gfx: i64, is_kfd_process: bool| ProcUsage {
+        usage: FdInfoUsage { gfx, ..Default::default() },
+        is_kfd_process,
+        ..Default::default()
+    };
+    let fdinfo = |proc_usage: Vec<ProcUsage>| FdInfoStat { proc_usage, ..Default::default() };
+    let idle_activity = GpuActivity { gfx: Some(0), umc: Some(0), media: None };
+    let busy_activity = GpuActivity { gfx: Some(30), ..idle_activity.clone() };
+
+    let idle = PowerStateInput::new(&fdinfo(vec![proc(0, false)]), &idle_activity, false);
+    let busy = PowerStateInput::new(&fdinfo(vec![proc(50, false)]), &busy_activity, false);
+    let kfd = PowerStateInput::new(&fdinfo(vec![proc(0, true)]), &idle_activity, false);
+    let mut sm = PowerStateMachine::default();
+
+    // GpuActivity is not 0% yet
+    let input = PowerStateInput::new(&fdinfo(vec![]), &busy_activity, false);
+    sm.update_with_elapsed(secs(1), &policies, &input, || unreachable!());
+    assert_eq!(sm.state, PowerState::NoPerfCounter);
+    assert!(!sm.state.allows(SampleSource::PerfCounter));
+
+    sm.update_with_elapsed(secs(2), &policies, &idle, || unreachable!());
+    assert_eq!(sm.state, PowerState::D3hot);
+    assert!(sm.state.allows(SampleSource::Fdinfo));
+    assert!(!sm.state.allows(SampleSource::Sensors));
+
+    // stay in D3hot until the device is resumed
+    assert!(sm.update_with_elapsed(secs(3), &policies, &busy, || false).is_none());
+    let t = sm.update_with_elapsed(secs(4), &policies, &busy, || true).unwrap().clone();
+    assert_eq!((t.from, t.to, t.time), (PowerState::D3hot, PowerState::Active, secs(4)));
+
+    sm.update_with_elapsed(secs(5), &policies, &kfd, || true);
+    assert_eq!(sm.state, PowerState::Active);
+    assert_eq!(sm.transitions.len(), 3);
+    assert_eq!(sm.duration(), secs(1));
+
+    // APU does not enter D3hot
+    let mut sm = PowerStateMachine::default();
+    let apu_idle = PowerStateInput { is_apu: true, ..idle };
+    sm.update_with_elapsed(secs(1), &policies, &apu_idle, || true);
+    assert_eq!(sm.state, PowerState::NoPerfCounter);
+
+    // `--no-d3hot`, `--pc-while-idle`
+    sm.update_with_elapsed(secs(2), &[], &apu_idle, || true);
+    assert_eq!(sm.state, PowerState::Active);
+    sm.update_with_elapsed(secs(3), &[SamplingPolicy::NoPcWhileIdle], &idle, || true);
+    assert_eq!(sm.state, PowerState::NoPerfCounter);
+}
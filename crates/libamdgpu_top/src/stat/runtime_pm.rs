@@ -0,0 +1,200 @@
+// ref: https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-devices-power
+
+// Reading `power/*` does not resume the device,
+// so the runtime PM statistics are updated even while the device is suspended.
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use super::parse_hwmon;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeStatus {
+    Active,
+    Suspended,
+    Suspending,
+    Resuming,
+    Error,
+    Unsupported,
+}
+
+impl RuntimeStatus {
+    pub fn from_sysfs(s: &str) -> Option<Self> {
+        let status = match s.trim() {
+            "active" => Self::Active,
+            "suspended" => Self::Suspended,
+            "suspending" => Self::Suspending,
+            "resuming" => Self::Resuming,
+            "error" => Self::Error,
+            "unsupported" => Self::Unsupported,
+            _ => return None,
+        };
+
+        Some(status)
+    }
+}
+
+impl fmt::Display for RuntimeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimePmControl {
+    /// the device may be suspended by the driver
+    Auto,
+    /// the device is kept active (runtime PM is disabled)
+    On,
+}
+
+impl RuntimePmControl {
+    pub fn from_sysfs(s: &str) -> Option<Self> {
+        match s.trim() {
+            "auto" => Some(Self::Auto),
+            "on" => Some(Self::On),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RuntimePmControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimePm {
+    power_path: PathBuf,
+    pub status: RuntimeStatus,
+    pub control: Option<RuntimePmControl>,
+    /// `None` if the driver does not use autosuspend
+    pub autosuspend_delay_ms: Option<i64>,
+    /// `runtime_active_time`, cumulative since boot
+    pub active_time: Duration,
+    /// `runtime_suspended_time`, cumulative since boot
+    pub suspended_time: Duration,
+    start_active_time: Duration,
+    start_suspended_time: Duration,
+}
+
+impl RuntimePm {
+    /// `None` for env where CONFIG_PM is disabled
+    pub fn new<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let power_path = sysfs_path.into().join("power");
+        let status = fs::read_to_string(power_path.join("runtime_status")).ok()
+            .and_then(|s| RuntimeStatus::from_sysfs(&s))?;
+        let mut runtime_pm = Self {
+            power_path,
+            status,
+            control: None,
+            autosuspend_delay_ms: None,
+            active_time: Duration::ZERO,
+            suspended_time: Duration::ZERO,
+            start_active_time: Duration::ZERO,
+            start_suspended_time: Duration::ZERO,
+        };
+
+        runtime_pm.update();
+        runtime_pm.start_active_time = runtime_pm.active_time;
+        runtime_pm.start_suspended_time = runtime_pm.suspended_time;
+
+        Some(runtime_pm)
+    }
+
+    pub fn update(&mut self) {
+        if let Some(status) = fs::read_to_string(self.power_path.join("runtime_status")).ok()
+            .and_then(|s| RuntimeStatus::from_sysfs(&s))
+        {
+            self.status = status;
+        }
+
+        self.control = fs::read_to_string(self.power_path.join("control")).ok()
+            .and_then(|s| RuntimePmControl::from_sysfs(&s));
+        // returns EIO if autosuspend is not used
+        self.autosuspend_delay_ms = parse_hwmon(self.power_path.join("autosuspend_delay_ms"));
+
+        if let Some(ms) = parse_hwmon::<u64, _>(self.power_path.join("runtime_active_time")) {
+            self.active_time = Duration::from_millis(ms);
+        }
+
+        if let Some(ms) = parse_hwmon::<u64, _>(self.power_path.join("runtime_suspended_time")) {
+            self.suspended_time = Duration::from_millis(ms);
+        }
+    }
+
+    /// Active time since the session started
+    pub fn session_active_time(&self) -> Duration {
+        self.active_time.saturating_sub(self.start_active_time)
+    }
+
+    /// Suspended time since the session started
+    pub fn session_suspended_time(&self) -> Duration {
+        self.suspended_time.saturating_sub(self.start_suspended_time)
+    }
+
+    /// Residency of suspended state over the session
+    pub fn suspended_percent(&self) -> f64 {
+        let suspended = self.session_suspended_time().as_secs_f64();
+        let total = self.session_active_time().as_secs_f64() + suspended;
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        suspended * 100.0 / total
+    }
+
+    /// Residency of active state over the session
+    pub fn active_percent(&self) -> f64 {
+        let active = self.session_active_time().as_secs_f64();
+        let total = active + self.session_suspended_time().as_secs_f64();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        active * 100.0 / total
+    }
+}
+
+#[test]
+fn test_runtime_pm() {
+    let sysfs_path = std::env::temp_dir().join(format!("amdgpu_top_runtime_pm_{}", std::process::id()));
+    let power_path = sysfs_path.join("power");
+    let write = |name: &str, s: &str| fs::write(power_path.join(name), s).unwrap();
+
+    fs::create_dir_all(&power_path).unwrap();
+    assert!(RuntimePm::new(&sysfs_path).is_none());
+
+    write("runtime_status", "active\n");
+    write("control", "auto\n");
+    write("runtime_active_time", "1000\n");
+    write("runtime_suspended_time", "5000\n");
+
+    let mut runtime_pm = RuntimePm::new(&sysfs_path).unwrap();
+    assert_eq!(runtime_pm.control, Some(RuntimePmControl::Auto));
+    assert_eq!(runtime_pm.autosuspend_delay_ms, None);
+    assert_eq!(runtime_pm.suspended_percent(), 0.0);
+
+    write("runtime_status", "suspended\n");
+    write("autosuspend_delay_ms", "5000\n");
+    write("runtime_active_time", "2000\n");
+    write("runtime_suspended_time", "8000\n");
+    runtime_pm.update();
+
+    assert_eq!(runtime_pm.status, RuntimeStatus::Suspended);
+    assert_eq!(runtime_pm.autosuspend_delay_ms, Some(5000));
+    assert_eq!(runtime_pm.session_suspended_time(), Duration::from_secs(3));
+    assert_eq!(runtime_pm.suspended_percent(), 75.0);
+    assert_eq!(runtime_pm.active_percent(), 25.0);
+
+    let _ = fs::remove_dir_all(&sysfs_path);
+}
@@ -11,14 +11,28 @@ use libdrm_amdgpu_sys::{
         DeviceHandle,
         DpmClockRange,
         DpmClockType,
-        HwmonTemp,
         HwmonTempType,
         SENSOR_INFO::SENSOR_TYPE,
         PowerCap,
-        PowerProfile,
     },
 };
-use super::{CpuFreqInfo, parse_hwmon, HwmonPower, PowerType};
+use super::{
+    CpuFreqInfo,
+    DpmTable,
+    parse_hwmon,
+    HwmonChannel,
+    HwmonChannelType,
+    HwmonCurrent,
+    HwmonPower,
+    HwmonTemperature,
+    HwmonVoltage,
+    MicroWatt,
+    MilliAmpere,
+    MilliCelsius,
+    MilliVolt,
+    PowerType,
+};
+use crate::{FanInfo, PerfLevel, PowerProfileMode, PowerProfileTable};
 
 #[derive(Clone, Debug)]
 pub struct Sensors {
@@ -37,20 +51,32 @@ pub struct Sensors {
     pub mclk: Option<u32>,
     pub vddnb: Option<u32>,
     pub vddgfx: Option<u32>,
-    pub edge_temp: Option<HwmonTemp>,
-    pub junction_temp: Option<HwmonTemp>,
-    pub memory_temp: Option<HwmonTemp>,
+    pub voltage_in0: Option<HwmonVoltage>,
+    pub voltage_in1: Option<HwmonVoltage>,
+    pub currents: Vec<HwmonCurrent>,
+    pub edge_temp: Option<HwmonTemperature>,
+    pub junction_temp: Option<HwmonTemperature>,
+    pub memory_temp: Option<HwmonTemperature>,
     pub average_power: Option<HwmonPower>,
     pub input_power: Option<HwmonPower>,
+    pub power2: Option<HwmonPower>, // fastPPT on APUs
     pub power_cap: Option<PowerCap>,
     pub fan_rpm: Option<u32>,
     pub fan_max_rpm: Option<u32>,
+    pub fan_target_rpm: Option<u32>, // same as `fan.target_rpm`
+    pub fan_pwm: Option<u8>, // 0-255, same as `fan.pwm`
+    pub fan: Option<FanInfo>,
+    pub hwmon_channels: Vec<HwmonChannel>,
     pub pci_power_state: Option<String>,
-    pub power_profile: Option<PowerProfile>,
+    /// read at startup and by `update_power_ctrl`
+    pub perf_level: Option<PerfLevel>,
+    /// read at startup and by `update_power_ctrl`
+    pub power_profile_table: Option<PowerProfileTable>,
     pub fclk_dpm: Option<DpmClockRange>,
+    pub dpm_tables: Vec<DpmTable>,
     // pub socclk_dpm: Option<DpmClockRange>,
     k10temp_tctl_path: Option<PathBuf>,
-    pub tctl: Option<i64>, // CPU Temp.
+    pub tctl: Option<MilliCelsius>, // CPU Temp.
     pub all_cpu_core_freq_info: Vec<CpuFreqInfo>,
     pub is_idle: bool,
 }
@@ -108,15 +134,23 @@ impl Sensors {
             amdgpu_dev.sensor_info(SENSOR_TYPE::VDDNB).ok(),
             amdgpu_dev.sensor_info(SENSOR_TYPE::VDDGFX).ok(),
         ];
-        let edge_temp = HwmonTemp::from_hwmon_path(&hwmon_path, HwmonTempType::Edge);
-        let junction_temp = HwmonTemp::from_hwmon_path(&hwmon_path, HwmonTempType::Junction);
-        let memory_temp = HwmonTemp::from_hwmon_path(&hwmon_path, HwmonTempType::Memory);
+        let voltage_in0 = HwmonVoltage::from_hwmon_path(&hwmon_path, 0);
+        let voltage_in1 = HwmonVoltage::from_hwmon_path(&hwmon_path, 1);
+        let currents = HwmonCurrent::all_from_hwmon_path(&hwmon_path);
+        let edge_temp = HwmonTemperature::from_hwmon_path(&hwmon_path, HwmonTempType::Edge);
+        let junction_temp = HwmonTemperature::from_hwmon_path(&hwmon_path, HwmonTempType::Junction);
+        let memory_temp = HwmonTemperature::from_hwmon_path(&hwmon_path, HwmonTempType::Memory);
         let power_cap = PowerCap::from_hwmon_path(&hwmon_path);
         let average_power = HwmonPower::from_hwmon_path_with_type(&hwmon_path, PowerType::Average);
         let input_power = HwmonPower::from_hwmon_path_with_type(&hwmon_path, PowerType::Input);
+        let power2 = HwmonPower::from_hwmon_path_with_channel_any_type(&hwmon_path, 2);
 
         let fan_rpm = parse_hwmon(hwmon_path.join("fan1_input"));
         let fan_max_rpm = parse_hwmon(hwmon_path.join("fan1_max"));
+        let fan = FanInfo::new(&hwmon_path, &sysfs_path);
+        let fan_target_rpm = fan.as_ref().and_then(|fan| fan.target_rpm);
+        let fan_pwm = fan.as_ref().and_then(|fan| fan.pwm);
+        let hwmon_channels = HwmonChannel::get_all_from_hwmon_path(&hwmon_path);
         let gpu_port_path = pci_bus.get_gpu_pcie_port_bus().get_sysfs_path();
         let pci_power_state = if !is_apu {
             fs::read_to_string(gpu_port_path.join("power_state"))
@@ -128,7 +162,8 @@ impl Sensors {
         } else {
             None
         };
-        let power_profile = PowerProfile::get_current_profile_from_sysfs(&sysfs_path);
+        let perf_level = PerfLevel::from_sysfs(&sysfs_path);
+        let power_profile_table = PowerProfileTable::from_sysfs(&sysfs_path);
         let k10temp_path = if is_apu {
             Self::find_k10temp_path()
         } else {
@@ -148,6 +183,7 @@ impl Sensors {
         };
 
         let fclk_dpm = DpmClockRange::from_sysfs(DpmClockType::FCLK, &sysfs_path);
+        let dpm_tables = DpmTable::get_all_from_sysfs(&sysfs_path);
         // let socclk_dpm = DpmClockRange::from_sysfs(DpmClockType::SOCCLK, &sysfs_path);
 
         Some(Self {
@@ -165,18 +201,28 @@ impl Sensors {
             mclk,
             vddnb,
             vddgfx,
+            voltage_in0,
+            voltage_in1,
+            currents,
             edge_temp,
             junction_temp,
             memory_temp,
             average_power,
             input_power,
+            power2,
             power_cap,
             fan_rpm,
             fan_max_rpm,
+            fan_target_rpm,
+            fan_pwm,
+            fan,
+            hwmon_channels,
             gpu_port_path,
             pci_power_state,
-            power_profile,
+            perf_level,
+            power_profile_table,
             fclk_dpm,
+            dpm_tables,
             // socclk_dpm,
             k10temp_tctl_path,
             tctl,
@@ -194,23 +240,96 @@ impl Sensors {
             self.bus_info.get_current_link_info()
         };
 
-        let hwmon_path = &self.hwmon_path;
+        self.power_cap = PowerCap::from_hwmon_path(&self.hwmon_path);
+        self.update_hwmon_sensors();
+        if let Some(fan) = &mut self.fan {
+            fan.update();
+
+            if self.is_idle {
+                fan.update_od();
+            }
+        }
+
+        // `FanInfo` reads `fan1_target` and `pwm1`
+        self.fan_target_rpm = self.fan.as_ref().and_then(|fan| fan.target_rpm);
+        self.fan_pwm = self.fan.as_ref().and_then(|fan| fan.pwm);
 
-        self.edge_temp = HwmonTemp::from_hwmon_path(hwmon_path, HwmonTempType::Edge);
-        self.junction_temp = HwmonTemp::from_hwmon_path(hwmon_path, HwmonTempType::Junction);
-        self.memory_temp = HwmonTemp::from_hwmon_path(hwmon_path, HwmonTempType::Memory);
-        self.power_cap = PowerCap::from_hwmon_path(hwmon_path);
-        self.average_power = HwmonPower::from_hwmon_path_with_type(hwmon_path, PowerType::Average);
-        self.input_power = HwmonPower::from_hwmon_path_with_type(hwmon_path, PowerType::Input);
+        if self.is_idle {
+            self.update_power_ctrl();
+        }
 
-        self.fan_rpm = parse_hwmon(self.hwmon_path.join("fan1_input"));
-        self.power_profile = PowerProfile::get_current_profile_from_sysfs(&self.sysfs_path);
         self.update_pci_power_state();
         self.update_tctl();
-        self.update_all_cpu_core_cur_freq();
         self.is_idle = false;
     }
 
+    /// Re-read `power_dpm_force_performance_level` and `pp_power_profile_mode`,
+    /// call after changing them from TUI/GUI
+    pub fn update_power_ctrl(&mut self) {
+        self.perf_level = PerfLevel::from_sysfs(&self.sysfs_path);
+        self.power_profile_table = PowerProfileTable::from_sysfs(&self.sysfs_path);
+    }
+
+    pub fn power_profile(&self) -> Option<&PowerProfileMode> {
+        self.power_profile_table.as_ref()?.current()
+    }
+
+    // Update `hwmon_channels` and fill the dedicated sensors from them,
+    // to read each `*_input` of hwmon once per update.
+    fn update_hwmon_sensors(&mut self) {
+        for ch in self.hwmon_channels.iter_mut() {
+            ch.update(&self.hwmon_path);
+        }
+
+        let channel = |type_: HwmonChannelType, index: u32| {
+            self.hwmon_channels.iter().find(|ch| ch.type_ == type_ && ch.index == index)
+        };
+        // ref: drivers/gpu/drm/amd/pm/amdgpu_pm.c
+        let temp = |type_: HwmonTempType, index: u32| {
+            let ch = channel(HwmonChannelType::Temp, index)?;
+
+            Some(HwmonTemperature {
+                type_,
+                current: MilliCelsius(ch.input?),
+                critical: ch.crit.map(MilliCelsius),
+                emergency: ch.emergency.map(MilliCelsius),
+            })
+        };
+        let voltage = |index: u32| {
+            let ch = channel(HwmonChannelType::In, index)?;
+
+            Some(HwmonVoltage { channel: index, label: ch.label.clone(), value: MilliVolt(ch.input? as u32) })
+        };
+        let power = |index: u32| {
+            let ch = channel(HwmonChannelType::Power, index)?;
+            let type_ = if ch.input_attr? == "average" { PowerType::Average } else { PowerType::Input };
+
+            Some(HwmonPower { type_, label: ch.label.clone(), value: MicroWatt(ch.input? as u64) })
+        };
+        let power1 = power(1);
+
+        self.voltage_in0 = voltage(0);
+        self.voltage_in1 = voltage(1);
+        // `curr1_*` is the first channel
+        self.currents = (1..).map_while(|index| {
+            let ch = channel(HwmonChannelType::Curr, index)?;
+
+            Some(HwmonCurrent { channel: index, label: ch.label.clone(), value: MilliAmpere(ch.input? as u32) })
+        }).collect();
+        self.edge_temp = temp(HwmonTempType::Edge, 1);
+        self.junction_temp = temp(HwmonTempType::Junction, 2);
+        self.memory_temp = temp(HwmonTempType::Memory, 3);
+        // The power channel reads `power1_average` first, `power1_input` is read only if both exist
+        self.input_power = match &power1 {
+            Some(p) if p.type_ == PowerType::Average =>
+                HwmonPower::from_hwmon_path_with_type(&self.hwmon_path, PowerType::Input),
+            _ => power1.clone(),
+        };
+        self.average_power = power1.filter(|p| p.type_ == PowerType::Average);
+        self.power2 = power(2);
+        self.fan_rpm = channel(HwmonChannelType::Fan, 1).and_then(|ch| ch.input).map(|rpm| rpm as u32);
+    }
+
     pub fn update_for_idle(&mut self) {
         self.current_link = None;
         self.edge_temp = None;
@@ -218,13 +337,34 @@ impl Sensors {
         self.memory_temp = None;
         self.average_power = None;
         self.input_power = None;
+        self.power2 = None;
+        self.voltage_in0 = None;
+        self.voltage_in1 = None;
+        self.currents.clear();
         self.sclk = None;
         self.mclk = None;
         self.vddnb = None;
         self.vddgfx = None;
         self.fan_rpm = None;
-        self.power_profile = None;
+        self.fan_target_rpm = None;
+        self.fan_pwm = None;
+
+        if let Some(fan) = &mut self.fan {
+            fan.clear();
+        }
+
+        for ch in self.hwmon_channels.iter_mut() {
+            ch.input = None;
+        }
+
+        self.perf_level = None;
+        self.power_profile_table = None;
         self.fclk_dpm = None;
+
+        for table in self.dpm_tables.iter_mut() {
+            table.update_for_idle();
+        }
+
         self.is_idle = true;
 
         self.update_pci_power_state();
@@ -248,6 +388,10 @@ impl Sensors {
         self.vddnb = amdgpu_dev.sensor_info(SENSOR_TYPE::VDDNB).ok();
         self.vddgfx = amdgpu_dev.sensor_info(SENSOR_TYPE::VDDGFX).ok();
         self.fclk_dpm = DpmClockRange::from_sysfs(DpmClockType::FCLK, &self.sysfs_path);
+
+        for table in self.dpm_tables.iter_mut() {
+            table.update(&self.sysfs_path);
+        }
         // self.socclk_dpm = DpmClockRange::from_sysfs(DpmClockType::SOCCLK, &self.sysfs_path);
     }
 
@@ -276,10 +420,8 @@ impl Sensors {
         None
     }
 
-    fn get_tctl(tctl_path: &Path) -> Option<i64> {
-        let val = fs::read_to_string(tctl_path).ok()?;
-
-        val.trim_end().parse::<i64>().ok()
+    fn get_tctl(tctl_path: &Path) -> Option<MilliCelsius> {
+        parse_hwmon(tctl_path).map(MilliCelsius)
     }
 
     fn update_tctl(&mut self) {
@@ -288,7 +430,7 @@ impl Sensors {
         }
     }
 
-    fn update_all_cpu_core_cur_freq(&mut self) {
+    pub fn update_all_cpu_core_cur_freq(&mut self) {
         for freq_info in self.all_cpu_core_freq_info.iter_mut() {
             freq_info.update_cur_freq();
         }
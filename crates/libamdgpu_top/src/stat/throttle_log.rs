@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::AMDGPU::{GpuMetrics, MetricsInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleReason {
+    Power,
+    Current,
+    Thermal,
+    Prochot,
+    Other,
+}
+
+impl ThrottleReason {
+    pub const ALL: [Self; 5] = [
+        Self::Power,
+        Self::Current,
+        Self::Thermal,
+        Self::Prochot,
+        Self::Other,
+    ];
+
+    /// Classify the name of the throttler bit
+    /// ref: drivers/gpu/drm/amd/include/kgd_pp_interface.h (SMU_THROTTLER_*)
+    pub fn from_throttler(name: &str) -> Self {
+        if name.starts_with("PROCHOT") {
+            Self::Prochot
+        } else if name.starts_with("PPT") || name.ends_with("PPT") || name.starts_with("SPL") {
+            Self::Power
+        } else if name.starts_with("TDC") || name.starts_with("EDC") {
+            Self::Current
+        } else if name.starts_with("TEMP") || name.starts_with("THM") || name.starts_with("VR_THM") {
+            Self::Thermal
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl fmt::Display for ThrottleReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleEvent {
+    pub throttlers: Vec<String>,
+    /// elapsed time since the session started
+    pub start: Duration,
+    pub end: Option<Duration>,
+}
+
+impl ThrottleEvent {
+    pub fn reasons(&self) -> Vec<ThrottleReason> {
+        ThrottleReason::ALL
+            .into_iter()
+            .filter(|r| self.throttlers.iter().any(|t| ThrottleReason::from_throttler(t) == *r))
+            .collect()
+    }
+
+    pub fn duration(&self, now: Duration) -> Duration {
+        self.end.unwrap_or(now).saturating_sub(self.start)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.end.is_none()
+    }
+}
+
+/// Delta of the `throttle_residency_*` counters (gpu_metrics v3.0, APU)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleResidencyCounter {
+    pub label: &'static str,
+    pub value: u32,
+    pub delta: u32,
+    pub total_delta: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThrottleLog {
+    start: Instant,
+    last_update: Duration,
+    pub events: VecDeque<ThrottleEvent>,
+    /// per-reason residency over the session, in the order of `ThrottleReason::ALL`
+    pub residency: [Duration; 5],
+    pub total: Duration,
+    pub apu_residency: Vec<ThrottleResidencyCounter>,
+}
+
+impl Default for ThrottleLog {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            last_update: Duration::ZERO,
+            events: VecDeque::with_capacity(Self::MAX_EVENTS),
+            residency: [Duration::ZERO; 5],
+            total: Duration::ZERO,
+            apu_residency: Vec::new(),
+        }
+    }
+}
+
+impl ThrottleLog {
+    pub const MAX_EVENTS: usize = 256;
+
+    pub fn elapsed(&self) -> Duration {
+        self.last_update
+    }
+
+    pub fn current(&self) -> Option<&ThrottleEvent> {
+        self.events.back().filter(|e| e.is_active())
+    }
+
+    pub fn residency_percent(&self, reason: ThrottleReason) -> f64 {
+        let Some(i) = ThrottleReason::ALL.iter().position(|r| *r == reason) else { return 0.0 };
+
+        if self.total.is_zero() {
+            return 0.0;
+        }
+
+        self.residency[i].as_secs_f64() * 100.0 / self.total.as_secs_f64()
+    }
+
+    /// `None` for when gpu_metrics is not available (e.g. the device is suspended)
+    pub fn update(&mut self, metrics: Option<&GpuMetrics>) {
+        let throttlers: Vec<String> = metrics
+            .and_then(|m| m.get_throttle_status_info())
+            .map(|thr| thr.get_all_throttler().into_iter().map(|t| t.to_string()).collect())
+            .unwrap_or_default();
+
+        if let Some(m) = metrics {
+            self.update_apu_residency([
+                ("PROCHOT", m.get_throttle_residency_prochot()),
+                ("SPL", m.get_throttle_residency_spl()),
+                ("FPPT", m.get_throttle_residency_fppt()),
+                ("SPPT", m.get_throttle_residency_sppt()),
+                ("THM_CORE", m.get_throttle_residency_thm_core()),
+                ("THM_GFX", m.get_throttle_residency_thm_gfx()),
+                ("THM_SOC", m.get_throttle_residency_thm_soc()),
+            ]);
+        }
+
+        self.update_with_elapsed(self.start.elapsed(), throttlers);
+    }
+
+    /// The interval since the last update is counted for the throttlers of the last update.
+    pub fn update_with_elapsed(&mut self, now: Duration, throttlers: Vec<String>) {
+        let delta = now.saturating_sub(self.last_update);
+
+        if let Some(cur) = self.current() {
+            for reason in cur.reasons() {
+                let i = ThrottleReason::ALL.iter().position(|r| *r == reason).unwrap();
+                self.residency[i] += delta;
+            }
+        }
+
+        self.total += delta;
+        self.last_update = now;
+
+        if self.current().map(|e| &e.throttlers) == Some(&throttlers) {
+            return;
+        }
+
+        if let Some(cur) = self.events.back_mut().filter(|e| e.is_active()) {
+            cur.end = Some(now);
+        }
+
+        if throttlers.is_empty() {
+            return;
+        }
+
+        if self.events.len() >= Self::MAX_EVENTS {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(ThrottleEvent { throttlers, start: now, end: None });
+    }
+
+    fn update_apu_residency<I: IntoIterator<Item = (&'static str, Option<u32>)>>(&mut self, counters: I) {
+        for (label, value) in counters {
+            let Some(value) = value else { continue };
+
+            if let Some(c) = self.apu_residency.iter_mut().find(|c| c.label == label) {
+                c.delta = value.wrapping_sub(c.value);
+                c.total_delta += c.delta as u64;
+                c.value = value;
+            } else {
+                self.apu_residency.push(ThrottleResidencyCounter { label, value, delta: 0, total_delta: 0 });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_throttle_log() {
+    let secs = Duration::from_secs;
+    let thr = |v: &[&str]| -> Vec<String> { v.iter().map(|s| s.to_string()).collect() };
+    let mut log = ThrottleLog::default();
+
+    log.update_with_elapsed(secs(1), thr(&[]));
+    log.update_with_elapsed(secs(2), thr(&["PPT0", "TEMP_HOTSPOT"]));
+    log.update_with_elapsed(secs(4), thr(&["PPT0", "TEMP_HOTSPOT"]));
+    log.update_with_elapsed(secs(5), thr(&["PROCHOT_GFX"]));
+    log.update_with_elapsed(secs(6), thr(&[]));
+    log.update_with_elapsed(secs(10), thr(&[]));
+
+    assert_eq!(log.events.len(), 2);
+    assert_eq!(log.events[0].reasons(), vec![ThrottleReason::Power, ThrottleReason::Thermal]);
+    assert_eq!(log.events[0].duration(log.elapsed()), secs(3));
+    assert_eq!(log.events[1].end, Some(secs(6)));
+    assert!(log.current().is_none());
+    assert_eq!(log.residency_percent(ThrottleReason::Power), 30.0);
+    assert_eq!(log.residency_percent(ThrottleReason::Prochot), 10.0);
+
+    log.update_apu_residency([("SPL", Some(u32::MAX)), ("FPPT", None)]);
+    log.update_apu_residency([("SPL", Some(9)), ("FPPT", None)]);
+    assert_eq!(log.apu_residency.len(), 1);
+    assert_eq!(log.apu_residency[0].delta, 10);
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+use crate::AMDGPU::{GpuMetrics, MetricsInfo};
+
+// Per-link XGMI throughput from the accumulators of gpu_metrics v1.3+ (Instinct MI200/MI300 series).
+// `xgmi_{read,write}_data_acc` are in KB.
+// The index of the link does not tell the peer GPU.
+//
+// ref: drivers/gpu/drm/amd/include/kgd_pp_interface.h
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct XgmiLinkStat {
+    /// KB
+    pub read_acc: u64,
+    /// KB
+    pub write_acc: u64,
+    /// MiB/s
+    pub read: f64,
+    /// MiB/s
+    pub write: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct XgmiStat {
+    pub link_width: Option<u16>,
+    /// Gbps
+    pub link_speed: Option<u16>,
+    pub links: Vec<XgmiLinkStat>,
+    start: Option<Instant>,
+    last_update: Duration,
+}
+
+impl XgmiStat {
+    pub fn has_stat(&self) -> bool {
+        self.link_width.is_some() || !self.links.is_empty()
+    }
+
+    pub fn update(&mut self, metrics: &GpuMetrics) {
+        let now = self.start.get_or_insert_with(Instant::now).elapsed();
+        let read_acc = metrics.get_xgmi_read_data_acc().map(|v| v.to_vec()).unwrap_or_default();
+        let write_acc = metrics.get_xgmi_write_data_acc().map(|v| v.to_vec()).unwrap_or_default();
+
+        self.link_width = metrics.get_xgmi_link_width().filter(|v| *v != u16::MAX && *v != 0);
+        self.link_speed = metrics.get_xgmi_link_speed().filter(|v| *v != u16::MAX && *v != 0);
+        self.update_with_elapsed(now, &read_acc, &write_acc);
+    }
+
+    pub fn update_with_elapsed(&mut self, now: Duration, read_acc: &[u64], write_acc: &[u64]) {
+        let secs = now.saturating_sub(self.last_update).as_secs_f64();
+
+        self.last_update = now;
+
+        let links = read_acc.iter().zip(write_acc).take_while(|(r, w)| **r != u64::MAX && **w != u64::MAX);
+
+        for (i, (read_acc, write_acc)) in links.enumerate() {
+            let Some(link) = self.links.get_mut(i) else {
+                self.links.push(XgmiLinkStat { read_acc: *read_acc, write_acc: *write_acc, ..Default::default() });
+                continue;
+            };
+            let to_mib_s = |cur: u64, prev: u64| if secs == 0.0 {
+                0.0
+            } else {
+                cur.saturating_sub(prev) as f64 / 1024.0 / secs
+            };
+
+            link.read = to_mib_s(*read_acc, link.read_acc);
+            link.write = to_mib_s(*write_acc, link.write_acc);
+            link.read_acc = *read_acc;
+            link.write_acc = *write_acc;
+        }
+    }
+
+    /// MiB/s
+    pub fn total(&self) -> f64 {
+        self.links.iter().map(|l| l.read + l.write).sum()
+    }
+}
+
+#[test]
+fn test_xgmi_stat() {
+    let mut xgmi = XgmiStat::default();
+
+    xgmi.update_with_elapsed(Duration::from_secs(1), &[1024, 0, u64::MAX], &[0, 0, u64::MAX]);
+    assert_eq!(xgmi.links.len(), 2);
+    assert_eq!(xgmi.total(), 0.0);
+
+    xgmi.update_with_elapsed(Duration::from_secs(3), &[1024 * 5, 0, u64::MAX], &[2048, 0, u64::MAX]);
+    assert_eq!(xgmi.links[0].read, 2.0);
+    assert_eq!(xgmi.links[0].write, 1.0);
+    assert_eq!(xgmi.total(), 3.0);
+    assert!(xgmi.has_stat());
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::{DevicePath, KfdTopology, PCI};
+use crate::kfd_topology::parse_properties;
+
+// XGMI hive and multi-GPU topology
+//
+//  * XGMI: `xgmi_device_id`, `xgmi_physical_id`, `xgmi_hive_info/xgmi_hive_id` (sysfs)
+//  * links between GPUs: `io_links` and `p2p_links` of the KFD topology
+//
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_xgmi.c
+// ref: drivers/gpu/drm/amd/amdkfd/kfd_topology.c
+// ref: drivers/gpu/drm/amd/amdkfd/kfd_crat.h
+
+const CRAT_IOLINK_TYPE_PCIEXPRESS: u32 = 2;
+const CRAT_IOLINK_TYPE_XGMI: u32 = 11;
+/// weight per hop of XGMI links
+const KFD_CRAT_XGMI_WEIGHT: u32 = 15;
+
+fn parse_u64<P: AsRef<Path>>(path: P) -> Option<u64> {
+    let s = fs::read_to_string(path).ok()?;
+    let s = s.trim();
+
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct XgmiInfo {
+    /// `None` if the GPU is not in a hive
+    pub hive_id: Option<u64>,
+    pub device_id: u64,
+    pub physical_id: Option<u64>,
+}
+
+impl XgmiInfo {
+    /// `None` if the GPU does not support XGMI
+    pub fn from_sysfs<P: AsRef<Path>>(sysfs_path: P) -> Option<Self> {
+        let sysfs_path = sysfs_path.as_ref();
+        let device_id = parse_u64(sysfs_path.join("xgmi_device_id"))?;
+
+        Some(Self {
+            hive_id: parse_u64(sysfs_path.join("xgmi_hive_info/xgmi_hive_id")).filter(|id| *id != 0),
+            device_id,
+            physical_id: parse_u64(sysfs_path.join("xgmi_physical_id")),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoLinkType {
+    Pcie,
+    Xgmi,
+    Other(u64),
+}
+
+impl From<u64> for IoLinkType {
+    fn from(val: u64) -> Self {
+        match val as u32 {
+            CRAT_IOLINK_TYPE_PCIEXPRESS => Self::Pcie,
+            CRAT_IOLINK_TYPE_XGMI => Self::Xgmi,
+            _ => Self::Other(val),
+        }
+    }
+}
+
+impl fmt::Display for IoLinkType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pcie => write!(f, "PCIe"),
+            Self::Xgmi => write!(f, "XGMI"),
+            Self::Other(t) => write!(f, "Type {t}"),
+        }
+    }
+}
+
+/// `io_links/*/properties` or `p2p_links/*/properties` of the KFD topology node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KfdIoLink {
+    pub link_type: IoLinkType,
+    pub node_from: u64,
+    pub node_to: u64,
+    pub weight: u64,
+    /// MB/s
+    pub min_bandwidth: u64,
+    /// MB/s
+    pub max_bandwidth: u64,
+}
+
+impl KfdIoLink {
+    // "type 11\nversion_major 0\nversion_minor 0\nnode_from 2\nnode_to 3\nweight 20\n..."
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut link_type = None;
+        let [mut node_from, mut node_to] = [None; 2];
+        let [mut weight, mut min_bandwidth, mut max_bandwidth] = [0; 3];
+
+        for (name, val) in parse_properties(s) {
+            match name {
+                "type" => link_type = Some(IoLinkType::from(val)),
+                "node_from" => node_from = Some(val),
+                "node_to" => node_to = Some(val),
+                "weight" => weight = val,
+                "min_bandwidth" => min_bandwidth = val,
+                "max_bandwidth" => max_bandwidth = val,
+                _ => {},
+            }
+        }
+
+        Some(Self {
+            link_type: link_type?,
+            node_from: node_from?,
+            node_to: node_to?,
+            weight,
+            min_bandwidth,
+            max_bandwidth,
+        })
+    }
+
+    /// The number of XGMI hops
+    pub fn hops(&self) -> Option<u64> {
+        (self.link_type == IoLinkType::Xgmi).then(|| (self.weight / KFD_CRAT_XGMI_WEIGHT as u64).max(1))
+    }
+
+    /// "XGMI(1)", "PCIe"
+    pub fn short_label(&self) -> String {
+        match self.hops() {
+            Some(hops) => format!("{}({hops})", self.link_type),
+            None => self.link_type.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TopologyGpu {
+    pub pci: PCI::BUS_INFO,
+    pub name: String,
+    pub kfd_node: Option<u64>,
+    pub xgmi: Option<XgmiInfo>,
+}
+
+/// Links between the GPUs, read once at startup
+#[derive(Debug, Clone, Default)]
+pub struct GpuTopology {
+    pub gpus: Vec<TopologyGpu>,
+    pub links: Vec<KfdIoLink>,
+}
+
+impl GpuTopology {
+    pub fn new(device_path_list: &[DevicePath]) -> Self {
+        let kfd = KfdTopology::new();
+
+        let gpus: Vec<TopologyGpu> = device_path_list
+            .iter()
+            .filter(|device_path| device_path.is_amdgpu())
+            .map(|device_path| {
+                let kfd_node = kfd.find_by_render(&device_path.render).map(|node| node.index);
+
+                TopologyGpu {
+                    pci: device_path.pci,
+                    name: device_path.device_name.clone(),
+                    kfd_node,
+                    xgmi: XgmiInfo::from_sysfs(&device_path.sysfs_path),
+                }
+            })
+            .collect();
+
+        let links = kfd.nodes
+            .into_iter()
+            .flat_map(|node| node.io_links)
+            .filter(|link| {
+                [link.node_from, link.node_to].iter()
+                    .all(|node| gpus.iter().any(|gpu| gpu.kfd_node == Some(*node)))
+            })
+            .collect();
+
+        Self { gpus, links }
+    }
+
+    /// The link from `gpus[from]` to `gpus[to]`, XGMI is preferred
+    pub fn link(&self, from: usize, to: usize) -> Option<&KfdIoLink> {
+        let [from, to] = [from, to].map(|i| self.gpus.get(i)?.kfd_node);
+        let [from, to] = [from?, to?];
+
+        self.links
+            .iter()
+            .filter(|link| link.node_from == from && link.node_to == to)
+            .min_by_key(|link| link.link_type != IoLinkType::Xgmi)
+    }
+
+    /// (hive id, indexes of the GPUs)
+    pub fn hives(&self) -> Vec<(u64, Vec<usize>)> {
+        let mut hives: Vec<(u64, Vec<usize>)> = Vec::new();
+
+        for (i, hive_id) in self.gpus.iter().enumerate().filter_map(|(i, gpu)| Some((i, gpu.xgmi?.hive_id?))) {
+            if let Some((_, indexes)) = hives.iter_mut().find(|(id, _)| *id == hive_id) {
+                indexes.push(i);
+            } else {
+                hives.push((hive_id, vec![i]));
+            }
+        }
+
+        hives
+    }
+
+    pub fn has_multiple_gpus(&self) -> bool {
+        self.gpus.len() > 1
+    }
+}
+
+#[test]
+fn test_kfd_io_link() {
+    let s = "type 11\nversion_major 0\nversion_minor 0\nnode_from 2\nnode_to 3\nweight 30\n\
+        min_latency 0\nmax_latency 0\nmin_bandwidth 50000\nmax_bandwidth 50000\n\
+        recommended_transfer_size 0\nflags 1\n";
+    let link = KfdIoLink::parse(s).unwrap();
+
+    assert_eq!(link.link_type, IoLinkType::Xgmi);
+    assert_eq!((link.node_from, link.node_to), (2, 3));
+    assert_eq!(link.max_bandwidth, 50000);
+    assert_eq!(link.hops(), Some(2));
+    assert_eq!(link.short_label(), "XGMI(2)");
+
+    let pcie = KfdIoLink::parse("type 2\nnode_from 1\nnode_to 0\nweight 20\n").unwrap();
+    assert_eq!(pcie.link_type, IoLinkType::Pcie);
+    assert_eq!(pcie.hops(), None);
+    assert!(KfdIoLink::parse("weight 20\n").is_none());
+}
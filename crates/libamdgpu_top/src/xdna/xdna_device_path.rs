@@ -26,6 +26,7 @@ pub fn find_xdna_device() -> Option<DevicePath> {
         render,
         card,
         accel,
+        partition_render: Vec::new(),
         pci,
         sysfs_path,
         device_id,
@@ -1,4 +1,8 @@
-use libamdgpu_top::{GuiMode, GuiWgpuBackend, PCI};
+use std::path::PathBuf;
+use std::time::Duration;
+#[cfg(feature = "json")]
+use libamdgpu_top::app::AppOption;
+use libamdgpu_top::{GuiMode, GuiWgpuBackend, MIN_INTERVAL, PCI, SamplingConfig, SamplingPolicy, stat::PCRegTable};
 
 pub struct MainOpt {
     pub instance: Option<usize>, // index
@@ -12,11 +16,26 @@ pub struct MainOpt {
     pub opt_dump_mode: OptDumpMode,
     pub single_gpu: bool,
     pub no_pc: bool,
+    pub pc_tables: Vec<PCRegTable>,
     pub is_dark_mode: Option<bool>,
     pub decode_gpu_metrics: Option<String>,
+    pub pp_table_files: Vec<String>,
     pub hide_fdinfo: bool,
     pub wgpu_backend: GuiWgpuBackend,
     pub gui_mode: GuiMode,
+    pub set_fan_curve: Option<String>,
+    pub reset_fan_curve: bool,
+    pub restore_power_settings: bool,
+    pub allow_power_ctrl: bool,
+    pub power_cap: Option<PowerCapOpt>,
+    pub set_od: Option<String>,
+    pub reset_od: bool,
+    pub dry_run: bool,
+    pub sampling: Option<String>,
+    pub sampling_file: Option<String>,
+    pub no_d3hot: bool,
+    pub pc_while_idle: bool,
+    pub power_state_log: Option<PathBuf>,
 }
 
 impl Default for MainOpt {
@@ -33,11 +52,26 @@ impl Default for MainOpt {
             json_iterations: 0,
             single_gpu: false,
             no_pc: false,
+            pc_tables: Vec::new(),
             is_dark_mode: None,
             decode_gpu_metrics: None,
+            pp_table_files: Vec::new(),
             hide_fdinfo: false,
             wgpu_backend: GuiWgpuBackend::Gl,
             gui_mode: GuiMode::Auto,
+            set_fan_curve: None,
+            reset_fan_curve: false,
+            restore_power_settings: false,
+            allow_power_ctrl: false,
+            power_cap: None,
+            set_od: None,
+            reset_od: false,
+            dry_run: false,
+            sampling: None,
+            sampling_file: None,
+            no_d3hot: false,
+            pc_while_idle: false,
+            power_state_log: None,
         }
     }
 }
@@ -65,9 +99,16 @@ pub enum DumpMode {
     Version,
     PPTable,
     Xdna,
+    CheckLink,
     NoDump,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PowerCapOpt {
+    Set(u32), // W
+    Reset,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum OptDumpMode {
     NoOptDump,
@@ -109,23 +150,44 @@ const HELP_MSG: &str = concat!(
     "       The application does not read the performance counter (GRBM, GRBM2)\n",
     "       if this flag is set.\n",
     "       Reading the performance counter may deactivate the power saving feature of APU/GPU.\n",
+    "   --pc-table <Path>\n",
+    "       Load the perf counter table (TOML), tables with the same name replace the built-in ones.\n",
+    "   --pc-while-idle\n",
+    "       Keep reading the performance counter while no process is using the GPU.\n",
+    "   --no-d3hot\n",
+    "       Keep monitoring the idle dGPU instead of pausing it to allow D3hot (runtime suspend).\n",
+    "       The AGT_NO_DROP=1 environment variable is deprecated, and works the same as this flag.\n",
+    "   --power-state-log <Path>\n",
+    "       Append the power state transitions (Active, NoPerfCounter, D3hot) to the file.\n",
     "   -gm, --gpu_metrics, --gpu-metrics\n",
     "       Dump gpu_metrics for all AMD GPUs.\n",
     "       https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#gpu-metrics\n",
     "   --pp_table, --pp-table\n",
-    "       Dump pp_table from sysfs and VBIOS for all AMD GPUs.\n",
-    "       (only support Navi1x and Navi2x, Navi3x)\n",
+    "       Dump pp_table from sysfs and VBIOS for all AMD GPUs, and compare them.\n",
+    "       (the limits for Polaris, Vega and Navi1x-4x, the full table only for Navi1x and Navi2x, Navi3x)\n",
+    "   --pp_table_file, --pp-table-file <Path>\n",
+    "       Decode a saved pp_table file.\n",
+    "       With two files, the second file is compared with the first file.\n",
     "   --drm_info, --drm-info\n",
-    "       Dump DRM info.\n",
+    "       Dump DRM info (connectors, CRTCs, encoders, planes and framebuffers).\n",
     "       Inspired by https://gitlab.freedesktop.org/emersion/drm_info\n",
     "   --xdna\n",
     "       Dump XDNA NPU info.\n",
+    "   --check-link\n",
+    "       Check PCIe link health of all AMD GPUs. (negotiated speed/width of each upstream hop, AER)\n",
+    "       Exits with status 1 if any issue is found. Run it under load, the link may be downclocked while idle.\n",
     "   --dark, --dark-mode\n",
     "       Set to the dark mode. (TUI/GUI)\n",
     "   --light, --light-mode\n",
     "       Set to the light mode. (TUI/GUI)\n",
     "   --hide-fdinfo\n",
     "       Hide fdinfo panel and launch. (TUI)\n",
+    "   --restore-power-settings\n",
+    "       Restore power_dpm_force_performance_level and pp_power_profile_mode on exit,\n",
+    "       if they have been changed from TUI/GUI. (TUI/GUI)\n",
+    "   --allow-power-ctrl\n",
+    "       Allow changing perf_level, power_profile and power_cap in the Power Control panel,\n",
+    "       and committing the overdrive settings in the GUI. (TUI/GUI)\n",
     "   --gl, --opengl\n",
     "       Use OpenGL API to the GUI backend.\n",
     "   --vk, --vulkan\n",
@@ -148,10 +210,38 @@ const HELP_MSG: &str = concat!(
     "       If 0 is specified, it will be an infinite loop. (default: 0)\n",
     "   -u <u64>, --update-process-index <u64>\n",
     "       Update interval in seconds of the process index for fdinfo. (default: 5s)\n",
+    "   --sampling <SOURCE=INTERVAL[:COUNT],..>\n",
+    "       Set the update interval for each source, and the number of samples for the performance counter.\n",
+    "       SOURCE: pc, sensors, metrics, fdinfo, pcie, ras, cpu_freq\n",
+    "       (pcie: PCIe bandwidth, not available on APUs and RDNA dGPUs)\n",
+    "       The other sources use the default interval. (TUI/GUI/SMI: 1s, JSON: \"-s\")\n",
+    "       e.g. --sampling pc=2s:200,fdinfo=500ms,ras=10s\n",
+    "   --sampling-file <Path>\n",
+    "       Load the sampling config from the file, \"<SOURCE>=<INTERVAL>[:<COUNT>]\" per line.\n",
+    "       \"--sampling\" is applied over the file.\n",
     "   --json_fifo, --json-fifo <String>\n",
     "       Output JSON formatted data to FIFO (named pipe) for other application and scripts.\n",
     "   --decode-gm <Path>, --decode-gpu-metrics <Path>\n",
     "       Decode the specified gpu_metrics file.\n",
+    "   --set-fan-curve <temp:speed,..>\n",
+    "       Apply the fan curve (C:%) to the selected GPU. (RDNA 3 and later, requires root)\n",
+    "       The number of points and each value must be within the range reported by the driver.\n",
+    "       e.g. --set-fan-curve 30:20,45:30,60:45,75:70,90:100\n",
+    "   --reset-fan-curve\n",
+    "       Restore the default fan curve of the selected GPU. (requires root)\n",
+    "   --set-power-cap <u32>\n",
+    "       Set the power cap (W) of the selected GPU. (requires root)\n",
+    "       The value is clamped to the min/max reported by the driver.\n",
+    "   --reset-power-cap\n",
+    "       Reset the power cap of the selected GPU to the default value. (requires root)\n",
+    "   --set-od <EDIT,..>\n",
+    "       Stage the OD (pp_od_clk_voltage) settings of the selected GPU, and commit them. (requires root)\n",
+    "       Each edit uses the same format as pp_od_clk_voltage and must be within OD_RANGE.\n",
+    "       e.g. --set-od \"s 1 2600,m 1 1250\", --set-od \"vo -50\", --set-od \"s 200\" (GFX12)\n",
+    "   --reset-od\n",
+    "       Restore the default OD settings of the selected GPU. (requires root)\n",
+    "   --dry-run\n",
+    "       Print the change by \"--set-power-cap\", \"--reset-power-cap\", \"--set-od\" or \"--reset-od\" without applying it.\n",
 );
 
 impl MainOpt {
@@ -233,6 +323,11 @@ impl MainOpt {
                             val_str.parse::<u64>().unwrap()
                         };
 
+                        if tmp != 0 && Duration::from_millis(tmp) < MIN_INTERVAL {
+                            eprintln!("\"-s {val_str}\": the refresh period must be at least {MIN_INTERVAL:?}");
+                            std::process::exit(1);
+                        }
+
                         if tmp != 0 {
                             opt.refresh_period = tmp;
                         }
@@ -352,6 +447,24 @@ impl MainOpt {
                 "--no-pc" => {
                     opt.no_pc = true;
                 },
+                "--pc-table" => {
+                    let Some(path) = args.get(idx+1) else {
+                        eprintln!("missing argument: \"--pc-table <Path>\"");
+                        std::process::exit(1);
+                    };
+
+                    match PCRegTable::from_file(path) {
+                        Ok(tables) => PCRegTable::merge(&mut opt.pc_tables, tables),
+                        Err(e) => {
+                            eprintln!("Failed to load the perf counter table ({path}): {e}");
+                            std::process::exit(1);
+                        },
+                    }
+
+                    skip = true;
+                },
+                "--pc-while-idle" => opt.pc_while_idle = true,
+                "--no-d3hot" => opt.no_d3hot = true,
                 "-gm" | "--gpu-metrics" | "--gpu_metrics" => {
                     opt.opt_dump_mode = OptDumpMode::GpuMetrics;
                 },
@@ -365,6 +478,15 @@ impl MainOpt {
 
                     skip = true;
                 },
+                "--pp-table-file" | "--pp_table_file" => {
+                    let Some(path) = args.get(idx+1) else {
+                        eprintln!("missing argument: \"--pp-table-file <Path>\"");
+                        std::process::exit(1);
+                    };
+
+                    opt.pp_table_files.push(path.to_string());
+                    skip = true;
+                },
                 "--drm-info" | "--drm_info" => {
                     opt.opt_dump_mode = OptDumpMode::DrmInfo;
                 },
@@ -375,11 +497,95 @@ impl MainOpt {
                     opt.is_dark_mode = Some(false);
                 },
                 "--hide-fdinfo" => opt.hide_fdinfo = true,
+                "--restore-power-settings" => opt.restore_power_settings = true,
+                "--allow-power-ctrl" => opt.allow_power_ctrl = true,
                 "--gl" | "--opengl" => opt.wgpu_backend = GuiWgpuBackend::Gl,
                 "--vk" | "--vulkan" => opt.wgpu_backend = GuiWgpuBackend::Vulkan,
                 "--xdna" => {
                     opt.dump_mode = DumpMode::Xdna;
                 },
+                "--check-link" => {
+                    opt.dump_mode = DumpMode::CheckLink;
+                },
+                "--set-fan-curve" => {
+                    opt.set_fan_curve = args.get(idx+1).map(|s| s.to_string());
+
+                    if opt.set_fan_curve.is_none() {
+                        eprintln!("missing argument: \"--set-fan-curve <temp:speed,..>\"");
+                        std::process::exit(1);
+                    }
+
+                    skip = true;
+                },
+                "--reset-fan-curve" => opt.reset_fan_curve = true,
+                "--set-power-cap" => {
+                    if let Some(val_str) = args.get(idx+1) {
+                        let w = val_str.trim_end_matches('W').parse::<u32>().unwrap_or_else(|_| {
+                            eprintln!("Failed to parse {val_str:?} as watts");
+                            std::process::exit(1);
+                        });
+                        opt.power_cap = Some(PowerCapOpt::Set(w));
+                        skip = true;
+                    } else {
+                        eprintln!("missing argument: \"--set-power-cap <u32>\"");
+                        std::process::exit(1);
+                    }
+                },
+                "--reset-power-cap" => opt.power_cap = Some(PowerCapOpt::Reset),
+                "--set-od" => {
+                    opt.set_od = args.get(idx+1).map(|s| s.to_string());
+
+                    if opt.set_od.is_none() {
+                        eprintln!("missing argument: \"--set-od <EDIT,..>\"");
+                        std::process::exit(1);
+                    }
+
+                    skip = true;
+                },
+                "--reset-od" => opt.reset_od = true,
+                "--dry-run" => opt.dry_run = true,
+                "--sampling" => {
+                    if let Some(val_str) = args.get(idx+1) {
+                        if let Err(e) = SamplingConfig::default().apply(val_str) {
+                            eprintln!("Failed to parse \"--sampling {val_str}\": {e}");
+                            std::process::exit(1);
+                        }
+
+                        opt.sampling = Some(val_str.to_string());
+                        skip = true;
+                    } else {
+                        eprintln!("missing argument: \"--sampling <SOURCE=INTERVAL[:COUNT],..>\"");
+                        std::process::exit(1);
+                    }
+                },
+                "--power-state-log" => {
+                    let Some(path) = args.get(idx+1) else {
+                        eprintln!("missing argument: \"--power-state-log <Path>\"");
+                        std::process::exit(1);
+                    };
+
+                    if let Err(e) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+                        eprintln!("Failed to open the power state log ({path}): {e}");
+                        std::process::exit(1);
+                    }
+
+                    opt.power_state_log = Some(PathBuf::from(path));
+                    skip = true;
+                },
+                "--sampling-file" => {
+                    let Some(path) = args.get(idx+1) else {
+                        eprintln!("missing argument: \"--sampling-file <Path>\"");
+                        std::process::exit(1);
+                    };
+
+                    if let Err(e) = SamplingConfig::default().apply_file(path) {
+                        eprintln!("Failed to load the sampling config ({path}): {e}");
+                        std::process::exit(1);
+                    }
+
+                    opt.sampling_file = Some(path.to_string());
+                    skip = true;
+                },
                 _ => {
                     eprintln!("Unknown option: {arg}");
                     std::process::exit(1);
@@ -387,6 +593,50 @@ impl MainOpt {
             }
         }
 
+        // deprecated, replaced by `--no-d3hot`
+        if std::env::var("AGT_NO_DROP").is_ok_and(|s| s == "1") {
+            eprintln!("AGT_NO_DROP is deprecated, use \"--no-d3hot\" instead.");
+            opt.no_d3hot = true;
+        }
+
         opt
     }
+
+    /// Apply "--sampling-file", "--sampling", "--pc-while-idle" and "--no-d3hot" over the default interval
+    pub fn sampling_config(&self, interval: Duration) -> SamplingConfig {
+        let mut config = SamplingConfig::new(interval);
+
+        if let Some(path) = &self.sampling_file {
+            // already checked in `MainOpt::parse`
+            let _ = config.apply_file(path);
+        }
+
+        if let Some(s) = &self.sampling {
+            // already checked in `MainOpt::parse`
+            let _ = config.apply(s);
+        }
+
+        if self.pc_while_idle {
+            config.policies.retain(|p| *p != SamplingPolicy::NoPcWhileIdle);
+        }
+
+        if self.no_d3hot {
+            config.policies.retain(|p| *p != SamplingPolicy::D3hotWhileIdle);
+        }
+
+        config
+    }
+
+    /// for JSON mode
+    #[cfg(feature = "json")]
+    pub fn app_option(&self, interval: Duration) -> AppOption {
+        AppOption {
+            sampling: self.sampling_config(interval),
+            no_pc: self.no_pc,
+            pc_tables: self.pc_tables.clone(),
+            power_state_log: self.power_state_log.clone(),
+            gfxoff_residency: true,
+            ..Default::default()
+        }
+    }
 }
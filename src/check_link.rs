@@ -0,0 +1,61 @@
+use libamdgpu_top::{DevicePath, LinkHealth, LINK_CHECK_LOAD};
+
+/// Returns `true` if any issue is found
+pub fn check_all_link(title: &str, device_path_list: &[DevicePath]) -> bool {
+    println!("{title}\n");
+
+    let mut found = false;
+
+    for (i, device_path) in device_path_list.iter().enumerate() {
+        println!("--------\n#{i} {} ({})", device_path.device_name, device_path.pci);
+        found |= check_link(device_path);
+    }
+
+    found
+}
+
+fn check_link(device_path: &DevicePath) -> bool {
+    let Some(link_health) = LinkHealth::new(&device_path.sysfs_path) else {
+        println!("    The upstream PCIe topology is not found. (APU?)");
+        return false;
+    };
+
+    // a suspended device is idle, do not resume it to read `gpu_busy_percent`
+    let gpu_busy = device_path.check_if_device_is_active().then(|| {
+        std::fs::read_to_string(device_path.sysfs_path.join("gpu_busy_percent")).ok()
+            .and_then(|s| s.trim().parse::<u16>().ok())
+    }).flatten();
+    let is_under_load = gpu_busy.is_some_and(|v| v >= LINK_CHECK_LOAD);
+
+    println!("    Topology (Root Port -> GPU):");
+
+    for hop in &link_health.hops {
+        let [cur, max] = [hop.current_link, hop.max_link]
+            .map(|link| link.map_or("N/A".to_string(), |l| format!("Gen{}x{}", l.r#gen, l.width)));
+
+        println!("      {} {cur:>8} (Max. {max:>8}), {}", hop.bdf, hop.aer);
+    }
+
+    let downgraded = link_health.downgraded_links();
+    // only AER errors are reported without a check under load
+    let aer_issues = &link_health.issues;
+
+    if downgraded.is_empty() && aer_issues.is_empty() {
+        println!("    OK");
+        return false;
+    }
+
+    for issue in &downgraded {
+        if is_under_load {
+            println!("    [WARN] {issue}");
+        } else {
+            println!("    [INFO] {issue} (GPU is idle, the link may be downclocked for power saving)");
+        }
+    }
+
+    for issue in aer_issues {
+        println!("    [WARN] {issue}");
+    }
+
+    (is_under_load && !downgraded.is_empty()) || !aer_issues.is_empty()
+}
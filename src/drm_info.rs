@@ -3,6 +3,13 @@ use libamdgpu_top::{
     drmModePropType,
     // ConnectorInfo,
     ModeProp,
+    Edid,
+    EdidTiming,
+    DisplayIdExtension,
+    DrmObjectsInfo,
+    PlaneInfo,
+    DrmFormatModifier,
+    fourcc_name,
 };
 
 pub fn dump_all_drm_info(device_path_list: &[DevicePath]) {
@@ -14,12 +21,13 @@ pub fn dump_all_drm_info(device_path_list: &[DevicePath]) {
 
 pub fn dump_drm_info(device_path: &DevicePath) {
     let vec_conn_info = libamdgpu_top::connector_info(device_path);
+    let objects = DrmObjectsInfo::new(device_path);
     let len = vec_conn_info.len() - 1;
 
     println!("\nNode: {:?}", device_path.card);
 
     for (i, conn) in vec_conn_info.iter().enumerate() {
-        let last = i == len;
+        let last = i == len && objects.is_empty();
 
         println!(
             "{}───{}",
@@ -57,6 +65,10 @@ pub fn dump_drm_info(device_path: &DevicePath) {
             }
         }
 
+        if let Some(edid) = &conn.edid {
+            dump_edid(edid, last);
+        }
+
         let props_len = conn.mode_props.len() - 1;
 
         for (j, mode_prop) in conn.mode_props.iter().enumerate() {
@@ -64,6 +76,236 @@ pub fn dump_drm_info(device_path: &DevicePath) {
             dump_mode_prop(mode_prop, last, last_prop);
         }
     }
+
+    dump_drm_objects(&objects);
+}
+
+struct TreeNode {
+    label: String,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    fn new<T: Into<String>>(label: T) -> Self {
+        Self { label: label.into(), children: Vec::new() }
+    }
+
+    fn with_children<T: Into<String>>(label: T, children: Vec<TreeNode>) -> Self {
+        Self { label: label.into(), children }
+    }
+
+    fn print(&self, prefix: &str, last: bool) {
+        println!("{prefix}{}───{}", if last { "└" } else { "├" }, self.label);
+
+        let prefix = format!("{prefix}{}    ", if last { " " } else { "│" });
+        let len = self.children.len().saturating_sub(1);
+
+        for (i, child) in self.children.iter().enumerate() {
+            child.print(&prefix, i == len);
+        }
+    }
+}
+
+fn props_node(props: &[(String, u64)]) -> TreeNode {
+    TreeNode::with_children(
+        "Properties",
+        props.iter().map(|(name, value)| TreeNode::new(format!("{name:?}: {value}"))).collect(),
+    )
+}
+
+fn plane_node(plane: &PlaneInfo) -> TreeNode {
+    let formats = |formats: &[u32]| formats.iter().map(|f| fourcc_name(*f)).collect::<Vec<_>>().join(", ");
+    let mut children = Vec::new();
+
+    if let Some(fb) = plane.fb {
+        children.push(TreeNode::new(format!(
+            "FB {}: {}x{}, {}, pitch: {}, modifier: {}",
+            fb.fb_id,
+            fb.width,
+            fb.height,
+            fourcc_name(fb.pixel_format),
+            fb.pitch,
+            fb.modifier.map_or("_".to_string(), |m| DrmFormatModifier(m).to_string()),
+        )));
+    }
+
+    let mut s = Vec::new();
+
+    if let Some(zpos) = plane.zpos {
+        s.push(format!("zpos: {zpos}"));
+    }
+
+    if let Some(rotation) = plane.rotation_string() {
+        s.push(format!("rotation: [{rotation}]"));
+    }
+
+    if let Some(alpha) = plane.alpha {
+        s.push(format!("alpha: {alpha:#06X}"));
+    }
+
+    if !s.is_empty() {
+        children.push(TreeNode::new(s.join(", ")));
+    }
+
+    children.push(TreeNode::new(format!("Formats: {}", formats(&plane.formats))));
+
+    if !plane.in_formats.is_empty() {
+        children.push(TreeNode::with_children(
+            "IN_FORMATS",
+            plane.in_formats.iter().map(|(modifier, f)| TreeNode::new(format!("{modifier}: {}", formats(f)))).collect(),
+        ));
+    }
+
+    children.push(props_node(&plane.props));
+
+    TreeNode::with_children(
+        format!(
+            "Plane {} ({}), CRTC: {}, possible_crtcs: {:#X}",
+            plane.plane_id,
+            plane.plane_type_name(),
+            plane.crtc_id,
+            plane.possible_crtcs,
+        ),
+        children,
+    )
+}
+
+pub fn dump_drm_objects(objects: &DrmObjectsInfo) {
+    let crtcs: Vec<TreeNode> = objects.crtcs.iter().map(|crtc_info| {
+        let crtc = crtc_info.crtc;
+        let label = if crtc.mode_valid != 0 {
+            format!(
+                "CRTC {}: {}x{}@{:.2}, FB: {}",
+                crtc.crtc_id,
+                crtc.mode.hdisplay,
+                crtc.mode.vdisplay,
+                crtc.mode.refresh_rate(),
+                crtc.buffer_id,
+            )
+        } else {
+            format!("CRTC {}: disabled", crtc.crtc_id)
+        };
+
+        TreeNode::with_children(label, vec![props_node(&crtc_info.props)])
+    }).collect();
+    let encoders: Vec<TreeNode> = objects.encoders.iter().map(|e| TreeNode::new(format!(
+        "Encoder {}: {}, CRTC: {}, possible_crtcs: {:#X}, possible_clones: {:#X}",
+        e.encoder_id,
+        e.encoder_type_name(),
+        e.crtc_id,
+        e.possible_crtcs,
+        e.possible_clones,
+    ))).collect();
+    let planes: Vec<TreeNode> = objects.planes.iter().map(plane_node).collect();
+
+    let nodes: Vec<TreeNode> = [
+        ("CRTCs", crtcs),
+        ("Encoders", encoders),
+        ("Planes", planes),
+    ]
+        .into_iter()
+        .filter(|(_, children)| !children.is_empty())
+        .map(|(label, children)| TreeNode::with_children(label, children))
+        .collect();
+    let len = nodes.len().saturating_sub(1);
+
+    for (i, node) in nodes.iter().enumerate() {
+        node.print("", i == len);
+    }
+}
+
+pub fn dump_edid(edid: &Edid, last: bool) {
+    let timings = |t: &[EdidTiming]| t.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ");
+    let mut lines = Vec::new();
+
+    println!(
+        "{}    ├───EDID: {} {:?}, product: {:#06X}, serial: {}, {}{}, version: {}.{}",
+        if last { " " } else { "│" },
+        edid.manufacturer,
+        edid.monitor_name.as_deref().unwrap_or(""),
+        edid.product_code,
+        edid.serial_string.clone().unwrap_or_else(|| edid.serial_number.to_string()),
+        if edid.model_year { "model year " } else { "" },
+        if let Some(week) = edid.week { format!("{} week {week}", edid.year) } else { edid.year.to_string() },
+        edid.version.0,
+        edid.version.1,
+    );
+
+    if let Some(diagonal) = edid.diagonal() {
+        lines.push(format!("Size: {}x{} cm ({diagonal:.1}\")", edid.size.0, edid.size.1));
+    }
+
+    if !edid.established_timings.is_empty() {
+        lines.push(format!("Established timings: {}", edid.established_timings.join(", ")));
+    }
+
+    if !edid.standard_timings.is_empty() {
+        lines.push(format!("Standard timings: {}", timings(&edid.standard_timings)));
+    }
+
+    if !edid.detailed_timings.is_empty() {
+        lines.push(format!("Detailed timings: {}", timings(&edid.detailed_timings)));
+    }
+
+    if let Some(r) = edid.range_limits {
+        lines.push(format!(
+            "Range limits: {}-{} Hz, {}-{} kHz, max pixel clock: {} MHz",
+            r.min_vrate,
+            r.max_vrate,
+            r.min_hrate,
+            r.max_hrate,
+            r.max_pixel_clock,
+        ));
+    }
+
+    if let Some((min, max)) = edid.vrr_range() {
+        lines.push(format!("VRR range: {min}-{max} Hz"));
+    }
+
+    if let Some(cta) = &edid.cta {
+        let mut s = format!("CTA-861 rev. {}, VIC: {:?}", cta.revision, cta.vics);
+
+        if !cta.detailed_timings.is_empty() {
+            s.push_str(&format!(", Detailed timings: {}", timings(&cta.detailed_timings)));
+        }
+
+        if cta.hdmi_forum {
+            s.push_str(", HDMI Forum");
+        }
+
+        lines.push(s);
+    }
+
+    if let Some(hdr) = edid.hdr_static_metadata() {
+        let lum = |v: Option<f32>| v.map(|v| format!("{v:.2}")).unwrap_or_else(|| "_".to_string());
+
+        lines.push(format!(
+            "HDR static metadata: EOTF: [{}], luminance: max {}, max frame-avg {}, min {} cd/m^2",
+            hdr.eotf.join(", "),
+            lum(hdr.max_luminance),
+            lum(hdr.max_frame_avg_luminance),
+            lum(hdr.min_luminance),
+        ));
+    }
+
+    if let Some(displayid) = &edid.displayid {
+        let blocks: Vec<String> = displayid.data_block_tags
+            .iter()
+            .map(|tag| format!("{} ({tag:#04X})", DisplayIdExtension::data_block_name(*tag)))
+            .collect();
+
+        lines.push(format!("DisplayID {}, data blocks: [{}]", displayid.version_string(), blocks.join(", ")));
+    }
+
+    let lines_len = lines.len().saturating_sub(1);
+
+    for (i, line) in lines.iter().enumerate() {
+        println!(
+            "{}    │    {}───{line}",
+            if last { " " } else { "│" },
+            if i == lines_len { "└" } else { "├" },
+        );
+    }
 }
 
 pub fn dump_mode_prop((mode_prop, value): &(ModeProp, u64), last: bool, last_prop: bool) {
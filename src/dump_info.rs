@@ -13,7 +13,13 @@ use libamdgpu_top::{
     AppDeviceInfo,
     // DeviceHandle,
     DevicePath,
+    GpuTopology,
+    KfdNode,
+    KfdTopology,
+    PartitionInfo,
+    RasInfo,
     stat::Sensors,
+    XgmiInfo,
 };
 use crate::{OptDumpMode, drm_info};
 
@@ -48,6 +54,18 @@ pub fn dump_all(title: &str, device_path_list: &[DevicePath], opt_dump_mode: Opt
         println!("\n--------\n#{i} {device_path:#X?}");
         dump(device_path, opt_dump_mode);
     }
+
+    let topology = GpuTopology::new(device_path_list);
+
+    if topology.has_multiple_gpus() {
+        topology_info(&topology);
+    }
+
+    let kfd = KfdTopology::new();
+
+    if !kfd.is_empty() {
+        kfd_agent_info(&kfd, device_path_list);
+    }
 }
 
 pub fn dump(device_path: &DevicePath, opt_dump_mode: OptDumpMode) {
@@ -107,6 +125,18 @@ pub fn dump(device_path: &DevicePath, opt_dump_mode: OptDumpMode) {
         println!("\npp_feature_mask: {pp_feature_mask:#?}");
     }
 
+    if let Some(ras) = &app.stat.ras {
+        ras_info(ras);
+    }
+
+    if let Some(xgmi) = &info.xgmi {
+        xgmi_info(xgmi);
+    }
+
+    if let Some(partition) = &info.partition {
+        partition_info(partition);
+    }
+
     if let OptDumpMode::GpuMetrics = opt_dump_mode {
         if let Some(m) = app.stat.metrics {
             println!("\nGPU Metrics: {m:#?}");
@@ -122,30 +152,263 @@ pub fn dump(device_path: &DevicePath, opt_dump_mode: OptDumpMode) {
     }
 }
 
+fn xgmi_info(xgmi: &XgmiInfo) {
+    println!("\nXGMI:");
+    println!("    Device ID   : {:#x}", xgmi.device_id);
+
+    if let Some(id) = xgmi.physical_id {
+        println!("    Physical ID : {id}");
+    }
+
+    if let Some(id) = xgmi.hive_id {
+        println!("    Hive ID     : {id:#x}");
+    }
+}
+
+fn partition_info(partition: &PartitionInfo) {
+    let modes = |v: Vec<String>| if v.is_empty() { "N/A".to_string() } else { v.join(", ") };
+
+    println!("\nPartition:");
+    println!(
+        "    Compute : {} (Available: {})",
+        partition.compute_mode,
+        modes(partition.available_compute_modes.iter().map(|m| m.to_string()).collect()),
+    );
+    println!(
+        "    Memory  : {} (Available: {})",
+        partition.memory_mode.map_or("N/A".to_string(), |m| m.to_string()),
+        modes(partition.available_memory_modes.iter().map(|m| m.to_string()).collect()),
+    );
+
+    for p in &partition.partitions {
+        println!(
+            "    [{}] {}, KFD Node {}, XCC {:?}",
+            p.index,
+            p.render.display(),
+            p.kfd_node,
+            p.xcc,
+        );
+    }
+}
+
+fn kfd_agent_info(kfd: &KfdTopology, device_path_list: &[DevicePath]) {
+    println!("\n--------\nROCm Agents (KFD Topology):");
+
+    for (i, node) in kfd.nodes.iter().enumerate() {
+        // partitions other than the first one have their own render node
+        let device_path = node.render_path().and_then(|render| device_path_list.iter().find(|d| {
+            d.render == render || d.partition_render.contains(&render)
+        }));
+
+        println!("\nAgent {}", i + 1);
+        kfd_node_info(node, device_path);
+    }
+}
+
+fn kfd_node_info(node: &KfdNode, device_path: Option<&DevicePath>) {
+    let kib = |v: u64| format!("{v} KiB");
+    let name = match node.gfx_target() {
+        Some(gfx) => gfx.to_string(),
+        None if !node.name.is_empty() => node.name.clone(),
+        None => "CPU".to_string(),
+    };
+
+    println!("    Name                : {name}");
+
+    if let Some(device_path) = device_path {
+        println!("    Marketing Name      : {}", device_path.device_name);
+        println!("    Device              : {} ({})", node.render_path().unwrap_or_default().display(), device_path.pci);
+    }
+
+    println!("    Device Type         : {}", if node.is_gpu() { "GPU" } else { "CPU" });
+    println!("    KFD Node            : {}", node.index);
+
+    if node.is_gpu() {
+        println!("    Chip ID             : {:#06x}", node.device_id);
+        println!("    Compute Unit        : {}", node.compute_units());
+        println!("    SIMDs per CU        : {}", node.simd_per_cu);
+        println!("    Wavefront Size      : {}", node.wave_front_size);
+        println!("    Max Waves Per CU    : {}", node.max_waves_per_cu());
+        println!("    Max Clock (MHz)     : {}", node.max_engine_clk_fcompute);
+        println!("    LDS                 : {}", kib(node.lds_size_in_kb));
+        println!("    XCC                 : {}", node.num_xcc);
+        println!(
+            "    SDMA Engines        : {} (XGMI: {})",
+            node.num_sdma_engines,
+            node.num_sdma_xgmi_engines,
+        );
+        println!("    CP Queues           : {}", node.num_cp_queues);
+        println!("    Firmware            : MEC {}, SDMA {}", node.fw_version, node.sdma_fw_version);
+    } else {
+        println!("    Compute Unit        : {}", node.compute_units());
+        println!("    Max Clock (MHz)     : {}", node.max_engine_clk_ccompute);
+    }
+
+    let caches: Vec<String> = node.cache_size_per_level()
+        .iter()
+        .map(|(level, size)| format!("L{level} {}", kib(*size)))
+        .collect();
+
+    if !caches.is_empty() {
+        println!("    Cache               : {}", caches.join(", "));
+    }
+
+    for bank in &node.mem_banks {
+        println!(
+            "    Memory Pool         : {:<11} {:8} MiB, {}-bit, {} MHz",
+            bank.heap_type_name(),
+            bank.size_in_bytes >> 20,
+            bank.width,
+            bank.mem_clk_max,
+        );
+    }
+
+    for link in &node.io_links {
+        println!("    Link                : Node {} -> Node {}, {}", link.node_from, link.node_to, link.short_label());
+    }
+
+    let caps = node.capability_names();
+
+    if !caps.is_empty() {
+        println!("    Capability          : {:#x} {caps:?}", node.capability);
+    }
+}
+
+fn topology_info(topology: &GpuTopology) {
+    const CELL: usize = 9;
+
+    println!("\n--------\nTopology:");
+
+    for (i, gpu) in topology.gpus.iter().enumerate() {
+        print!("    GPU{i:<2}: {} ({})", gpu.name, gpu.pci);
+
+        if let Some(node) = gpu.kfd_node {
+            print!(", KFD Node {node}");
+        }
+
+        if let Some(hive_id) = gpu.xgmi.and_then(|x| x.hive_id) {
+            print!(", XGMI Hive {hive_id:#x}");
+        }
+
+        println!();
+    }
+
+    println!();
+    print!("    {:CELL$}", "");
+
+    for i in 0..topology.gpus.len() {
+        print!("{:>CELL$}", format!("GPU{i}"));
+    }
+
+    println!();
+
+    for i in 0..topology.gpus.len() {
+        print!("    {:CELL$}", format!("GPU{i}"));
+
+        for j in 0..topology.gpus.len() {
+            let cell = if i == j {
+                "-".to_string()
+            } else {
+                topology.link(i, j).map_or("N/A".to_string(), |link| link.short_label())
+            };
+
+            print!("{cell:>CELL$}");
+        }
+
+        println!();
+    }
+
+    println!("\n    Link (hops), Bandwidth (Min - Max):");
+
+    for i in 0..topology.gpus.len() {
+        for j in (0..topology.gpus.len()).filter(|j| *j != i) {
+            let Some(link) = topology.link(i, j) else { continue };
+
+            println!(
+                "    GPU{i} -> GPU{j}: {:<9} {:>6} - {:>6} MB/s",
+                link.short_label(),
+                link.min_bandwidth,
+                link.max_bandwidth,
+            );
+        }
+    }
+}
+
+fn ras_info(ras: &RasInfo) {
+    println!("\nRAS:");
+
+    if let Some(features) = ras.features {
+        println!("    Features             : {features:#x}");
+    }
+
+    if let Some(ver) = ras.eeprom.version {
+        println!("    EEPROM Table Version : {ver:#x}");
+    }
+
+    if let Some(schema) = ras.eeprom.schema {
+        println!("    EEPROM Schema        : {schema:#x}");
+    }
+
+    println!("    {:<10} {:>8} {:>8} {:>8}", "Block", "UE", "CE", "DE");
+
+    for b in &ras.blocks {
+        println!(
+            "    {:<10} {:>8} {:>8} {:>8}",
+            b.block.to_uppercase(),
+            b.ue,
+            b.ce,
+            b.de.map_or("-".to_string(), |de| de.to_string()),
+        );
+    }
+
+    println!("    Retired Pages: {}", ras.bad_pages.len());
+
+    for p in &ras.bad_pages {
+        println!("        {:#014x} ({:#x} bytes): {}", p.address(), p.size, p.status);
+    }
+}
+
 fn sensors_info(sensors: &Sensors) {
     println!();
 
     for temp in [&sensors.edge_temp, &sensors.junction_temp, &sensors.memory_temp] {
         let Some(temp) = temp else { continue };
         let label = format!("{} Temp.", temp.type_);
-        print!("{label:<15} : {:>3} C (Current)", temp.current);
+        print!("{label:<15} : {:>5.1} C (Current)", temp.current.as_celsius_f64());
         if let Some(crit) = &temp.critical {
-            print!(", {crit:>3} C (Critical)");
+            print!(", {:>3} C (Critical)", crit.as_celsius());
         }
         if let Some(e) = &temp.emergency {
-            print!(", {e:>3} C (Emergency)");
+            print!(", {:>3} C (Emergency)", e.as_celsius());
         }
         println!();
     }
 
     if let Some(ref tctl) = sensors.tctl {
-        println!("CPU Tctl        : {:3} C", tctl / 1000);
+        println!("CPU Tctl        : {:5.1} C", tctl.as_celsius_f64());
     }
 
     println!();
     for power in [&sensors.average_power, &sensors.input_power] {
         let Some(power) = power else { continue };
-        println!("Power ({:<7})     : {:3} W", power.type_.to_string(), power.value);
+        println!(
+            "Power ({:<7})     : {:6.2} W",
+            power.type_.to_string(),
+            power.value.as_watts_f64(),
+        );
+    }
+    if let Some(power2) = &sensors.power2 {
+        println!(
+            "Power ({:<7})     : {:6.2} W",
+            power2.label.as_deref().unwrap_or("Power2"),
+            power2.value.as_watts_f64(),
+        );
+    }
+    for v in [&sensors.voltage_in0, &sensors.voltage_in1].into_iter().flatten() {
+        println!("Voltage ({:<7})   : {:4} mV", v.name(), v.value.0);
+    }
+    for c in &sensors.currents {
+        println!("Current ({:<7})   : {:6.3} A", c.name(), c.value.as_amperes_f64());
     }
     if let Some(cap) = &sensors.power_cap {
         println!("Power Cap.          : {:3} W ({}-{} W)", cap.current, cap.min, cap.max);
@@ -157,6 +420,33 @@ fn sensors_info(sensors: &Sensors) {
     if let Some(fan_rpm) = &sensors.fan_rpm {
         println!("Fan RPM (Current)   : {fan_rpm} RPM");
     }
+    if let Some(fan) = &sensors.fan {
+        if let Some(target_rpm) = fan.target_rpm {
+            println!("Fan RPM (Target)    : {target_rpm} RPM");
+        }
+        if let Some(min_rpm) = fan.min_rpm {
+            println!("Fan RPM (Min)       : {min_rpm} RPM");
+        }
+        if let Some(pwm) = fan.pwm_percent() {
+            println!("Fan PWM             : {pwm}%");
+        }
+        if let Some(mode) = fan.pwm_mode {
+            println!("Fan Control Mode    : {mode}");
+        }
+        if let Some(zero_rpm) = fan.zero_rpm_enable {
+            println!("Fan Zero RPM        : {}", zero_rpm.value == 1);
+        }
+        if let Some(stop_temp) = fan.zero_rpm_stop_temp {
+            println!("Fan Zero RPM Stop   : {} C", stop_temp.value);
+        }
+        if let Some(curve) = &fan.fan_curve {
+            print!("Fan Curve           :");
+            for p in &curve.points {
+                print!(" {}C/{}%", p.temp, p.speed);
+            }
+            println!();
+        }
+    }
 
     const PCIE_LABEL: &str = "PCIe Link Speed";
     const PCIE_LEN: usize = 14;
@@ -191,6 +481,15 @@ fn sensors_info(sensors: &Sensors) {
         println!("SOCCLK (DPM): {:>4}MHz ({:>4}-{:>4}MHz)", s.current_mhz, s.min_mhz, s.max_mhz);
     }
 
+    for table in &sensors.dpm_tables {
+        println!("\n{} (DPM):", table.domain);
+
+        for level in &table.levels {
+            let mark = if level.is_active { "*" } else { "" };
+            println!("    {:>2}: {}{mark}", level.label, level.desc);
+        }
+    }
+
     if !sensors.all_cpu_core_freq_info.is_empty() {
         println!("\nCPU Core freq: <MIN>-<CUR>-<MAX>")
     }
@@ -1,11 +1,8 @@
 use libamdgpu_top::{
-    AMDGPU::{
-        self,
-        HwId,
-        IpHwId,
-        PPTable,
-    },
+    AMDGPU::PPTable,
     DevicePath,
+    PpTableComparison,
+    PpTableSources,
 };
 
 pub fn dump_all_pp_table(title: &str, device_path_list: &[DevicePath]) {
@@ -18,8 +15,6 @@ pub fn dump_all_pp_table(title: &str, device_path_list: &[DevicePath]) {
 }
 
 fn dump_pp_table(device_path: &DevicePath) {
-    let Ok(amdgpu_dev) = device_path.init() else { return };
-
     if let [Some(did), Some(rid)] = [device_path.device_id, device_path.revision_id] {
         println!(
             "{} ({}, {did:#0X}:{rid:#0X})",
@@ -28,45 +23,97 @@ fn dump_pp_table(device_path: &DevicePath) {
         );
     }
 
-    let sysfs = &device_path.sysfs_path;
-    let smu = IpHwId::get_from_die_id_sysfs(HwId::MP1, sysfs.join("ip_discovery/die/0/")).ok().and_then(|smu| smu.instances.first().cloned());
+    let sources = PpTableSources::read(device_path);
 
-    if let Some(smu) = &smu {
-        println!("    SMU (MP1) version: {}.{}.{}", smu.major, smu.minor, smu.revision);
+    if let Some((major, minor, rev)) = sources.smu_version {
+        println!("    SMU (MP1) version: {major}.{minor}.{rev}");
     }
 
-    let pp_table_bytes_sysfs = std::fs::read(sysfs.join("pp_table")).ok();
-    let pp_table_bytes_vbios = amdgpu_dev.get_vbios_image().ok().and_then(|vbios_image| {
-        use AMDGPU::VBIOS::VbiosParser;
-
-        let vbios_parser = VbiosParser::new(vbios_image);
-        let rom_header = vbios_parser.get_atom_rom_header()?;
-        let data_table = vbios_parser.get_atom_data_table(&rom_header)?;
-
-        Some(vbios_parser.get_powerplay_table_bytes(&data_table)?.to_vec())
-    });
+    print_pp_tables(&sources.compare());
 
     for (bytes, src) in [
-        (pp_table_bytes_sysfs, "sysfs"),
-        (pp_table_bytes_vbios, "VBIOS"),
+        (&sources.sysfs, "sysfs"),
+        (&sources.vbios, "VBIOS"),
     ] {
-        let Some(bytes) = bytes else {
-            println!("    from {src}: N/A");
-            continue;
-        };
+        let Some(bytes) = bytes else { continue };
 
-        let pp_table = if let Some(smu) = &smu {
-            PPTable::decode_with_smu_version(&bytes, smu.version())
+        let pp_table = if let Some(smu_version) = sources.smu_version {
+            PPTable::decode_with_smu_version(bytes, smu_version)
         } else {
-            PPTable::decode(&bytes)
+            PPTable::decode(bytes)
         };
 
-        if let Ok (pp_table) = &pp_table {
+        if let Ok(pp_table) = &pp_table {
             println!("    from {src}: {pp_table:#?}");
-        } else {
-            println!("    from {src}: N/A");
         }
     }
 
     println!();
 }
+
+/// decode saved pp_table files, the second file is compared with the first file
+pub fn dump_pp_table_files(paths: &[String]) {
+    let files: Vec<(&str, Option<Vec<u8>>)> = paths.iter().map(|path| {
+        let bytes = std::fs::read(path).map_err(|e| eprintln!("{path}: {e}")).ok();
+
+        (path.as_str(), bytes)
+    }).collect();
+    let files: Vec<(&str, Option<&[u8]>)> = files.iter().map(|(path, bytes)| (*path, bytes.as_deref())).collect();
+
+    print_pp_tables(&PpTableComparison::new(&files, None));
+}
+
+/// print the decoded fields side by side, with the modified fields and byte ranges between the first two tables
+fn print_pp_tables(cmp: &PpTableComparison) {
+    for (src, info) in &cmp.tables {
+        let Some(info) = info else {
+            println!("    from {src}: N/A");
+            continue;
+        };
+
+        println!(
+            "    from {src}: {}, {} bytes, format_revision: {}, content_revision: {}, table_revision: {}",
+            info.generation,
+            info.size,
+            info.format_revision,
+            info.content_revision,
+            info.table_revision,
+        );
+    }
+
+    if cmp.names.is_empty() { return }
+
+    let width = cmp.tables.iter().map(|(src, _)| src.len()).max().unwrap_or(0).max(16);
+
+    print!("\n    {:<30}", "Field");
+    for (src, _) in &cmp.tables {
+        print!(" {src:>width$}");
+    }
+    println!();
+
+    for name in &cmp.names {
+        print!("    {name:<30}");
+        for v in cmp.values(name) {
+            let v = v.map_or("-".to_string(), |f| f.to_string());
+            print!(" {v:>width$}");
+        }
+        println!("{}", if cmp.is_modified(name) { "  *" } else { "" });
+    }
+
+    if let Some((fields, ranges)) = &cmp.diff
+        && let [(a_src, _), (b_src, _), ..] = cmp.tables.as_slice()
+    {
+        if ranges.is_empty() {
+            println!("\n    {b_src} is the same as {a_src}");
+        } else {
+            let ranges: Vec<String> = ranges.iter().map(|r| format!("{:#X}..{:#X}", r.start, r.end)).collect();
+
+            println!(
+                "\n    {b_src} is modified from {a_src}: {} fields, {} byte ranges [{}]",
+                fields.len(),
+                ranges.len(),
+                ranges.join(", "),
+            );
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub fn dump_process(title: &str, list: &[DevicePath]) {
 
         stat::update_index_by_all_proc(
             &mut proc_index,
-            &[&device_path.render, &device_path.card],
+            &device_path.drm_paths(),
             &process_list,
         );
 
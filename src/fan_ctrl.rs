@@ -0,0 +1,44 @@
+use libamdgpu_top::{DevicePath, FanCurve};
+
+fn get_fan_curve(device_path: &DevicePath) -> FanCurve {
+    FanCurve::from_sysfs(device_path.pci.get_sysfs_path()).unwrap_or_else(|| {
+        eprintln!("{}: fan_curve is not supported", device_path.pci);
+        std::process::exit(1);
+    })
+}
+
+pub fn set_fan_curve(device_path: &DevicePath, s: &str) {
+    let fan_curve = get_fan_curve(device_path);
+    let Some(points) = FanCurve::points_from_str(s) else {
+        eprintln!("Failed to parse {s:?}, expected \"temp:speed,temp:speed,..\"");
+        std::process::exit(1);
+    };
+
+    if let Err(e) = fan_curve.validate(&points) {
+        eprintln!("Invalid fan curve: {e}");
+        std::process::exit(1);
+    }
+
+    if let Err(e) = fan_curve.apply(device_path.pci.get_sysfs_path(), &points) {
+        eprintln!("Failed to apply the fan curve: {e}");
+        std::process::exit(1);
+    }
+
+    let fan_curve = get_fan_curve(device_path);
+
+    println!("{}: applied the fan curve", device_path.pci);
+    for (i, p) in fan_curve.points.iter().enumerate() {
+        println!("    {i}: {:>3}C {:>3}%", p.temp, p.speed);
+    }
+}
+
+pub fn reset_fan_curve(device_path: &DevicePath) {
+    let _ = get_fan_curve(device_path);
+
+    if let Err(e) = FanCurve::reset(device_path.pci.get_sysfs_path()) {
+        eprintln!("Failed to reset the fan curve: {e}");
+        std::process::exit(1);
+    }
+
+    println!("{}: restored the default fan curve", device_path.pci);
+}
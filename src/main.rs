@@ -1,17 +1,24 @@
+use std::time::Duration;
 use libamdgpu_top::{DevicePath, UiArgs};
+#[cfg(feature = "json")]
+use libamdgpu_top::app::AppOption;
 
 #[cfg(feature = "gui")]
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const TITLE: &str = env!("TITLE");
 
 mod args;
-use args::{AppMode, DumpMode, OptDumpMode, MainOpt};
+use args::{AppMode, DumpMode, OptDumpMode, MainOpt, PowerCapOpt};
 mod dump_info;
 mod dump_pp_table;
 mod dump_process;
 use dump_process::dump_process;
 mod dump_xdna_device;
+mod check_link;
 mod drm_info;
+mod fan_ctrl;
+mod power_cap;
+mod od_ctrl;
 
 fn main() {
     let main_opt = MainOpt::parse();
@@ -30,6 +37,17 @@ fn main() {
         return;
     }
 
+    if !main_opt.pp_table_files.is_empty() {
+        #[cfg(feature = "json")]
+        if let AppMode::JSON = main_opt.app_mode {
+            amdgpu_top_json::pp_table_files_json(&main_opt.pp_table_files);
+            return;
+        }
+
+        dump_pp_table::dump_pp_table_files(&main_opt.pp_table_files);
+        return;
+    }
+
     fn get_list_and_selected_device_path(main_opt: &MainOpt)
         -> (Vec<DevicePath>, DevicePath)
     {
@@ -67,6 +85,36 @@ fn main() {
 
     let (device_path_list, device_path) = get_list_and_selected_device_path(&main_opt);
 
+    if let Some(s) = &main_opt.set_fan_curve {
+        fan_ctrl::set_fan_curve(&device_path, s);
+        return;
+    }
+
+    if main_opt.reset_fan_curve {
+        fan_ctrl::reset_fan_curve(&device_path);
+        return;
+    }
+
+    if let Some(power_cap) = main_opt.power_cap {
+        let watts = match power_cap {
+            PowerCapOpt::Set(w) => Some(w),
+            PowerCapOpt::Reset => None,
+        };
+
+        power_cap::set_power_cap(&device_path, watts, main_opt.dry_run);
+        return;
+    }
+
+    if let Some(s) = &main_opt.set_od {
+        od_ctrl::set_od(&device_path, s, main_opt.dry_run);
+        return;
+    }
+
+    if main_opt.reset_od {
+        od_ctrl::reset_od(&device_path, main_opt.dry_run);
+        return;
+    }
+
     #[cfg(feature = "json")]
     if let AppMode::JSON = main_opt.app_mode { match main_opt.dump_mode {
         DumpMode::Info => {
@@ -78,13 +126,17 @@ fn main() {
             return;
         },
         DumpMode::Process => {
+            let app_opt = AppOption {
+                sampling: libamdgpu_top::SamplingConfig::new(libamdgpu_top::MIN_INTERVAL), // main_opt.refresh_period,
+                no_pc: true, // ui_args.no_pc,
+                ..Default::default()
+            };
             let j = amdgpu_top_json::JsonApp::new(
                 TITLE,
                 &device_path_list,
-                1, // main_opt.refresh_period,
+                app_opt,
                 5, // ui_args.update_process_index,
                 1, // main_opt.json_iterations,
-                true, // ui_args.no_pc,
             );
 
             let s = j.json().to_string();
@@ -92,7 +144,10 @@ fn main() {
 
             return;
         },
-        DumpMode::PPTable => {},
+        DumpMode::PPTable => {
+            amdgpu_top_json::pp_table_json(&device_path_list);
+            return;
+        },
         DumpMode::NoDump => {
             match main_opt.opt_dump_mode {
                 OptDumpMode::GpuMetrics => {
@@ -109,10 +164,9 @@ fn main() {
             let mut j = amdgpu_top_json::JsonApp::new(
                 TITLE,
                 &device_path_list,
-                main_opt.refresh_period,
+                main_opt.app_option(Duration::from_millis(main_opt.refresh_period)),
                 main_opt.update_process_index,
                 main_opt.json_iterations,
-                main_opt.no_pc,
             );
 
             j.run();
@@ -151,6 +205,12 @@ fn main() {
             dump_xdna_device::dump_xdna_device(TITLE);
             return;
         },
+        DumpMode::CheckLink => {
+            if check_link::check_all_link(TITLE, &device_path_list) {
+                std::process::exit(1);
+            }
+            return;
+        },
         DumpMode::NoDump => match main_opt.opt_dump_mode {
             OptDumpMode::GpuMetrics => {
                 dump_info::dump_gpu_metrics(TITLE, &device_path_list);
@@ -169,10 +229,15 @@ fn main() {
         device_path_list,
         update_process_index: main_opt.update_process_index,
         no_pc: main_opt.no_pc,
+        pc_tables: main_opt.pc_tables.clone(),
+        power_state_log: main_opt.power_state_log.clone(),
         is_dark_mode: main_opt.is_dark_mode,
         hide_fdinfo: main_opt.hide_fdinfo,
         gui_wgpu_backend: main_opt.wgpu_backend,
         gui_mode: main_opt.gui_mode,
+        restore_power_settings: main_opt.restore_power_settings,
+        allow_power_ctrl: main_opt.allow_power_ctrl,
+        sampling: main_opt.sampling_config(Duration::from_secs(1)),
     };
 
     match main_opt.app_mode {
@@ -216,10 +281,9 @@ fn main() {
             let mut j = amdgpu_top_json::JsonApp::new(
                 TITLE,
                 &ui_args.device_path_list,
-                main_opt.refresh_period,
+                main_opt.app_option(Duration::from_millis(main_opt.refresh_period)),
                 ui_args.update_process_index,
                 main_opt.json_iterations,
-                ui_args.no_pc,
             );
 
             j.run_fifo(path);
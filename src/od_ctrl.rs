@@ -0,0 +1,62 @@
+use libamdgpu_top::{DevicePath, OdClkVoltage, OdEdit, OdStaging};
+
+fn get_od_staging(device_path: &DevicePath) -> OdStaging {
+    OdStaging::from_sysfs(device_path.pci.get_sysfs_path()).unwrap_or_else(|| {
+        eprintln!("{}: pp_od_clk_voltage is not supported", device_path.pci);
+        std::process::exit(1);
+    })
+}
+
+pub fn set_od(device_path: &DevicePath, s: &str, dry_run: bool) {
+    let mut staging = get_od_staging(device_path);
+
+    for cmd in s.split(',') {
+        let Ok(edit) = cmd.parse::<OdEdit>() else {
+            eprintln!("Failed to parse {cmd:?}, expected \"s <index> <MHz>\", \"m <index> <MHz>\", \"vc <index> <MHz> <mV>\", \"vo <mV>\" or \"s <MHz>\"");
+            std::process::exit(1);
+        };
+
+        if let Err(e) = staging.stage(edit) {
+            eprintln!("Invalid OD setting: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    let diff = staging.diff();
+
+    println!("{}:", device_path.pci);
+
+    if diff.is_empty() {
+        println!("    no changes");
+    }
+
+    for d in &diff {
+        println!("    {d}");
+    }
+
+    if dry_run {
+        println!("dry run, pp_od_clk_voltage is not changed");
+        return;
+    }
+
+    if let Err(e) = staging.commit(device_path.pci.get_sysfs_path()) {
+        eprintln!("Failed to commit the OD settings: {e}");
+        std::process::exit(1);
+    }
+}
+
+pub fn reset_od(device_path: &DevicePath, dry_run: bool) {
+    let _ = get_od_staging(device_path);
+
+    if dry_run {
+        println!("dry run, pp_od_clk_voltage is not changed");
+        return;
+    }
+
+    if let Err(e) = OdClkVoltage::reset(device_path.pci.get_sysfs_path()) {
+        eprintln!("Failed to reset the OD settings: {e}");
+        std::process::exit(1);
+    }
+
+    println!("{}: restored the default OD settings", device_path.pci);
+}
@@ -0,0 +1,28 @@
+use libamdgpu_top::{AMDGPU::PowerCap, DevicePath, PowerCapChange};
+
+/// `None` for resetting to the default value
+pub fn set_power_cap(device_path: &DevicePath, watts: Option<u32>, dry_run: bool) {
+    let Some((hwmon_path, cap)) = device_path.pci.get_hwmon_path()
+        .and_then(|path| PowerCap::from_hwmon_path(&path).map(|cap| (path, cap)))
+    else {
+        eprintln!("{}: power1_cap is not supported", device_path.pci);
+        std::process::exit(1);
+    };
+
+    let change = match watts {
+        Some(w) => PowerCapChange::new(&cap, w),
+        None => PowerCapChange::reset_to_default(&cap),
+    };
+
+    println!("{}: {change} ({}-{} W, Default {} W)", device_path.pci, cap.min, cap.max, cap.default);
+
+    if dry_run {
+        println!("dry run, power1_cap is not changed");
+        return;
+    }
+
+    if let Err(e) = change.apply(&hwmon_path) {
+        eprintln!("Failed to set the power cap: {e}");
+        std::process::exit(1);
+    }
+}